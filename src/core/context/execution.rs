@@ -44,10 +44,14 @@ pub struct ExecutionContext {
                                                    // second arg is the instance number
     vr_instances_count : BTreeMap<usize,u32>,
     active_clocks : HashSet<usize>,
+    // lifelines which are to be created by a '@create' fragment and have not been yet
+    uncreated_lifelines : HashSet<usize>,
     // ********** ********** ********** ********** ********** ********** **********
     interpretation : BTreeMap<usize, BTreeMap<usize,TD_Generic> >,  // key is the lifeline
                                                                     // arg is mapping to current value
-    path_condition : TD_Bool
+    path_condition : TD_Bool,
+    // ********** ********** ********** ********** ********** ********** **********
+    symbol_values : BTreeMap<usize,TD_Generic> // only used when executing concretely i.e. without DIVERSITY
 }
 
 impl ExecutionContext {
@@ -78,8 +82,10 @@ impl ExecutionContext {
             vr_originals:BTreeMap::new(),
             vr_instances_count:BTreeMap::new(),
             active_clocks : gen_ctx.get_clocks().clone(),
+            uncreated_lifelines : gen_ctx.get_created_lfs().clone(),
             interpretation:interpretation,
-            path_condition:TD_Bool::TRUE
+            path_condition:TD_Bool::TRUE,
+            symbol_values:BTreeMap::new()
         }
     }
 
@@ -97,6 +103,21 @@ impl ExecutionContext {
         return self.vr_id_counter;
     }
 
+    pub fn get_sy_num(&self) -> usize {
+        // symbol ids start at 1
+        return self.symbol_counter.saturating_sub(1);
+    }
+
+    pub fn is_lf_created(&self, lf_id : usize) -> bool {
+        return !self.uncreated_lifelines.contains(&lf_id);
+    }
+
+    pub fn create_lifelines(&mut self, lf_ids : &Vec<usize>) {
+        for lf_id in lf_ids {
+            self.uncreated_lifelines.remove(lf_id);
+        }
+    }
+
     pub fn get_active_clocks(&self) -> &HashSet<usize> {
         return &self.active_clocks;
     }
@@ -191,6 +212,20 @@ impl ExecutionContext {
         return new_symbol_id;
     }
 
+    pub fn add_fresh_symbol(&mut self, sy_type : &TD_DataType) -> usize {
+        let new_symbol_id = self.symbol_counter;
+        let sy_fqn = format!("#{}", new_symbol_id);
+        return self.add_diversity_symbol(&sy_fqn, sy_type);
+    }
+
+    pub fn get_symbol_values(&self) -> &BTreeMap<usize,TD_Generic> {
+        return &self.symbol_values;
+    }
+
+    pub fn bind_symbol(&mut self, sy_id : usize, value : TD_Generic) {
+        self.symbol_values.insert(sy_id, value);
+    }
+
     pub fn get_sy_diversity_name(&self, sy_id: usize) -> Result<String, HibouCoreError> {
         match self.symbol_diversity_names.get(&sy_id) {
             None => {
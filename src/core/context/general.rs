@@ -9,9 +9,10 @@
  * SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
  ********************************************************************************/
 
-use std::collections::HashSet;
+use std::collections::{HashSet,HashMap};
 
 use crate::core::syntax::data::td_type::TD_DataType;
+use crate::core::syntax::action::CommunicationKind;
 
 use crate::core::error::HibouCoreError;
 
@@ -19,7 +20,17 @@ use crate::core::error::HibouCoreError;
 pub struct GeneralContext {
     lf_groups : Vec<String>,
     lf_names : Vec<String>,
+    // lifelines which only exist once created by a '@create' fragment
+    created_lfs : HashSet<usize>,
+    // key is the lifeline, value is the group it was created in
+    lf_group_members : HashMap<usize,usize>,
+    // labels of the references to named sub-interactions i.e. their name followed by their arguments
+    ref_labels : Vec<String>,
+    // requirement identifiers or labels annotating actions and fragments
+    tags : Vec<String>,
     ms_specs : Vec< (String,Vec<(TD_DataType,Option<String>)>) >,
+    // only for the messages which are used as synchronous calls returning a value
+    ms_return_types : HashMap<usize,TD_DataType>,
     vr_names : Vec<String>,
     vr_types : Vec<TD_DataType>,
     clocks : HashSet<usize>
@@ -33,7 +44,12 @@ impl GeneralContext {
         return GeneralContext{
             lf_groups:Vec::new(),
             lf_names:Vec::new(),
+            created_lfs:HashSet::new(),
+            lf_group_members:HashMap::new(),
+            ref_labels:Vec::new(),
+            tags:Vec::new(),
             ms_specs:Vec::new(),
+            ms_return_types:HashMap::new(),
             vr_names:Vec::new(),
             vr_types:Vec::new(),
             clocks:HashSet::new()};
@@ -67,6 +83,30 @@ impl GeneralContext {
         self.clocks.insert(vr_id);
     }
 
+    pub fn add_ref_label(&mut self, ref_label : String) -> usize {
+        match self.ref_labels.iter().position(|rl| *rl == ref_label) {
+            None => {
+                self.ref_labels.push(ref_label);
+                return self.ref_labels.len() - 1;
+            },
+            Some( ref_id ) => {
+                return ref_id;
+            }
+        }
+    }
+
+    pub fn add_tag(&mut self, tag : String) -> usize {
+        match self.get_tag_id(&tag) {
+            None => {
+                self.tags.push(tag);
+                return self.tags.len() - 1;
+            },
+            Some( tag_id ) => {
+                return tag_id;
+            }
+        }
+    }
+
     pub fn add_lf_group(&mut self, lgr_name : String) -> usize {
         match self.get_lf_id( &lgr_name ) {
             None => {
@@ -105,19 +145,41 @@ impl GeneralContext {
         }
     }
 
-    pub fn add_msg(&mut self, ms_name : String, ms_spec : Vec<(TD_DataType,Option<String>)>) -> usize {
-        for (got_ms_name,got_ms_spec) in &self.ms_specs {
-            if got_ms_name == &ms_name {
-                panic!();
+    pub fn add_created_lf(&mut self, lf_id : usize) {
+        self.created_lfs.insert(lf_id);
+    }
+
+    pub fn set_lf_group(&mut self, lf_id : usize, lgr_id : usize) {
+        self.lf_group_members.insert(lf_id, lgr_id);
+    }
+
+    pub fn add_msg(&mut self, ms_name : String, ms_spec : Vec<(TD_DataType,Option<String>)>) -> Result<usize,HibouCoreError> {
+        match self.get_ms_id(&ms_name) {
+            None => {
+                self.ms_specs.push( (ms_name, ms_spec) );
+                return Ok( self.ms_specs.len() - 1 );
+            },
+            Some( _ ) => {
+                return Err( HibouCoreError::NameCollision(ms_name) );
             }
         }
-        self.ms_specs.push( (ms_name, ms_spec) );
-        return self.ms_specs.len() - 1;
     }
 
-    pub fn add_vr(&mut self, vr_name : String, vr_type : TD_DataType) {
-        self.vr_names.push(vr_name);
-        self.vr_types.push(vr_type);
+    pub fn set_ms_return_type(&mut self, ms_id : usize, return_type : TD_DataType) {
+        self.ms_return_types.insert(ms_id, return_type);
+    }
+
+    pub fn add_vr(&mut self, vr_name : String, vr_type : TD_DataType) -> Result<usize,HibouCoreError> {
+        match self.get_vr_id(&vr_name) {
+            None => {
+                self.vr_names.push(vr_name);
+                self.vr_types.push(vr_type);
+                return Ok( self.vr_names.len() - 1 );
+            },
+            Some( _ ) => {
+                return Err( HibouCoreError::NameCollision(vr_name) );
+            }
+        }
     }
 
     // ********** ********** ********** ********** ********** ********** **********
@@ -128,6 +190,10 @@ impl GeneralContext {
         return self.lf_groups.iter().position(|gn| gn == lgr_name);
     }
 
+    pub fn get_tag_id(&self, tag : &str) -> Option<usize> {
+        return self.tags.iter().position(|t| t == tag);
+    }
+
     pub fn get_lf_id(&self, lf_name : &str) -> Option<usize> {
         return self.lf_names.iter().position(|r| r == lf_name);
     }
@@ -144,6 +210,22 @@ impl GeneralContext {
         return self.clocks.contains( &vr_id );
     }
 
+    pub fn get_created_lfs(&self) -> &HashSet<usize> {
+        return &self.created_lfs;
+    }
+
+    pub fn is_created_lf(&self, lf_id : usize) -> bool {
+        return self.created_lfs.contains( &lf_id );
+    }
+
+    pub fn get_lf_group(&self, lf_id : usize) -> Option<usize> {
+        return self.lf_group_members.get( &lf_id ).cloned();
+    }
+
+    pub fn get_lgr_lifelines(&self, lgr_id : usize) -> HashSet<usize> {
+        return self.lf_group_members.iter().filter(|(_,gr)| **gr == lgr_id).map(|(lf,_)| *lf).collect();
+    }
+
     // ********** ********** ********** ********** ********** ********** **********
     // ********** ********** ********** ********** ********** ********** **********
     // ********** ********** ********** ********** ********** ********** **********
@@ -164,6 +246,32 @@ impl GeneralContext {
     // ********** ********** ********** ********** ********** ********** **********
     // ********** ********** ********** ********** ********** ********** **********
 
+    pub fn get_ref_label(&self, ref_id : usize) -> Result<String,HibouCoreError> {
+        match self.ref_labels.get(ref_id) {
+            None => {
+                return Err( HibouCoreError::UnknownReference(ref_id) );
+            },
+            Some( got_str ) => {
+                return Ok( got_str.to_string() );
+            }
+        }
+    }
+
+    pub fn get_tag(&self, tag_id : usize) -> Result<String,HibouCoreError> {
+        match self.tags.get(tag_id) {
+            None => {
+                return Err( HibouCoreError::UnknownTag(tag_id) );
+            },
+            Some( got_str ) => {
+                return Ok( got_str.to_string() );
+            }
+        }
+    }
+
+    pub fn get_tags(&self) -> &Vec<String> {
+        return &self.tags;
+    }
+
     pub fn get_lgr_name(&self, lgr_id : usize) -> Result<String,HibouCoreError> {
         match self.lf_groups.get(lgr_id) {
             None => {
@@ -208,6 +316,10 @@ impl GeneralContext {
         }
     }
 
+    pub fn get_ms_return_type(&self, ms_id : usize) -> Option<TD_DataType> {
+        return self.ms_return_types.get(&ms_id).cloned();
+    }
+
     pub fn get_pr_type(&self, ms_id : usize, pr_id : usize) -> Result<TD_DataType,HibouCoreError> {
         match self.ms_specs.get(ms_id) {
             None => {
@@ -226,6 +338,27 @@ impl GeneralContext {
         }
     }
 
+    /*
+        The only parameter carried by a reply is the value returned by the callee
+    */
+    pub fn get_action_pr_type(&self, ms_id : usize, com_kind : &CommunicationKind, pr_id : usize) -> Result<TD_DataType,HibouCoreError> {
+        match com_kind {
+            CommunicationKind::Reply => {
+                match (self.get_ms_return_type(ms_id), pr_id) {
+                    (Some(return_type),0) => {
+                        return Ok(return_type);
+                    },
+                    _ => {
+                        return Err( HibouCoreError::UnknownParameter(pr_id) );
+                    }
+                }
+            },
+            _ => {
+                return self.get_pr_type(ms_id,pr_id);
+            }
+        }
+    }
+
     pub fn get_vr_name(&self, vr_id : usize) -> Result<String,HibouCoreError> {
         match self.vr_names.get(vr_id) {
             None => {
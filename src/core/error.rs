@@ -28,6 +28,9 @@ pub enum HibouCoreError {
     PruningError,
     PositionError(Interaction,Position),
     UnknownLifelineGroup(usize),
+    UnknownReference(usize),
+    UnknownTag(usize),
+    NameCollision(String),
     UnknownLifeline(usize),
     UnknownMessage(usize),
     UnknownParameter(usize),
@@ -38,7 +41,15 @@ pub enum HibouCoreError {
     WronglyTypedGrpcInputOperation(Operation),
     WronglyTypedGrpcInput(String, TD_DataType, String),
     UnknownOperatorInGrpcInputOperation(Operation),
-    SolverUnknownSatisfiability
+    SolverUnknownSatisfiability,
+    // ***
+    UnboundSymbol(usize),
+    DivisionByZero,
+    ArithmeticOverflow,
+    UnresolvedFreshValue(String),
+    // ***
+    UnresumableLogger(usize,String),
+    UnreplayableCheckpoint(u32,String)
 }
 
 impl fmt::Display for HibouCoreError {
@@ -53,6 +64,15 @@ impl fmt::Display for HibouCoreError {
             HibouCoreError::UnknownLifelineGroup( lgr_id ) => {
                 return write!(f, "{}", format!("context error ; unknown lifeline group : {:}", lgr_id));
             },
+            HibouCoreError::UnknownReference( ref_id ) => {
+                return write!(f, "{}", format!("context error ; unknown interaction reference : {:}", ref_id));
+            },
+            HibouCoreError::UnknownTag( tag_id ) => {
+                return write!(f, "{}", format!("context error ; unknown tag : {:}", tag_id));
+            },
+            HibouCoreError::NameCollision( name ) => {
+                return write!(f, "{}", format!("context error ; name already declared : {:}", name));
+            },
             HibouCoreError::UnknownLifeline( lf_id ) => {
                 return write!(f, "{}", format!("context error ; unknown lifeline : {:}", lf_id));
             },
@@ -99,6 +119,24 @@ impl fmt::Display for HibouCoreError {
             },
             HibouCoreError::SolverUnknownSatisfiability => {
                 return write!(f,  "{}", format!("solver returned Unknown"));
+            },
+            HibouCoreError::UnboundSymbol( sy_id ) => {
+                return write!(f, "{}", format!("evaluation error ; no value for symbol : {:}", sy_id));
+            },
+            HibouCoreError::DivisionByZero => {
+                return write!(f, "{}", format!("evaluation error ; division by zero"));
+            },
+            HibouCoreError::ArithmeticOverflow => {
+                return write!(f, "{}", format!("evaluation error ; arithmetic overflow"));
+            },
+            HibouCoreError::UnresolvedFreshValue( sub_e ) => {
+                return write!(f, "{}", format!("concrete execution error ; a fresh value '#' cannot be resolved : {:}", sub_e));
+            },
+            HibouCoreError::UnresumableLogger( logger_index, sub_e ) => {
+                return write!(f, "{}", format!("checkpoint error ; logger number {:} cannot be resumed : {:}", logger_index, sub_e));
+            },
+            HibouCoreError::UnreplayableCheckpoint( state_id, sub_e ) => {
+                return write!(f, "{}", format!("checkpoint error ; checkpointed state {:} cannot be reached anew : {:}", state_id, sub_e));
             }
         }
     }
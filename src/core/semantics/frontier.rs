@@ -13,6 +13,7 @@ use std::collections::HashSet;
 
 use crate::core::syntax::position::*;
 use crate::core::syntax::interaction::*;
+use crate::core::context::execution::ExecutionContext;
 
 
 
@@ -47,17 +48,64 @@ pub fn make_frontier(interaction : &Interaction) -> Vec<Position> {
             return front;
         },
         &Interaction::Par(ref i1, ref i2) => {
-            let mut front = push_frontier(&PositionKind::Left, make_frontier(i1));
-            front.append( &mut push_frontier(&PositionKind::Right, make_frontier(i2)) );
+            // a lifeline waiting for the reply to its call on one side cannot act on the other
+            let mut front : Vec<Position> = Vec::new();
+            let blocked_by_i2 = i2.get_blocked_lifelines();
+            for pos1 in push_frontier(&PositionKind::Left, make_frontier(i1)) {
+                let act = interaction.get_sub_interaction(&pos1 ).as_leaf();
+                if !blocked_by_i2.contains(&act.lf_act.lf_id) {
+                    front.push(pos1);
+                }
+            }
+            let blocked_by_i1 = i1.get_blocked_lifelines();
+            for pos2 in push_frontier(&PositionKind::Right, make_frontier(i2)) {
+                let act = interaction.get_sub_interaction(&pos2 ).as_leaf();
+                if !blocked_by_i1.contains(&act.lf_act.lf_id) {
+                    front.push(pos2);
+                }
+            }
             return front;
         },
-        &Interaction::Loop(_, ref i1) => {
+        &Interaction::Loop(_, _, ref i1) => {
+            return push_frontier(&PositionKind::Left, make_frontier(i1));
+        },
+        &Interaction::Opt(ref i1) => {
+            return push_frontier(&PositionKind::Left, make_frontier(i1));
+        },
+        &Interaction::Neg(_, ref i1) | &Interaction::Assert(_, ref i1) | &Interaction::Break(_, ref i1)
+                | &Interaction::Create(_, ref i1) | &Interaction::Ref(_, ref i1) => {
             return push_frontier(&PositionKind::Left, make_frontier(i1));
         },
         &Interaction::Scope(_, ref i1) => {
             return push_frontier(&PositionKind::Left, make_frontier(i1));
+        },
+        &Interaction::Call(_, _, ref i1) => {
+            return push_frontier(&PositionKind::Left, make_frontier(i1));
+        }
+    }
+}
+
+/*
+    Removes from the frontier the actions which would occur on lifelines that are not yet created
+    i.e. which are neither created in the execution context nor by a '@create' fragment entered when executing the action
+*/
+pub fn make_frontier_in_context(interaction : &Interaction, exe_ctx : &ExecutionContext) -> Vec<Position> {
+    let mut frontier : Vec<Position> = Vec::new();
+    for front_pos in make_frontier(interaction) {
+        let front_act = interaction.get_sub_interaction(&front_pos).as_leaf();
+        let created_on_path = interaction.get_lifelines_created_on_path(&front_pos);
+        let mut allowed = true;
+        for lf_id in front_act.occupation_after() {
+            if !exe_ctx.is_lf_created(lf_id) && !created_on_path.contains(&lf_id) {
+                allowed = false;
+                break;
+            }
+        }
+        if allowed {
+            frontier.push(front_pos);
         }
     }
+    return frontier;
 }
 
 
@@ -44,15 +44,51 @@ pub fn prune(my_int : &Interaction, lf_id : usize) -> Interaction {
                 return prune(i2,lf_id);
             }
         },
-        Interaction::Loop(ref lkind, ref i1) => {
+        Interaction::Loop(ref lkind, ref bounds, ref i1) => {
             if i1.avoids(lf_id) {
-                return Interaction::Loop(lkind.clone(), Box::new(prune(i1,lf_id)));
+                return Interaction::Loop(lkind.clone(), bounds.clone(), Box::new(prune(i1,lf_id)));
             } else {
                 return Interaction::Empty;
             }
         },
+        Interaction::Opt(ref i1) => {
+            // the optional fragment can only be kept if it avoids the lifeline
+            if i1.avoids(lf_id) {
+                return Interaction::Opt(Box::new(prune(i1,lf_id)));
+            } else {
+                return Interaction::Empty;
+            }
+        },
+        Interaction::Create(lf_ids, ref i1) => {
+            // only reached if the fragment avoids the lifeline, which is then not among the created ones
+            return Interaction::Create(lf_ids.clone(), Box::new(prune(i1,lf_id)));
+        },
+        Interaction::Ref(ref_id, ref i1) => {
+            return Interaction::Ref(*ref_id, Box::new(prune(i1,lf_id)));
+        },
+        Interaction::Break(break_scope, ref i1) => {
+            if i1.avoids(lf_id) {
+                return Interaction::Break(break_scope.clone(), Box::new(prune(i1,lf_id)));
+            } else {
+                return Interaction::Empty;
+            }
+        },
+        Interaction::Neg(entered, ref i1) => {
+            // as for optional fragments, the forbidden behaviour can only be kept if it avoids the lifeline
+            if i1.avoids(lf_id) {
+                return Interaction::Neg(*entered, Box::new(prune(i1,lf_id)));
+            } else {
+                return Interaction::Empty;
+            }
+        },
+        Interaction::Assert(entered, i1) => {
+            return Interaction::Assert(*entered, Box::new(prune(i1,lf_id)));
+        },
         Interaction::Scope(sko, i1) => {
             return Interaction::Scope(sko.clone(), Box::new(prune(i1,lf_id)));
+        },
+        Interaction::Call(caller, called, i1) => {
+            return Interaction::Call(*caller, *called, Box::new(prune(i1,lf_id)));
         }
     }
 }
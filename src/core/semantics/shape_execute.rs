@@ -77,12 +77,110 @@ fn shape_execute_left_in_schedule_operator(   gen_ctx : &GeneralContext,
 
 
 
+/*
+    Follows the path towards the break fragment which is entered, discarding what remains of its loop
+    but entering the scopes, calls, negs, asserts and creations found on the way as would be done otherwise
+*/
+fn shape_execute_towards_break(gen_ctx : &GeneralContext,
+                               exe_ctx : &mut ExecutionContext,
+                               my_int : &Interaction,
+                               concerned_lf : &usize,
+                               target_pos : &Position,
+                               current_position : &mut Vec<u32>) -> Result< (Interaction,Position,ObservableAction,bool) , HibouCoreError > {
+    match (my_int,target_pos) {
+        (&Interaction::Break(_, ref i1),Position::Left(sub_pos)) => {
+            return shape_execute_rec(gen_ctx,exe_ctx, &*i1,concerned_lf,&*sub_pos,current_position);
+        },
+        (&Interaction::Scope(ref scope, ref i1),Position::Left(sub_pos)) => {
+            let new_i1 = exe_ctx.open_scope(gen_ctx, scope, i1);
+            match shape_execute_towards_break(gen_ctx,exe_ctx, &new_i1,concerned_lf,&*sub_pos,current_position) {
+                Err(e) => {
+                    return Err(e);
+                },
+                Ok( (final_interaction,final_pos,final_action,_) ) => {
+                    return Ok( (final_interaction,final_pos,final_action,true) );
+                }
+            }
+        },
+        (&Interaction::Create(ref lf_ids, ref i1),Position::Left(sub_pos)) => {
+            exe_ctx.create_lifelines(lf_ids);
+            return shape_execute_towards_break(gen_ctx,exe_ctx, &*i1,concerned_lf,&*sub_pos,current_position);
+        },
+        (&Interaction::Call(ref caller, _, ref i1),Position::Left(sub_pos)) => {
+            current_position.push(1);
+            match shape_execute_towards_break(gen_ctx,exe_ctx, &*i1,concerned_lf,&*sub_pos,current_position) {
+                Err(e) => {
+                    return Err(e);
+                },
+                Ok( (new_i1,final_pos,final_action,needs_scoping) ) => {
+                    return Ok( (Interaction::Call(*caller, true, Box::new(new_i1)),final_pos,final_action,needs_scoping) );
+                }
+            }
+        },
+        (&Interaction::Neg(_, ref i1),Position::Left(sub_pos)) => {
+            current_position.push(1);
+            match shape_execute_towards_break(gen_ctx,exe_ctx, &*i1,concerned_lf,&*sub_pos,current_position) {
+                Err(e) => {
+                    return Err(e);
+                },
+                Ok( (new_i1,final_pos,final_action,needs_scoping) ) => {
+                    return Ok( (Interaction::Neg(true, Box::new(new_i1)),final_pos,final_action,needs_scoping) );
+                }
+            }
+        },
+        (&Interaction::Assert(_, ref i1),Position::Left(sub_pos)) => {
+            current_position.push(1);
+            match shape_execute_towards_break(gen_ctx,exe_ctx, &*i1,concerned_lf,&*sub_pos,current_position) {
+                Err(e) => {
+                    return Err(e);
+                },
+                Ok( (new_i1,final_pos,final_action,needs_scoping) ) => {
+                    return Ok( (Interaction::Assert(true, Box::new(new_i1)),final_pos,final_action,needs_scoping) );
+                }
+            }
+        },
+        (&Interaction::Strict(ref i1, _),Position::Left(sub_pos)) | (&Interaction::Seq(ref i1, _),Position::Left(sub_pos))
+                | (&Interaction::Alt(ref i1, _),Position::Left(sub_pos)) | (&Interaction::Par(ref i1, _),Position::Left(sub_pos))
+                | (&Interaction::Loop(_, _, ref i1),Position::Left(sub_pos)) | (&Interaction::Opt(ref i1),Position::Left(sub_pos))
+                | (&Interaction::Ref(_, ref i1),Position::Left(sub_pos)) => {
+            return shape_execute_towards_break(gen_ctx,exe_ctx, &*i1,concerned_lf,&*sub_pos,current_position);
+        },
+        (&Interaction::Strict(_, ref i2),Position::Right(sub_pos)) | (&Interaction::Seq(_, ref i2),Position::Right(sub_pos))
+                | (&Interaction::Alt(_, ref i2),Position::Right(sub_pos)) | (&Interaction::Par(_, ref i2),Position::Right(sub_pos)) => {
+            return shape_execute_towards_break(gen_ctx,exe_ctx, &*i2,concerned_lf,&*sub_pos,current_position);
+        },
+        _ => {
+            return Err( HibouCoreError::PositionError(my_int.clone(), target_pos.clone()) );
+        }
+    }
+}
+
+
+
 fn shape_execute_rec(gen_ctx : &GeneralContext,
                     exe_ctx : &mut ExecutionContext,
                     my_int : &Interaction,
                     concerned_lf : &usize,
                     target_pos : &Position,
                     current_position : &mut Vec<u32>) -> Result< (Interaction,Position,ObservableAction,bool) , HibouCoreError > {
+    // entering a break fragment replaces what remains of its loop by the break fragment itself
+    // what remains of the loop is the largest sub-interaction made only of actions originating from its body
+    // except for the scopes, calls, negs, asserts and creations which enclose it and which are to be kept
+    match my_int {
+        &Interaction::Scope(_, _) | &Interaction::Call(_, _, _) | &Interaction::Neg(_, _) | &Interaction::Assert(_, _)
+                | &Interaction::Create(_, _) => {},
+        _ => {
+            match my_int.get_break_on_path(target_pos) {
+                None => {},
+                Some( (break_scope,_,_) ) => {
+                    if my_int.is_within_original_position(break_scope) {
+                        return shape_execute_towards_break(gen_ctx,exe_ctx, my_int,concerned_lf,target_pos,current_position);
+                    }
+                }
+            }
+        }
+    }
+    // ***
     match target_pos {
         Position::Epsilon => {
             match my_int {
@@ -99,8 +197,29 @@ fn shape_execute_rec(gen_ctx : &GeneralContext,
                 &Interaction::Alt(ref i1,_) => {
                     return shape_execute_rec(gen_ctx,exe_ctx, &*i1,concerned_lf,&*sub_pos,current_position);
                 },
-                &Interaction::Loop(ref lkind, ref i1) => {
-                    return shape_execute_left_in_schedule_operator(gen_ctx,exe_ctx,&*i1, my_int, concerned_lf,&*sub_pos, lkind,current_position);
+                &Interaction::Opt(ref i1) => {
+                    return shape_execute_rec(gen_ctx,exe_ctx, &*i1,concerned_lf,&*sub_pos,current_position);
+                },
+                &Interaction::Ref(_, ref i1) => {
+                    // once entered, the reference is replaced by its content
+                    return shape_execute_rec(gen_ctx,exe_ctx, &*i1,concerned_lf,&*sub_pos,current_position);
+                },
+                &Interaction::Create(ref lf_ids, ref i1) => {
+                    // entering the fragment creates the lifelines, after which the fragment behaves as its content
+                    exe_ctx.create_lifelines(lf_ids);
+                    return shape_execute_rec(gen_ctx,exe_ctx, &*i1,concerned_lf,&*sub_pos,current_position);
+                },
+                &Interaction::Loop(ref lkind, ref bounds, ref i1) => {
+                    match bounds.after_iteration() {
+                        None => {
+                            // last allowed iteration
+                            return shape_execute_rec(gen_ctx,exe_ctx, &*i1,concerned_lf,&*sub_pos,current_position);
+                        },
+                        Some( remaining_bounds ) => {
+                            let remaining_loop = Interaction::Loop(lkind.clone(), remaining_bounds, i1.clone());
+                            return shape_execute_left_in_schedule_operator(gen_ctx,exe_ctx,&*i1, &remaining_loop, concerned_lf,&*sub_pos, lkind,current_position);
+                        }
+                    }
                 },
                 &Interaction::Scope(ref scope, ref i1) => {
                     let new_i1 = exe_ctx.open_scope(gen_ctx, scope, i1);
@@ -113,6 +232,45 @@ fn shape_execute_rec(gen_ctx : &GeneralContext,
                         }
                     }
                 },
+                &Interaction::Call(ref caller, _, ref i1) => {
+                    // whatever is executed within the call, the call itself is the first thing to be
+                    current_position.push(1);
+                    match shape_execute_rec(gen_ctx,exe_ctx, &*i1,concerned_lf,&*sub_pos,current_position) {
+                        Err(e) => {
+                            return Err(e);
+                        },
+                        Ok( (new_i1,final_pos,final_action,needs_scoping) ) => {
+                            let final_interaction = Interaction::Call(*caller, true, Box::new(new_i1));
+                            return Ok( (final_interaction,final_pos,final_action,needs_scoping) );
+                        }
+                    }
+                },
+                &Interaction::Neg(_, ref i1) => {
+                    // the neg fragment is kept so as to know when the forbidden behaviour is entirely observed
+                    current_position.push(1);
+                    match shape_execute_rec(gen_ctx,exe_ctx, &*i1,concerned_lf,&*sub_pos,current_position) {
+                        Err(e) => {
+                            return Err(e);
+                        },
+                        Ok( (new_i1,final_pos,final_action,needs_scoping) ) => {
+                            let final_interaction = Interaction::Neg(true, Box::new(new_i1));
+                            return Ok( (final_interaction,final_pos,final_action,needs_scoping) );
+                        }
+                    }
+                },
+                &Interaction::Assert(_, ref i1) => {
+                    // the assert fragment is kept so that deviations from it can be reported
+                    current_position.push(1);
+                    match shape_execute_rec(gen_ctx,exe_ctx, &*i1,concerned_lf,&*sub_pos,current_position) {
+                        Err(e) => {
+                            return Err(e);
+                        },
+                        Ok( (new_i1,final_pos,final_action,needs_scoping) ) => {
+                            let final_interaction = Interaction::Assert(true, Box::new(new_i1));
+                            return Ok( (final_interaction,final_pos,final_action,needs_scoping) );
+                        }
+                    }
+                },
                 &Interaction::Strict(ref i1,ref i2) => {
                     return shape_execute_left_in_schedule_operator(gen_ctx,exe_ctx,&*i1, &*i2, concerned_lf,&*sub_pos, &ScheduleOperatorKind::Strict,current_position);
                 },
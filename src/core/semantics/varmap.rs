@@ -52,10 +52,29 @@ impl VarMapAble for Interaction {
                 return Interaction::Par(Box::new(i1.apply_variable_mapping(mapping)),
                                            Box::new(i2.apply_variable_mapping(mapping)));
             },
-            &Interaction::Loop(ref lkind, ref i1) => {
+            &Interaction::Loop(ref lkind, ref bounds, ref i1) => {
                 return Interaction::Loop(lkind.clone(),
+                                         bounds.clone(),
                                          Box::new(i1.apply_variable_mapping(mapping)));
             },
+            &Interaction::Opt(ref i1) => {
+                return Interaction::Opt(Box::new(i1.apply_variable_mapping(mapping)));
+            },
+            &Interaction::Break(ref break_scope, ref i1) => {
+                return Interaction::Break(break_scope.clone(), Box::new(i1.apply_variable_mapping(mapping)));
+            },
+            &Interaction::Create(ref lf_ids, ref i1) => {
+                return Interaction::Create(lf_ids.clone(), Box::new(i1.apply_variable_mapping(mapping)));
+            },
+            &Interaction::Ref(ref_id, ref i1) => {
+                return Interaction::Ref(ref_id, Box::new(i1.apply_variable_mapping(mapping)));
+            },
+            &Interaction::Neg(ref entered, ref i1) => {
+                return Interaction::Neg(*entered, Box::new(i1.apply_variable_mapping(mapping)));
+            },
+            &Interaction::Assert(ref entered, ref i1) => {
+                return Interaction::Assert(*entered, Box::new(i1.apply_variable_mapping(mapping)));
+            },
             &Interaction::Scope(ref scoped_vr_ids, ref i1) => {
                 let mut new_vr_ids : Vec<usize> = Vec::new();
                 for vr_id in scoped_vr_ids {
@@ -70,6 +89,11 @@ impl VarMapAble for Interaction {
                 }
                 return Interaction::Scope(new_vr_ids,
                                           Box::new(i1.apply_variable_mapping(mapping)));
+            },
+            &Interaction::Call(ref caller, ref called, ref i1) => {
+                return Interaction::Call(*caller,
+                                         *called,
+                                         Box::new(i1.apply_variable_mapping(mapping)));
             }
         }
     }
@@ -176,7 +200,9 @@ impl VarMapAble for ObservableAction {
             act_kind:new_act_kind,
             ms_id:self.ms_id,
             params:new_params,
-            original_position:self.original_position.clone()
+            com_kind:self.com_kind.clone(),
+            original_position:self.original_position.clone(),
+            tags:self.tags.clone()
         }
     }
 }
@@ -43,13 +43,28 @@ pub enum ObservableActionKind {
     Emission(Vec<LifelineAction>)
 }
 
+/*
+    Asynchronous messages are the default
+    A synchronous call blocks its caller until the reception of the matching reply,
+    which carries the return value of the call, if any, as its only parameter
+*/
+#[derive(Clone, PartialEq, Debug)]
+pub enum CommunicationKind {
+    Asynchronous,
+    Call,
+    Reply
+}
+
 #[derive(Clone, PartialEq, Debug)]
 pub struct ObservableAction {
     pub lf_act : LifelineAction,
     pub act_kind : ObservableActionKind,
     pub ms_id : usize,
     pub params : Vec<ValueOrNewFresh>,
-    pub original_position : Option<Vec<u32>>
+    pub com_kind : CommunicationKind,
+    pub original_position : Option<Vec<u32>>,
+    // tags of the action and of the fragments containing it, given by their index in the general context
+    pub tags : Vec<usize>
 }
 
 
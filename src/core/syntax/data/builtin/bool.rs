@@ -42,6 +42,32 @@ pub enum TD_Bool {
 
 impl TD_Bool {
 
+    // number of boolean nodes (connectives and atoms) in the expression
+    pub fn get_size(&self) -> usize {
+        match self {
+            TD_Bool::AND(sub_bools) => {
+                let mut size : usize = 1;
+                for sub_bool in sub_bools {
+                    size = size + sub_bool.get_size();
+                }
+                return size;
+            },
+            TD_Bool::OR(sub_bools) => {
+                let mut size : usize = 1;
+                for sub_bool in sub_bools {
+                    size = size + sub_bool.get_size();
+                }
+                return size;
+            },
+            TD_Bool::NOT(sub_bool) => {
+                return 1 + (*sub_bool).get_size();
+            },
+            _ => {
+                return 1;
+            }
+        }
+    }
+
     pub fn get_occuring_variables(&self) -> HashSet<usize> {
         match self {
             TD_Bool::TRUE => {
@@ -0,0 +1,418 @@
+/********************************************************************************
+ * Copyright (c) 2020 Erwan Mahe (github.com/erwanM974)
+ *
+ * This program and the accompanying materials are made available under the
+ * terms of the Eclipse Public License 2.0 which is available at
+ * http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+ * which is available at https://www.apache.org/licenses/LICENSE-2.0.
+ *
+ * SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+ ********************************************************************************/
+
+use std::cmp::Ordering;
+use std::collections::btree_map::BTreeMap;
+
+use crate::core::error::HibouCoreError;
+use crate::core::syntax::data::td_type::TD_DataType;
+use crate::core::syntax::data::generic::TD_Generic;
+use crate::core::syntax::data::var_ref::VariableReference;
+use crate::core::syntax::data::builtin::bool::{TD_Bool,Bool_Compare};
+use crate::core::syntax::data::builtin::integer::TD_Integer;
+use crate::core::syntax::data::builtin::float::TD_Float;
+use crate::core::syntax::data::builtin::string::TD_String;
+use crate::core::syntax::data::builtin::number::*;
+
+/*
+    Values against which terms are concretely evaluated :
+    - the interpretation of the variables on a given lifeline
+    - the values of the symbols
+    - optionally, the values of the parameters of a given message
+    Variable interpretations may themselves refer to symbols
+*/
+pub struct Valuation<'a> {
+    pub interpretation : &'a BTreeMap<usize,TD_Generic>,
+    pub symbols : &'a BTreeMap<usize,TD_Generic>,
+    pub parameters : Option<(usize,&'a Vec<TD_Generic>)> // message id and parameter values
+}
+
+impl<'a> Valuation<'a> {
+    pub fn new(interpretation : &'a BTreeMap<usize,TD_Generic>,
+               symbols : &'a BTreeMap<usize,TD_Generic>) -> Valuation<'a> {
+        return Valuation{interpretation,symbols,parameters:None};
+    }
+
+    pub fn with_parameters(interpretation : &'a BTreeMap<usize,TD_Generic>,
+                           symbols : &'a BTreeMap<usize,TD_Generic>,
+                           ms_id : usize,
+                           parameters : &'a Vec<TD_Generic>) -> Valuation<'a> {
+        return Valuation{interpretation,symbols,parameters:Some((ms_id,parameters))};
+    }
+
+    fn resolve(&self, var_ref : &VariableReference) -> Result<TD_Generic,HibouCoreError> {
+        match var_ref {
+            VariableReference::VARIABLE( vr_id ) => {
+                match self.interpretation.get(vr_id) {
+                    None => {
+                        return Err( HibouCoreError::UninterpretedVariable(*vr_id) );
+                    },
+                    Some( td_gen ) => {
+                        return evaluate_generic(td_gen,self);
+                    }
+                }
+            },
+            VariableReference::SYMBOL( sy_id ) => {
+                match self.symbols.get(sy_id) {
+                    None => {
+                        return Err( HibouCoreError::UnboundSymbol(*sy_id) );
+                    },
+                    Some( td_gen ) => {
+                        return evaluate_generic(td_gen,self);
+                    }
+                }
+            },
+            VariableReference::MSG_PARAMETER( ms_id, pr_id ) => {
+                match self.parameters {
+                    Some( (got_ms_id, values) ) if got_ms_id == *ms_id => {
+                        match values.get(*pr_id) {
+                            None => {
+                                return Err( HibouCoreError::UninterpretedParameter(*ms_id,*pr_id) );
+                            },
+                            Some( td_gen ) => {
+                                return evaluate_generic(td_gen,self);
+                            }
+                        }
+                    },
+                    _ => {
+                        return Err( HibouCoreError::UninterpretedParameter(*ms_id,*pr_id) );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/*
+    Returns a literal of the same type
+    i.e. TD_Bool::TRUE/FALSE, TD_Integer::Value, TD_Float::Value or TD_String::Value
+*/
+pub fn evaluate_generic(td_gen : &TD_Generic, valuation : &Valuation) -> Result<TD_Generic,HibouCoreError> {
+    match td_gen {
+        TD_Generic::Bool( td_bool ) => {
+            if evaluate_bool(td_bool,valuation)? {
+                return Ok( TD_Generic::Bool(TD_Bool::TRUE) );
+            } else {
+                return Ok( TD_Generic::Bool(TD_Bool::FALSE) );
+            }
+        },
+        TD_Generic::Integer( td_int ) => {
+            return Ok( TD_Generic::Integer( TD_Integer::Value(evaluate_integer(td_int,valuation)?) ) );
+        },
+        TD_Generic::Float( td_float ) => {
+            return Ok( TD_Generic::Float( TD_Float::Value(evaluate_float(td_float,valuation)?) ) );
+        },
+        TD_Generic::String( td_string ) => {
+            return Ok( TD_Generic::String( TD_String::Value(evaluate_string(td_string,valuation)?) ) );
+        }
+    }
+}
+
+pub fn evaluate_bool(td_bool : &TD_Bool, valuation : &Valuation) -> Result<bool,HibouCoreError> {
+    match td_bool {
+        TD_Bool::TRUE => {
+            return Ok(true);
+        },
+        TD_Bool::FALSE => {
+            return Ok(false);
+        },
+        TD_Bool::NOT( sub_bool ) => {
+            return Ok( !evaluate_bool(sub_bool,valuation)? );
+        },
+        TD_Bool::AND( sub_bools ) => {
+            for sub_bool in sub_bools {
+                if !evaluate_bool(sub_bool,valuation)? {
+                    return Ok(false);
+                }
+            }
+            return Ok(true);
+        },
+        TD_Bool::OR( sub_bools ) => {
+            for sub_bool in sub_bools {
+                if evaluate_bool(sub_bool,valuation)? {
+                    return Ok(true);
+                }
+            }
+            return Ok(false);
+        },
+        TD_Bool::COMPARE( kind, first, second ) => {
+            let first_val = evaluate_generic(first,valuation)?;
+            let second_val = evaluate_generic(second,valuation)?;
+            match (&first_val,&second_val) {
+                (TD_Generic::Bool(_),TD_Generic::Bool(_)) => {
+                    // booleans are not ordered
+                    match kind {
+                        Bool_Compare::Equal => {
+                            return Ok( first_val == second_val );
+                        },
+                        Bool_Compare::Different => {
+                            return Ok( first_val != second_val );
+                        },
+                        _ => {
+                            return Err( HibouCoreError::WronglyTypedExpression(first_val,TD_DataType::Integer) );
+                        }
+                    }
+                },
+                _ => {
+                    let ordering = compare_literals(&first_val,&second_val)?;
+                    return Ok( ordering_satisfies(kind,ordering) );
+                }
+            }
+        },
+        TD_Bool::Reference( var_ref ) => {
+            match valuation.resolve(var_ref)? {
+                TD_Generic::Bool(TD_Bool::TRUE) => {
+                    return Ok(true);
+                },
+                TD_Generic::Bool(TD_Bool::FALSE) => {
+                    return Ok(false);
+                },
+                other => {
+                    return Err( HibouCoreError::WronglyTypedExpression(other,TD_DataType::Bool) );
+                }
+            }
+        }
+    }
+}
+
+pub fn evaluate_integer(td_int : &TD_Integer, valuation : &Valuation) -> Result<i64,HibouCoreError> {
+    match td_int {
+        TD_Integer::Value( val ) => {
+            return Ok(*val);
+        },
+        TD_Integer::Minus( sub_int ) => {
+            match evaluate_integer(sub_int,valuation)?.checked_neg() {
+                None => {
+                    return Err( HibouCoreError::ArithmeticOverflow );
+                },
+                Some( got ) => {
+                    return Ok(got);
+                }
+            }
+        },
+        TD_Integer::Factor( sub_ints ) => {
+            let mut result : i64 = 1;
+            for (sign,sub_int) in sub_ints {
+                let sub_val = evaluate_integer(sub_int,valuation)?;
+                let computed : Option<i64>;
+                match sign {
+                    ARITH_FACTOR_SIGN::Mult => {
+                        computed = result.checked_mul(sub_val);
+                    },
+                    ARITH_FACTOR_SIGN::Div => {
+                        if sub_val == 0 {
+                            return Err( HibouCoreError::DivisionByZero );
+                        }
+                        computed = result.checked_div(sub_val);
+                    }
+                }
+                match computed {
+                    None => {
+                        return Err( HibouCoreError::ArithmeticOverflow );
+                    },
+                    Some( got ) => {
+                        result = got;
+                    }
+                }
+            }
+            return Ok(result);
+        },
+        TD_Integer::Add( sub_ints ) => {
+            let mut result : i64 = 0;
+            for (sign,sub_int) in sub_ints {
+                let sub_val = evaluate_integer(sub_int,valuation)?;
+                let computed : Option<i64>;
+                match sign {
+                    ARITH_ADD_SIGN::Plus => {
+                        computed = result.checked_add(sub_val);
+                    },
+                    ARITH_ADD_SIGN::Minus => {
+                        computed = result.checked_sub(sub_val);
+                    }
+                }
+                match computed {
+                    None => {
+                        return Err( HibouCoreError::ArithmeticOverflow );
+                    },
+                    Some( got ) => {
+                        result = got;
+                    }
+                }
+            }
+            return Ok(result);
+        },
+        TD_Integer::Reference( var_ref ) => {
+            match valuation.resolve(var_ref)? {
+                TD_Generic::Integer(TD_Integer::Value(val)) => {
+                    return Ok(val);
+                },
+                other => {
+                    return Err( HibouCoreError::WronglyTypedExpression(other,TD_DataType::Integer) );
+                }
+            }
+        }
+    }
+}
+
+pub fn evaluate_float(td_float : &TD_Float, valuation : &Valuation) -> Result<f64,HibouCoreError> {
+    match td_float {
+        TD_Float::Value( val ) => {
+            return Ok(*val);
+        },
+        TD_Float::Minus( sub_float ) => {
+            return Ok( - evaluate_float(sub_float,valuation)? );
+        },
+        TD_Float::Factor( sub_floats ) => {
+            let mut result : f64 = 1.0;
+            for (sign,sub_float) in sub_floats {
+                let sub_val = evaluate_float(sub_float,valuation)?;
+                match sign {
+                    ARITH_FACTOR_SIGN::Mult => {
+                        result = result * sub_val;
+                    },
+                    ARITH_FACTOR_SIGN::Div => {
+                        if sub_val == 0.0 {
+                            return Err( HibouCoreError::DivisionByZero );
+                        }
+                        result = result / sub_val;
+                    }
+                }
+            }
+            return Ok(result);
+        },
+        TD_Float::Add( sub_floats ) => {
+            let mut result : f64 = 0.0;
+            for (sign,sub_float) in sub_floats {
+                let sub_val = evaluate_float(sub_float,valuation)?;
+                match sign {
+                    ARITH_ADD_SIGN::Plus => {
+                        result = result + sub_val;
+                    },
+                    ARITH_ADD_SIGN::Minus => {
+                        result = result - sub_val;
+                    }
+                }
+            }
+            return Ok(result);
+        },
+        TD_Float::Reference( var_ref ) => {
+            match valuation.resolve(var_ref)? {
+                TD_Generic::Float(TD_Float::Value(val)) => {
+                    return Ok(val);
+                },
+                // integers are implicitly converted
+                TD_Generic::Integer(TD_Integer::Value(val)) => {
+                    return Ok(val as f64);
+                },
+                other => {
+                    return Err( HibouCoreError::WronglyTypedExpression(other,TD_DataType::Float) );
+                }
+            }
+        }
+    }
+}
+
+pub fn evaluate_string(td_string : &TD_String, valuation : &Valuation) -> Result<String,HibouCoreError> {
+    match td_string {
+        TD_String::Value( val ) => {
+            return Ok(val.clone());
+        },
+        TD_String::Reference( var_ref ) => {
+            match valuation.resolve(var_ref)? {
+                TD_Generic::String(TD_String::Value(val)) => {
+                    return Ok(val);
+                },
+                other => {
+                    return Err( HibouCoreError::WronglyTypedExpression(other,TD_DataType::String) );
+                }
+            }
+        }
+    }
+}
+
+/*
+    Compares two literals as returned by evaluate_generic
+    Integers and floats can be compared with one another
+*/
+pub fn compare_literals(first : &TD_Generic, second : &TD_Generic) -> Result<Ordering,HibouCoreError> {
+    match (first,second) {
+        (TD_Generic::Integer(TD_Integer::Value(x)),TD_Generic::Integer(TD_Integer::Value(y))) => {
+            return Ok( x.cmp(y) );
+        },
+        (TD_Generic::String(TD_String::Value(x)),TD_Generic::String(TD_String::Value(y))) => {
+            return Ok( x.cmp(y) );
+        },
+        (TD_Generic::Bool(x),TD_Generic::Bool(y)) => {
+            if x == y {
+                return Ok( Ordering::Equal );
+            } else {
+                return Err( HibouCoreError::WronglyTypedExpression(first.clone(),TD_DataType::Integer) );
+            }
+        },
+        _ => {
+            let x : f64;
+            match first {
+                TD_Generic::Float(TD_Float::Value(val)) => {
+                    x = *val;
+                },
+                TD_Generic::Integer(TD_Integer::Value(val)) => {
+                    x = *val as f64;
+                },
+                _ => {
+                    return Err( HibouCoreError::WronglyTypedExpression(first.clone(),second.get_td_type()) );
+                }
+            }
+            let y : f64;
+            match second {
+                TD_Generic::Float(TD_Float::Value(val)) => {
+                    y = *val;
+                },
+                TD_Generic::Integer(TD_Integer::Value(val)) => {
+                    y = *val as f64;
+                },
+                _ => {
+                    return Err( HibouCoreError::WronglyTypedExpression(second.clone(),first.get_td_type()) );
+                }
+            }
+            match x.partial_cmp(&y) {
+                None => {
+                    return Err( HibouCoreError::WronglyTypedExpression(first.clone(),TD_DataType::Float) );
+                },
+                Some( ordering ) => {
+                    return Ok(ordering);
+                }
+            }
+        }
+    }
+}
+
+pub fn ordering_satisfies(kind : &Bool_Compare, ordering : Ordering) -> bool {
+    match kind {
+        Bool_Compare::Equal => {
+            return ordering == Ordering::Equal;
+        },
+        Bool_Compare::Different => {
+            return ordering != Ordering::Equal;
+        },
+        Bool_Compare::Greater => {
+            return ordering == Ordering::Greater;
+        },
+        Bool_Compare::GreaterOrEqual => {
+            return ordering != Ordering::Less;
+        },
+        Bool_Compare::Lower => {
+            return ordering == Ordering::Less;
+        },
+        Bool_Compare::LowerOrEqual => {
+            return ordering != Ordering::Greater;
+        }
+    }
+}
@@ -13,5 +13,7 @@ pub mod td_type;
 pub mod builtin;
 pub mod var_ref;
 pub mod generic;
+pub mod evaluation;
+pub mod simplification;
 
 
@@ -0,0 +1,357 @@
+/********************************************************************************
+ * Copyright (c) 2020 Erwan Mahe (github.com/erwanM974)
+ *
+ * This program and the accompanying materials are made available under the
+ * terms of the Eclipse Public License 2.0 which is available at
+ * http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+ * which is available at https://www.apache.org/licenses/LICENSE-2.0.
+ *
+ * SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+ ********************************************************************************/
+
+use std::collections::btree_map::BTreeMap;
+
+use crate::core::syntax::data::td_type::TD_DataType;
+use crate::core::syntax::data::generic::TD_Generic;
+use crate::core::syntax::data::builtin::bool::*;
+use crate::core::syntax::data::builtin::integer::TD_Integer;
+use crate::core::syntax::data::builtin::float::TD_Float;
+use crate::core::syntax::data::builtin::string::TD_String;
+use crate::core::syntax::data::builtin::number::*;
+use crate::core::syntax::data::evaluation::*;
+
+
+/*
+    Returns an equivalent but simpler boolean expression
+        - nested conjunctions (resp. disjunctions) are flattened
+        - constant sub-expressions are folded
+        - duplicated operands are removed
+        - conjunctions (resp. disjunctions) containing an operand and its negation are contradictions (resp. tautologies)
+        - negations of comparisons are pushed into the comparison operator
+        - comparisons have their literal operand, if any, on the right
+*/
+pub fn simplify_bool(td_bool : &TD_Bool) -> TD_Bool {
+    match td_bool {
+        TD_Bool::TRUE => {
+            return TD_Bool::TRUE;
+        },
+        TD_Bool::FALSE => {
+            return TD_Bool::FALSE;
+        },
+        TD_Bool::AND( sub_bools ) => {
+            let mut operands : Vec<TD_Bool> = Vec::new();
+            for sub_bool in sub_bools {
+                match simplify_bool(sub_bool) {
+                    TD_Bool::TRUE => {},
+                    TD_Bool::FALSE => {
+                        return TD_Bool::FALSE;
+                    },
+                    TD_Bool::AND( sub_operands ) => {
+                        for sub_operand in sub_operands {
+                            push_operand(&mut operands,sub_operand);
+                        }
+                    },
+                    other => {
+                        push_operand(&mut operands,other);
+                    }
+                }
+            }
+            if contains_complementary_operands(&operands) {
+                return TD_Bool::FALSE;
+            }
+            return connect_operands(operands,TD_Bool::TRUE,TD_Bool::AND);
+        },
+        TD_Bool::OR( sub_bools ) => {
+            let mut operands : Vec<TD_Bool> = Vec::new();
+            for sub_bool in sub_bools {
+                match simplify_bool(sub_bool) {
+                    TD_Bool::FALSE => {},
+                    TD_Bool::TRUE => {
+                        return TD_Bool::TRUE;
+                    },
+                    TD_Bool::OR( sub_operands ) => {
+                        for sub_operand in sub_operands {
+                            push_operand(&mut operands,sub_operand);
+                        }
+                    },
+                    other => {
+                        push_operand(&mut operands,other);
+                    }
+                }
+            }
+            if contains_complementary_operands(&operands) {
+                return TD_Bool::TRUE;
+            }
+            return connect_operands(operands,TD_Bool::FALSE,TD_Bool::OR);
+        },
+        TD_Bool::NOT( sub_bool ) => {
+            match simplify_bool(sub_bool) {
+                TD_Bool::TRUE => {
+                    return TD_Bool::FALSE;
+                },
+                TD_Bool::FALSE => {
+                    return TD_Bool::TRUE;
+                },
+                TD_Bool::NOT( negated ) => {
+                    return *negated;
+                },
+                TD_Bool::COMPARE( kind, first, second ) => {
+                    if is_ordered(&first) {
+                        return TD_Bool::COMPARE( negate_compare(&kind), first, second );
+                    } else {
+                        match kind {
+                            Bool_Compare::Equal => {
+                                return TD_Bool::COMPARE( Bool_Compare::Different, first, second );
+                            },
+                            Bool_Compare::Different => {
+                                return TD_Bool::COMPARE( Bool_Compare::Equal, first, second );
+                            },
+                            _ => {
+                                return TD_Bool::NOT( Box::new( TD_Bool::COMPARE(kind,first,second) ) );
+                            }
+                        }
+                    }
+                },
+                other => {
+                    return TD_Bool::NOT( Box::new(other) );
+                }
+            }
+        },
+        TD_Bool::COMPARE( kind, first, second ) => {
+            return simplify_compare(kind,first,second);
+        },
+        TD_Bool::Reference( var_ref ) => {
+            return TD_Bool::Reference( var_ref.clone() );
+        }
+    }
+}
+
+fn simplify_compare(kind : &Bool_Compare, first : &TD_Generic, second : &TD_Generic) -> TD_Bool {
+    let folded_first = fold_generic(first);
+    let folded_second = fold_generic(second);
+    let no_interpretation : BTreeMap<usize,TD_Generic> = BTreeMap::new();
+    let no_symbols : BTreeMap<usize,TD_Generic> = BTreeMap::new();
+    let folded_compare = TD_Bool::COMPARE( kind.clone(), Box::new(folded_first.clone()), Box::new(folded_second.clone()) );
+    // both operands are literals
+    match evaluate_bool(&folded_compare,&Valuation::new(&no_interpretation,&no_symbols)) {
+        Ok( true ) => {
+            return TD_Bool::TRUE;
+        },
+        Ok( false ) => {
+            return TD_Bool::FALSE;
+        },
+        Err(_) => {}
+    }
+    // both operands are the same term
+    if folded_first == folded_second && (is_ordered(&folded_first) || kind == &Bool_Compare::Equal || kind == &Bool_Compare::Different) {
+        match kind {
+            Bool_Compare::Equal | Bool_Compare::GreaterOrEqual | Bool_Compare::LowerOrEqual => {
+                return TD_Bool::TRUE;
+            },
+            Bool_Compare::Different | Bool_Compare::Greater | Bool_Compare::Lower => {
+                return TD_Bool::FALSE;
+            }
+        }
+    }
+    // the literal operand is put on the right
+    if is_literal(&folded_first) && !is_literal(&folded_second) {
+        return TD_Bool::COMPARE( mirror_compare(kind), Box::new(folded_second), Box::new(folded_first) );
+    }
+    return folded_compare;
+}
+
+/*
+    Folds the constant sub-terms of a term
+*/
+pub fn fold_generic(td_gen : &TD_Generic) -> TD_Generic {
+    match td_gen {
+        TD_Generic::Bool( td_bool ) => {
+            return TD_Generic::Bool( simplify_bool(td_bool) );
+        },
+        TD_Generic::Integer( td_int ) => {
+            return TD_Generic::Integer( fold_integer(td_int) );
+        },
+        TD_Generic::Float( td_float ) => {
+            return TD_Generic::Float( fold_float(td_float) );
+        },
+        TD_Generic::String( td_string ) => {
+            return TD_Generic::String( td_string.clone() );
+        }
+    }
+}
+
+fn fold_integer(td_int : &TD_Integer) -> TD_Integer {
+    let no_interpretation : BTreeMap<usize,TD_Generic> = BTreeMap::new();
+    let no_symbols : BTreeMap<usize,TD_Generic> = BTreeMap::new();
+    match evaluate_integer(td_int,&Valuation::new(&no_interpretation,&no_symbols)) {
+        Ok( value ) => {
+            return TD_Integer::Value(value);
+        },
+        Err(_) => {}
+    }
+    match td_int {
+        TD_Integer::Minus( sub_int ) => {
+            match fold_integer(sub_int) {
+                TD_Integer::Minus( negated ) => {
+                    return *negated;
+                },
+                other => {
+                    return TD_Integer::Minus( Box::new(other) );
+                }
+            }
+        },
+        TD_Integer::Factor( factors ) => {
+            let mut folded : Vec<(ARITH_FACTOR_SIGN,TD_Integer)> = Vec::new();
+            for (sign,sub_int) in factors {
+                folded.push( (sign.clone(),fold_integer(sub_int)) );
+            }
+            return TD_Integer::Factor(folded);
+        },
+        TD_Integer::Add( terms ) => {
+            let mut folded : Vec<(ARITH_ADD_SIGN,TD_Integer)> = Vec::new();
+            for (sign,sub_int) in terms {
+                folded.push( (sign.clone(),fold_integer(sub_int)) );
+            }
+            return TD_Integer::Add(folded);
+        },
+        _ => {
+            return td_int.clone();
+        }
+    }
+}
+
+fn fold_float(td_float : &TD_Float) -> TD_Float {
+    let no_interpretation : BTreeMap<usize,TD_Generic> = BTreeMap::new();
+    let no_symbols : BTreeMap<usize,TD_Generic> = BTreeMap::new();
+    match evaluate_float(td_float,&Valuation::new(&no_interpretation,&no_symbols)) {
+        Ok( value ) => {
+            return TD_Float::Value(value);
+        },
+        Err(_) => {}
+    }
+    match td_float {
+        TD_Float::Minus( sub_float ) => {
+            match fold_float(sub_float) {
+                TD_Float::Minus( negated ) => {
+                    return *negated;
+                },
+                other => {
+                    return TD_Float::Minus( Box::new(other) );
+                }
+            }
+        },
+        TD_Float::Factor( factors ) => {
+            let mut folded : Vec<(ARITH_FACTOR_SIGN,TD_Float)> = Vec::new();
+            for (sign,sub_float) in factors {
+                folded.push( (sign.clone(),fold_float(sub_float)) );
+            }
+            return TD_Float::Factor(folded);
+        },
+        TD_Float::Add( terms ) => {
+            let mut folded : Vec<(ARITH_ADD_SIGN,TD_Float)> = Vec::new();
+            for (sign,sub_float) in terms {
+                folded.push( (sign.clone(),fold_float(sub_float)) );
+            }
+            return TD_Float::Add(folded);
+        },
+        _ => {
+            return td_float.clone();
+        }
+    }
+}
+
+fn push_operand(operands : &mut Vec<TD_Bool>, operand : TD_Bool) {
+    if !operands.contains(&operand) {
+        operands.push(operand);
+    }
+}
+
+fn connect_operands(mut operands : Vec<TD_Bool>, neutral : TD_Bool, connective : fn(Vec<TD_Bool>) -> TD_Bool) -> TD_Bool {
+    match operands.len() {
+        0 => {
+            return neutral;
+        },
+        1 => {
+            return operands.remove(0);
+        },
+        _ => {
+            return connective(operands);
+        }
+    }
+}
+
+fn contains_complementary_operands(operands : &Vec<TD_Bool>) -> bool {
+    for operand in operands {
+        let negated = simplify_bool( &TD_Bool::NOT(Box::new(operand.clone())) );
+        if operands.contains(&negated) {
+            return true;
+        }
+    }
+    return false;
+}
+
+fn is_literal(td_gen : &TD_Generic) -> bool {
+    match td_gen {
+        TD_Generic::Bool( TD_Bool::TRUE ) | TD_Generic::Bool( TD_Bool::FALSE ) => {
+            return true;
+        },
+        TD_Generic::Integer( TD_Integer::Value(_) ) | TD_Generic::Float( TD_Float::Value(_) ) | TD_Generic::String( TD_String::Value(_) ) => {
+            return true;
+        },
+        _ => {
+            return false;
+        }
+    }
+}
+
+// booleans are the only data type without an order
+fn is_ordered(td_gen : &TD_Generic) -> bool {
+    return td_gen.get_td_type() != TD_DataType::Bool;
+}
+
+fn negate_compare(kind : &Bool_Compare) -> Bool_Compare {
+    match kind {
+        Bool_Compare::Equal => {
+            return Bool_Compare::Different;
+        },
+        Bool_Compare::Different => {
+            return Bool_Compare::Equal;
+        },
+        Bool_Compare::Greater => {
+            return Bool_Compare::LowerOrEqual;
+        },
+        Bool_Compare::GreaterOrEqual => {
+            return Bool_Compare::Lower;
+        },
+        Bool_Compare::Lower => {
+            return Bool_Compare::GreaterOrEqual;
+        },
+        Bool_Compare::LowerOrEqual => {
+            return Bool_Compare::Greater;
+        }
+    }
+}
+
+// operator such that (a op b) is equivalent to (b mirror(op) a)
+fn mirror_compare(kind : &Bool_Compare) -> Bool_Compare {
+    match kind {
+        Bool_Compare::Equal => {
+            return Bool_Compare::Equal;
+        },
+        Bool_Compare::Different => {
+            return Bool_Compare::Different;
+        },
+        Bool_Compare::Greater => {
+            return Bool_Compare::Lower;
+        },
+        Bool_Compare::GreaterOrEqual => {
+            return Bool_Compare::LowerOrEqual;
+        },
+        Bool_Compare::Lower => {
+            return Bool_Compare::Greater;
+        },
+        Bool_Compare::LowerOrEqual => {
+            return Bool_Compare::GreaterOrEqual;
+        }
+    }
+}
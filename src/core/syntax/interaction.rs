@@ -10,6 +10,7 @@
  ********************************************************************************/
 
 use std::cmp;
+use std::collections::HashSet;
 
 use crate::core::syntax::position::*;
 use crate::core::syntax::action::*;
@@ -21,6 +22,43 @@ pub enum ScheduleOperatorKind {
     Par
 }
 
+/*
+    Number of iterations of a loop which remain to be done
+    A loop may only be left once 'min' iterations are done and disappears once 'max' iterations are done
+*/
+#[derive(Clone, PartialEq, Debug)]
+pub struct LoopBounds {
+    pub min : u32,
+    pub max : Option<u32>
+}
+
+impl LoopBounds {
+    pub fn unbounded() -> LoopBounds {
+        return LoopBounds{min:0,max:None};
+    }
+
+    pub fn is_unbounded(&self) -> bool {
+        return self.min == 0 && self.max.is_none();
+    }
+
+    // bounds of the loop remaining once a new iteration has started, none if it was the last one
+    pub fn after_iteration(&self) -> Option<LoopBounds> {
+        let min = self.min.saturating_sub(1);
+        match self.max {
+            None => {
+                return Some( LoopBounds{min,max:None} );
+            },
+            Some( max ) => {
+                if max <= 1 {
+                    return None;
+                } else {
+                    return Some( LoopBounds{min,max:Some(max-1)} );
+                }
+            }
+        }
+    }
+}
+
 #[derive(Clone, PartialEq, Debug)]
 pub enum Interaction {
     Empty,
@@ -29,8 +67,23 @@ pub enum Interaction {
     Seq(Box<Interaction>,Box<Interaction>),
     Alt(Box<Interaction>,Box<Interaction>),
     Par(Box<Interaction>,Box<Interaction>),
-    Loop(ScheduleOperatorKind,Box<Interaction>),
-    Scope(Vec<usize>,Box<Interaction>)
+    Loop(ScheduleOperatorKind,LoopBounds,Box<Interaction>),
+    Opt(Box<Interaction>),
+    // exiting the innermost enclosing loop, identified by the original position of its body
+    // (the whole interaction if there is none), as soon as the sub-interaction is entered
+    Break(Vec<u32>,Box<Interaction>),
+    // forbidden behaviours, the boolean telling whether the fragment has been entered
+    Neg(bool,Box<Interaction>),
+    // behaviours which, once the fragment has been entered, are the only allowed continuations
+    Assert(bool,Box<Interaction>),
+    Scope(Vec<usize>,Box<Interaction>),
+    // lifelines which are created upon entering the sub-interaction and on which no action may occur before that
+    Create(Vec<usize>,Box<Interaction>),
+    // a reference to a named sub-interaction, given by its label in the general context, and expanded as its content
+    Ref(usize,Box<Interaction>),
+    // synchronous call made by the given caller lifeline, the boolean telling whether the call has been emitted
+    // the sub-interaction starts with the call, ends with the reply and contains the handling of the call in between
+    Call(usize,bool,Box<Interaction>)
 }
 
 
@@ -70,12 +123,57 @@ impl Interaction {
                             return Interaction::Par( Box::new(substituted_i1), (*i2).clone() );
                         }
                     },
-                    Interaction::Loop(kind,i1) => {
+                    Interaction::Loop(kind,bounds,i1) => {
+                        let substituted_i1 = (*i1).substitute(subst_int, &(*sub_pos) );
+                        if substituted_i1 == Interaction::Empty {
+                            return Interaction::Empty;
+                        } else {
+                            return Interaction::Loop(kind.clone(), bounds.clone(), Box::new(substituted_i1) );
+                        }
+                    },
+                    Interaction::Opt(i1) => {
+                        let substituted_i1 = (*i1).substitute(subst_int, &(*sub_pos) );
+                        if substituted_i1 == Interaction::Empty {
+                            return Interaction::Empty;
+                        } else {
+                            return Interaction::Opt( Box::new(substituted_i1) );
+                        }
+                    },
+                    Interaction::Create(lf_ids,i1) => {
+                        let substituted_i1 = (*i1).substitute(subst_int, &(*sub_pos) );
+                        if substituted_i1 == Interaction::Empty {
+                            return Interaction::Empty;
+                        } else {
+                            return Interaction::Create( lf_ids.clone(), Box::new(substituted_i1) );
+                        }
+                    },
+                    Interaction::Ref(ref_id,i1) => {
+                        let substituted_i1 = (*i1).substitute(subst_int, &(*sub_pos) );
+                        if substituted_i1 == Interaction::Empty {
+                            return Interaction::Empty;
+                        } else {
+                            return Interaction::Ref( *ref_id, Box::new(substituted_i1) );
+                        }
+                    },
+                    Interaction::Break(break_scope,i1) => {
                         let substituted_i1 = (*i1).substitute(subst_int, &(*sub_pos) );
                         if substituted_i1 == Interaction::Empty {
                             return Interaction::Empty;
                         } else {
-                            return Interaction::Loop(kind.clone(), Box::new(substituted_i1) );
+                            return Interaction::Break( break_scope.clone(), Box::new(substituted_i1) );
+                        }
+                    },
+                    Interaction::Neg(entered,i1) => {
+                        // an exhausted neg fragment is kept so that the forbidden behaviour can be reported
+                        let substituted_i1 = (*i1).substitute(subst_int, &(*sub_pos) );
+                        return Interaction::Neg( *entered, Box::new(substituted_i1) );
+                    },
+                    Interaction::Assert(entered,i1) => {
+                        let substituted_i1 = (*i1).substitute(subst_int, &(*sub_pos) );
+                        if substituted_i1 == Interaction::Empty {
+                            return Interaction::Empty;
+                        } else {
+                            return Interaction::Assert( *entered, Box::new(substituted_i1) );
                         }
                     },
                     Interaction::Scope(scope,i1) => {
@@ -86,6 +184,14 @@ impl Interaction {
                             return Interaction::Scope(scope.clone(), Box::new(substituted_i1) );
                         }
                     },
+                    Interaction::Call(caller,called,i1) => {
+                        let substituted_i1 = (*i1).substitute(subst_int, &(*sub_pos) );
+                        if substituted_i1 == Interaction::Empty {
+                            return Interaction::Empty;
+                        } else {
+                            return Interaction::Call(*caller, *called, Box::new(substituted_i1) );
+                        }
+                    },
                     _ => {
                         panic!("cannot substitute on a position that does not exist within the interaction");
                     }
@@ -129,6 +235,10 @@ impl Interaction {
     }
 
     pub fn decorate_with_initial_positions(&self, prefix : Vec<u32>) -> Interaction {
+        return self.decorate_rec(prefix, &Vec::new());
+    }
+
+    fn decorate_rec(&self, prefix : Vec<u32>, break_scope : &Vec<u32>) -> Interaction {
         match &self {
             &Interaction::Empty => {
                 return Interaction::Empty;
@@ -139,45 +249,81 @@ impl Interaction {
             }, &Interaction::Strict(ref i1, ref i2) => {
                 let mut left = prefix.clone();
                 left.push(1);
-                let new_i1 = i1.decorate_with_initial_positions(left);
+                let new_i1 = i1.decorate_rec(left, break_scope);
                 let mut right = prefix.clone();
                 right.push(2);
-                let new_i2 = i2.decorate_with_initial_positions(right);
+                let new_i2 = i2.decorate_rec(right, break_scope);
                 return Interaction::Strict(Box::new(new_i1),Box::new(new_i2));
             }, &Interaction::Seq(ref i1, ref i2) => {
                 let mut left = prefix.clone();
                 left.push(1);
-                let new_i1 = i1.decorate_with_initial_positions(left);
+                let new_i1 = i1.decorate_rec(left, break_scope);
                 let mut right = prefix.clone();
                 right.push(2);
-                let new_i2 = i2.decorate_with_initial_positions(right);
+                let new_i2 = i2.decorate_rec(right, break_scope);
                 return Interaction::Seq(Box::new(new_i1),Box::new(new_i2));
             }, &Interaction::Par(ref i1, ref i2) => {
                 let mut left = prefix.clone();
                 left.push(1);
-                let new_i1 = i1.decorate_with_initial_positions(left);
+                let new_i1 = i1.decorate_rec(left, break_scope);
                 let mut right = prefix.clone();
                 right.push(2);
-                let new_i2 = i2.decorate_with_initial_positions(right);
+                let new_i2 = i2.decorate_rec(right, break_scope);
                 return Interaction::Par(Box::new(new_i1),Box::new(new_i2));
             }, &Interaction::Alt(ref i1, ref i2) => {
                 let mut left = prefix.clone();
                 left.push(1);
-                let new_i1 = i1.decorate_with_initial_positions(left);
+                let new_i1 = i1.decorate_rec(left, break_scope);
                 let mut right = prefix.clone();
                 right.push(2);
-                let new_i2 = i2.decorate_with_initial_positions(right);
+                let new_i2 = i2.decorate_rec(right, break_scope);
                 return Interaction::Alt(Box::new(new_i1),Box::new(new_i2));
-            }, &Interaction::Loop(ref lkind, ref i1) => {
+            }, &Interaction::Loop(ref lkind, ref bounds, ref i1) => {
+                let mut left = prefix.clone();
+                left.push(1);
+                // breaks within the body exit this loop
+                let new_i1 = i1.decorate_rec(left.clone(), &left);
+                return Interaction::Loop(lkind.clone(),bounds.clone(),Box::new(new_i1));
+            }, &Interaction::Opt(ref i1) => {
+                let mut left = prefix.clone();
+                left.push(1);
+                let new_i1 = i1.decorate_rec(left, break_scope);
+                return Interaction::Opt(Box::new(new_i1));
+            }, &Interaction::Ref(ref_id, ref i1) => {
+                let mut left = prefix.clone();
+                left.push(1);
+                let new_i1 = i1.decorate_rec(left, break_scope);
+                return Interaction::Ref(*ref_id,Box::new(new_i1));
+            }, &Interaction::Create(ref lf_ids, ref i1) => {
+                let mut left = prefix.clone();
+                left.push(1);
+                let new_i1 = i1.decorate_rec(left, break_scope);
+                return Interaction::Create(lf_ids.clone(),Box::new(new_i1));
+            }, &Interaction::Break(_, ref i1) => {
+                let mut left = prefix.clone();
+                left.push(1);
+                let new_i1 = i1.decorate_rec(left, break_scope);
+                return Interaction::Break(break_scope.clone(),Box::new(new_i1));
+            }, &Interaction::Neg(ref entered, ref i1) => {
                 let mut left = prefix.clone();
                 left.push(1);
-                let new_i1 = i1.decorate_with_initial_positions(left);
-                return Interaction::Loop(lkind.clone(),Box::new(new_i1));
+                let new_i1 = i1.decorate_rec(left, break_scope);
+                return Interaction::Neg(*entered,Box::new(new_i1));
+            }, &Interaction::Assert(ref entered, ref i1) => {
+                let mut left = prefix.clone();
+                left.push(1);
+                let new_i1 = i1.decorate_rec(left, break_scope);
+                return Interaction::Assert(*entered,Box::new(new_i1));
             }, &Interaction::Scope(ref scope, ref i1) => {
                 let mut left = prefix.clone();
                 left.push(1);
-                let new_i1 = i1.decorate_with_initial_positions(left);
+                let new_i1 = i1.decorate_rec(left, break_scope);
                 return Interaction::Scope(scope.clone(),Box::new(new_i1));
+            }, &Interaction::Call(ref caller, ref called, ref i1) => {
+                let mut left = prefix.clone();
+                left.push(1);
+                let new_i1 = i1.decorate_rec(left, break_scope);
+                return Interaction::Call(*caller,*called,Box::new(new_i1));
             }
         }
     }
@@ -212,12 +358,22 @@ impl Interaction {
                     &Interaction::Par(ref i1, ref i2) => {
                         return (&*i1).get_sub_interaction( &(*sub_pos) );
                     },
-                    &Interaction::Loop(_ , ref i1) => {
+                    &Interaction::Loop(_ , _, ref i1) => {
+                        return (&*i1).get_sub_interaction( &(*sub_pos) );
+                    },
+                    &Interaction::Opt(ref i1) => {
+                        return (&*i1).get_sub_interaction( &(*sub_pos) );
+                    },
+                    &Interaction::Neg(_, ref i1) | &Interaction::Assert(_, ref i1) | &Interaction::Break(_, ref i1) | &Interaction::Create(_, ref i1)
+                    | &Interaction::Ref(_, ref i1) => {
                         return (&*i1).get_sub_interaction( &(*sub_pos) );
                     },
                     &Interaction::Scope(_, ref i1) => {
                         return (&*i1).get_sub_interaction( &(*sub_pos) );
                     },
+                    &Interaction::Call(_, _, ref i1) => {
+                        return (&*i1).get_sub_interaction( &(*sub_pos) );
+                    },
                     _ => {
                         panic!();
                     }
@@ -259,10 +415,19 @@ impl Interaction {
                 return i1.express_empty() && i2.express_empty();
             }, &Interaction::Alt(ref i1, ref i2) => {
                 return i1.express_empty() || i2.express_empty();
-            }, &Interaction::Loop(_, _) => {
+            }, &Interaction::Loop(_, ref bounds, ref i1) => {
+                return bounds.min == 0 || i1.express_empty();
+            }, &Interaction::Opt(_) | &Interaction::Break(_, _) => {
                 return true;
+            }, &Interaction::Neg(ref entered, _) => {
+                // once entered, a neg fragment only leads to forbidden behaviours
+                return !entered;
+            }, &Interaction::Assert(_, ref i1) | &Interaction::Create(_, ref i1) | &Interaction::Ref(_, ref i1) => {
+                return i1.express_empty();
             }, &Interaction::Scope(_, ref i1) => {
                 return i1.express_empty();
+            }, &Interaction::Call(_, _, ref i1) => {
+                return i1.express_empty();
             }
         }
     }
@@ -281,10 +446,15 @@ impl Interaction {
                 return cmp::max(i1.loop_depth(),i2.loop_depth());
             }, &Interaction::Par(ref i1, ref i2) => {
                 return cmp::max(i1.loop_depth(),i2.loop_depth());
-            }, &Interaction::Loop(_, ref i1) => {
+            }, &Interaction::Loop(_, _, ref i1) => {
                 return 1 + i1.loop_depth();
+            }, &Interaction::Opt(ref i1) | &Interaction::Neg(_, ref i1) | &Interaction::Assert(_, ref i1) | &Interaction::Break(_, ref i1)
+                    | &Interaction::Create(_, ref i1) | &Interaction::Ref(_, ref i1) => {
+                return i1.loop_depth();
             }, &Interaction::Scope(_, ref i1) => {
                 return i1.loop_depth();
+            }, &Interaction::Call(_, _, ref i1) => {
+                return i1.loop_depth();
             }
         }
     }
@@ -308,12 +478,19 @@ impl Interaction {
                     &Interaction::Par(ref i1, ref i2) => {
                         return i1.get_loop_depth_at_pos(&(*sub_pos) );
                     },
-                    &Interaction::Loop(_, ref i1) => {
+                    &Interaction::Loop(_, _, ref i1) => {
                         return 1 + i1.get_loop_depth_at_pos(&(*sub_pos) );
                     },
+                    &Interaction::Opt(ref i1) | &Interaction::Neg(_, ref i1) | &Interaction::Assert(_, ref i1) | &Interaction::Break(_, ref i1)
+                    | &Interaction::Create(_, ref i1) | &Interaction::Ref(_, ref i1) => {
+                        return i1.get_loop_depth_at_pos(&(*sub_pos) );
+                    },
                     &Interaction::Scope(_, ref i1) => {
                         return i1.get_loop_depth_at_pos(&(*sub_pos) );
                     },
+                    &Interaction::Call(_, _, ref i1) => {
+                        return i1.get_loop_depth_at_pos(&(*sub_pos) );
+                    },
                     _ => {
                         panic!("undefined pos");
                     }
@@ -359,10 +536,376 @@ impl Interaction {
                 return i1.avoids(lf_id) && i2.avoids(lf_id);
             }, &Interaction::Alt(ref i1, ref i2) => {
                 return i1.avoids(lf_id) || i2.avoids(lf_id);
-            }, &Interaction::Loop(_, _) => {
+            }, &Interaction::Loop(_, ref bounds, ref i1) => {
+                return bounds.min == 0 || i1.avoids(lf_id);
+            }, &Interaction::Opt(_) | &Interaction::Break(_, _) => {
                 return true;
+            }, &Interaction::Neg(ref entered, ref i1) => {
+                return !entered || i1.avoids(lf_id);
+            }, &Interaction::Assert(_, ref i1) => {
+                return i1.avoids(lf_id);
+            }, &Interaction::Ref(_, ref i1) => {
+                return i1.avoids(lf_id);
+            }, &Interaction::Create(ref lf_ids, ref i1) => {
+                // the creation of a lifeline involves it
+                return !lf_ids.contains(&lf_id) && i1.avoids(lf_id);
             }, &Interaction::Scope(_, ref i1) => {
                 return i1.avoids(lf_id);
+            }, &Interaction::Call(_, _, ref i1) => {
+                return i1.avoids(lf_id);
+            }
+        }
+    }
+
+    /*
+        Lifelines which wait for the reply to a call they have emitted
+        and which therefore cannot take part in anything outside of that call
+    */
+    pub fn get_blocked_lifelines(&self) -> HashSet<usize> {
+        match self {
+            &Interaction::Empty | &Interaction::Action(_) => {
+                return HashSet::new();
+            }, &Interaction::Strict(ref i1, ref i2) | &Interaction::Seq(ref i1, ref i2) | &Interaction::Alt(ref i1, ref i2) | &Interaction::Par(ref i1, ref i2) => {
+                let mut blocked = i1.get_blocked_lifelines();
+                blocked.extend( i2.get_blocked_lifelines() );
+                return blocked;
+            }, &Interaction::Loop(_, _, ref i1) | &Interaction::Opt(ref i1) | &Interaction::Scope(_, ref i1)
+                    | &Interaction::Neg(_, ref i1) | &Interaction::Assert(_, ref i1) | &Interaction::Break(_, ref i1)
+                    | &Interaction::Create(_, ref i1) | &Interaction::Ref(_, ref i1) => {
+                return i1.get_blocked_lifelines();
+            }, &Interaction::Call(caller, called, ref i1) => {
+                let mut blocked = i1.get_blocked_lifelines();
+                if called {
+                    blocked.insert( caller );
+                }
+                return blocked;
+            }
+        }
+    }
+
+
+    /*
+        Whether a neg fragment has been entered and can be considered as entirely observed,
+        in which case a forbidden behaviour has been exhibited
+    */
+    pub fn exhibits_negative(&self) -> bool {
+        match self {
+            &Interaction::Empty | &Interaction::Action(_) => {
+                return false;
+            }, &Interaction::Strict(ref i1, ref i2) | &Interaction::Seq(ref i1, ref i2) | &Interaction::Alt(ref i1, ref i2) | &Interaction::Par(ref i1, ref i2) => {
+                return i1.exhibits_negative() || i2.exhibits_negative();
+            }, &Interaction::Loop(_, _, ref i1) | &Interaction::Opt(ref i1) | &Interaction::Scope(_, ref i1)
+                    | &Interaction::Call(_, _, ref i1) | &Interaction::Assert(_, ref i1) | &Interaction::Break(_, ref i1)
+                    | &Interaction::Create(_, ref i1) | &Interaction::Ref(_, ref i1) => {
+                return i1.exhibits_negative();
+            }, &Interaction::Neg(entered, ref i1) => {
+                return (entered && i1.express_empty()) || i1.exhibits_negative();
+            }
+        }
+    }
+
+    /*
+        Whether the interaction contains a neg or an assert fragment, entered or not
+    */
+    pub fn contains_neg_or_assert(&self) -> bool {
+        match self {
+            &Interaction::Empty | &Interaction::Action(_) => {
+                return false;
+            }, &Interaction::Strict(ref i1, ref i2) | &Interaction::Seq(ref i1, ref i2) | &Interaction::Alt(ref i1, ref i2) | &Interaction::Par(ref i1, ref i2) => {
+                return i1.contains_neg_or_assert() || i2.contains_neg_or_assert();
+            }, &Interaction::Loop(_, _, ref i1) | &Interaction::Opt(ref i1) | &Interaction::Scope(_, ref i1)
+                    | &Interaction::Call(_, _, ref i1) | &Interaction::Break(_, ref i1)
+                    | &Interaction::Create(_, ref i1) | &Interaction::Ref(_, ref i1) => {
+                return i1.contains_neg_or_assert();
+            }, &Interaction::Neg(_, _) | &Interaction::Assert(_, _) => {
+                return true;
+            }
+        }
+    }
+
+    /*
+        Whether the interaction contains a neg fragment, entered or not
+    */
+    pub fn contains_neg(&self) -> bool {
+        match self {
+            &Interaction::Empty | &Interaction::Action(_) => {
+                return false;
+            }, &Interaction::Strict(ref i1, ref i2) | &Interaction::Seq(ref i1, ref i2) | &Interaction::Alt(ref i1, ref i2) | &Interaction::Par(ref i1, ref i2) => {
+                return i1.contains_neg() || i2.contains_neg();
+            }, &Interaction::Loop(_, _, ref i1) | &Interaction::Opt(ref i1) | &Interaction::Scope(_, ref i1)
+                    | &Interaction::Call(_, _, ref i1) | &Interaction::Assert(_, ref i1) | &Interaction::Break(_, ref i1)
+                    | &Interaction::Create(_, ref i1) | &Interaction::Ref(_, ref i1) => {
+                return i1.contains_neg();
+            }, &Interaction::Neg(_, _) => {
+                return true;
+            }
+        }
+    }
+
+    /*
+        Whether a neg fragment has been entered and is not yet completed
+    */
+    pub fn is_within_neg(&self) -> bool {
+        match self {
+            &Interaction::Empty | &Interaction::Action(_) => {
+                return false;
+            }, &Interaction::Strict(ref i1, ref i2) | &Interaction::Seq(ref i1, ref i2) | &Interaction::Alt(ref i1, ref i2) | &Interaction::Par(ref i1, ref i2) => {
+                return i1.is_within_neg() || i2.is_within_neg();
+            }, &Interaction::Loop(_, _, ref i1) | &Interaction::Opt(ref i1) | &Interaction::Scope(_, ref i1)
+                    | &Interaction::Call(_, _, ref i1) | &Interaction::Assert(_, ref i1) | &Interaction::Break(_, ref i1)
+                    | &Interaction::Create(_, ref i1) | &Interaction::Ref(_, ref i1) => {
+                return i1.is_within_neg();
+            }, &Interaction::Neg(entered, ref i1) => {
+                return entered || i1.is_within_neg();
+            }
+        }
+    }
+
+    /*
+        Whether an assert fragment has been entered and is not yet completed,
+        in which case deviating from it is a failure
+    */
+    pub fn is_within_assert(&self) -> bool {
+        match self {
+            &Interaction::Empty | &Interaction::Action(_) => {
+                return false;
+            }, &Interaction::Strict(ref i1, ref i2) | &Interaction::Seq(ref i1, ref i2) | &Interaction::Alt(ref i1, ref i2) | &Interaction::Par(ref i1, ref i2) => {
+                return i1.is_within_assert() || i2.is_within_assert();
+            }, &Interaction::Loop(_, _, ref i1) | &Interaction::Opt(ref i1) | &Interaction::Scope(_, ref i1)
+                    | &Interaction::Call(_, _, ref i1) | &Interaction::Neg(_, ref i1) | &Interaction::Break(_, ref i1)
+                    | &Interaction::Create(_, ref i1) | &Interaction::Ref(_, ref i1) => {
+                return i1.is_within_assert();
+            }, &Interaction::Assert(entered, ref i1) => {
+                return entered || i1.is_within_assert();
+            }
+        }
+    }
+
+    /*
+        Whether the action at the given position belongs to a neg fragment
+    */
+    pub fn is_position_within_neg(&self, my_pos : &Position) -> bool {
+        match (self,my_pos) {
+            (_,Position::Epsilon) => {
+                return false;
+            },
+            (&Interaction::Neg(_, _),_) => {
+                return true;
+            },
+            (&Interaction::Strict(ref i1, _),Position::Left(sub_pos)) | (&Interaction::Seq(ref i1, _),Position::Left(sub_pos))
+                    | (&Interaction::Alt(ref i1, _),Position::Left(sub_pos)) | (&Interaction::Par(ref i1, _),Position::Left(sub_pos))
+                    | (&Interaction::Loop(_, _, ref i1),Position::Left(sub_pos)) | (&Interaction::Opt(ref i1),Position::Left(sub_pos))
+                    | (&Interaction::Scope(_, ref i1),Position::Left(sub_pos)) | (&Interaction::Call(_, _, ref i1),Position::Left(sub_pos))
+                    | (&Interaction::Assert(_, ref i1),Position::Left(sub_pos)) | (&Interaction::Break(_, ref i1),Position::Left(sub_pos))
+                    | (&Interaction::Create(_, ref i1),Position::Left(sub_pos)) | (&Interaction::Ref(_, ref i1),Position::Left(sub_pos)) => {
+                return i1.is_position_within_neg(sub_pos);
+            },
+            (&Interaction::Strict(_, ref i2),Position::Right(sub_pos)) | (&Interaction::Seq(_, ref i2),Position::Right(sub_pos))
+                    | (&Interaction::Alt(_, ref i2),Position::Right(sub_pos)) | (&Interaction::Par(_, ref i2),Position::Right(sub_pos)) => {
+                return i2.is_position_within_neg(sub_pos);
+            },
+            _ => {
+                panic!("undefined pos");
+            }
+        }
+    }
+
+
+    /*
+        Returns the scope and the sub-interaction of the first break fragment on the way to the given position,
+        along with the position relative to that sub-interaction
+    */
+    pub fn get_break_on_path(&self, my_pos : &Position) -> Option<(&Vec<u32>,&Interaction,Position)> {
+        match (self,my_pos) {
+            (_,Position::Epsilon) => {
+                return None;
+            },
+            (&Interaction::Break(ref break_scope, ref i1),Position::Left(sub_pos)) => {
+                return Some( (break_scope,&*i1,(**sub_pos).clone()) );
+            },
+            (&Interaction::Strict(ref i1, _),Position::Left(sub_pos)) | (&Interaction::Seq(ref i1, _),Position::Left(sub_pos))
+                    | (&Interaction::Alt(ref i1, _),Position::Left(sub_pos)) | (&Interaction::Par(ref i1, _),Position::Left(sub_pos))
+                    | (&Interaction::Loop(_, _, ref i1),Position::Left(sub_pos)) | (&Interaction::Opt(ref i1),Position::Left(sub_pos))
+                    | (&Interaction::Scope(_, ref i1),Position::Left(sub_pos)) | (&Interaction::Call(_, _, ref i1),Position::Left(sub_pos))
+                    | (&Interaction::Neg(_, ref i1),Position::Left(sub_pos)) | (&Interaction::Assert(_, ref i1),Position::Left(sub_pos))
+                    | (&Interaction::Create(_, ref i1),Position::Left(sub_pos)) | (&Interaction::Ref(_, ref i1),Position::Left(sub_pos)) => {
+                return i1.get_break_on_path(sub_pos);
+            },
+            (&Interaction::Strict(_, ref i2),Position::Right(sub_pos)) | (&Interaction::Seq(_, ref i2),Position::Right(sub_pos))
+                    | (&Interaction::Alt(_, ref i2),Position::Right(sub_pos)) | (&Interaction::Par(_, ref i2),Position::Right(sub_pos)) => {
+                return i2.get_break_on_path(sub_pos);
+            },
+            _ => {
+                panic!("undefined pos");
+            }
+        }
+    }
+
+    /*
+        Whether all the actions of the interaction come from the part of the initial interaction
+        located at the given original position
+    */
+    pub fn is_within_original_position(&self, orig_prefix : &Vec<u32>) -> bool {
+        match self {
+            &Interaction::Empty => {
+                return true;
+            }, &Interaction::Action(ref act) => {
+                match &act.original_position {
+                    None => {
+                        return false;
+                    },
+                    Some( orig_pos ) => {
+                        return orig_pos.starts_with(orig_prefix);
+                    }
+                }
+            }, &Interaction::Strict(ref i1, ref i2) | &Interaction::Seq(ref i1, ref i2) | &Interaction::Alt(ref i1, ref i2) | &Interaction::Par(ref i1, ref i2) => {
+                return i1.is_within_original_position(orig_prefix) && i2.is_within_original_position(orig_prefix);
+            }, &Interaction::Loop(_, _, ref i1) | &Interaction::Opt(ref i1) | &Interaction::Scope(_, ref i1) | &Interaction::Call(_, _, ref i1)
+                    | &Interaction::Neg(_, ref i1) | &Interaction::Assert(_, ref i1) | &Interaction::Break(_, ref i1)
+                    | &Interaction::Create(_, ref i1) | &Interaction::Ref(_, ref i1) => {
+                return i1.is_within_original_position(orig_prefix);
+            }
+        }
+    }
+
+
+    /*
+        Replaces every reference to a named sub-interaction by its content
+    */
+    pub fn expand_references(&self) -> Interaction {
+        match self {
+            &Interaction::Empty | &Interaction::Action(_) => {
+                return self.clone();
+            },
+            &Interaction::Strict(ref i1, ref i2) => {
+                return Interaction::Strict( Box::new(i1.expand_references()), Box::new(i2.expand_references()) );
+            },
+            &Interaction::Seq(ref i1, ref i2) => {
+                return Interaction::Seq( Box::new(i1.expand_references()), Box::new(i2.expand_references()) );
+            },
+            &Interaction::Alt(ref i1, ref i2) => {
+                return Interaction::Alt( Box::new(i1.expand_references()), Box::new(i2.expand_references()) );
+            },
+            &Interaction::Par(ref i1, ref i2) => {
+                return Interaction::Par( Box::new(i1.expand_references()), Box::new(i2.expand_references()) );
+            },
+            &Interaction::Loop(ref lkind, ref bounds, ref i1) => {
+                return Interaction::Loop( lkind.clone(), bounds.clone(), Box::new(i1.expand_references()) );
+            },
+            &Interaction::Opt(ref i1) => {
+                return Interaction::Opt( Box::new(i1.expand_references()) );
+            },
+            &Interaction::Break(ref break_scope, ref i1) => {
+                return Interaction::Break( break_scope.clone(), Box::new(i1.expand_references()) );
+            },
+            &Interaction::Neg(entered, ref i1) => {
+                return Interaction::Neg( entered, Box::new(i1.expand_references()) );
+            },
+            &Interaction::Assert(entered, ref i1) => {
+                return Interaction::Assert( entered, Box::new(i1.expand_references()) );
+            },
+            &Interaction::Scope(ref scope, ref i1) => {
+                return Interaction::Scope( scope.clone(), Box::new(i1.expand_references()) );
+            },
+            &Interaction::Call(caller, entered, ref i1) => {
+                return Interaction::Call( caller, entered, Box::new(i1.expand_references()) );
+            },
+            &Interaction::Create(ref lf_ids, ref i1) => {
+                return Interaction::Create( lf_ids.clone(), Box::new(i1.expand_references()) );
+            },
+            &Interaction::Ref(_, ref i1) => {
+                return i1.expand_references();
+            }
+        }
+    }
+
+    /*
+        Annotates every action of the interaction with the given tags
+        Tags of a fragment are those of all the actions it contains
+    */
+    pub fn add_tags(&self, tags : &Vec<usize>) -> Interaction {
+        match self {
+            &Interaction::Empty => {
+                return Interaction::Empty;
+            },
+            &Interaction::Action(ref act) => {
+                let mut tagged_act = act.clone();
+                for tag_id in tags {
+                    if !tagged_act.tags.contains(tag_id) {
+                        tagged_act.tags.push( *tag_id );
+                    }
+                }
+                return Interaction::Action(tagged_act);
+            },
+            &Interaction::Strict(ref i1, ref i2) => {
+                return Interaction::Strict( Box::new(i1.add_tags(tags)), Box::new(i2.add_tags(tags)) );
+            },
+            &Interaction::Seq(ref i1, ref i2) => {
+                return Interaction::Seq( Box::new(i1.add_tags(tags)), Box::new(i2.add_tags(tags)) );
+            },
+            &Interaction::Alt(ref i1, ref i2) => {
+                return Interaction::Alt( Box::new(i1.add_tags(tags)), Box::new(i2.add_tags(tags)) );
+            },
+            &Interaction::Par(ref i1, ref i2) => {
+                return Interaction::Par( Box::new(i1.add_tags(tags)), Box::new(i2.add_tags(tags)) );
+            },
+            &Interaction::Loop(ref lkind, ref bounds, ref i1) => {
+                return Interaction::Loop( lkind.clone(), bounds.clone(), Box::new(i1.add_tags(tags)) );
+            },
+            &Interaction::Opt(ref i1) => {
+                return Interaction::Opt( Box::new(i1.add_tags(tags)) );
+            },
+            &Interaction::Break(ref break_scope, ref i1) => {
+                return Interaction::Break( break_scope.clone(), Box::new(i1.add_tags(tags)) );
+            },
+            &Interaction::Neg(entered, ref i1) => {
+                return Interaction::Neg( entered, Box::new(i1.add_tags(tags)) );
+            },
+            &Interaction::Assert(entered, ref i1) => {
+                return Interaction::Assert( entered, Box::new(i1.add_tags(tags)) );
+            },
+            &Interaction::Scope(ref scope, ref i1) => {
+                return Interaction::Scope( scope.clone(), Box::new(i1.add_tags(tags)) );
+            },
+            &Interaction::Call(caller, entered, ref i1) => {
+                return Interaction::Call( caller, entered, Box::new(i1.add_tags(tags)) );
+            },
+            &Interaction::Create(ref lf_ids, ref i1) => {
+                return Interaction::Create( lf_ids.clone(), Box::new(i1.add_tags(tags)) );
+            },
+            &Interaction::Ref(ref_id, ref i1) => {
+                return Interaction::Ref( ref_id, Box::new(i1.add_tags(tags)) );
+            }
+        }
+    }
+
+    /*
+        Returns the lifelines created by the '@create' fragments on the way to the given position
+    */
+    pub fn get_lifelines_created_on_path(&self, my_pos : &Position) -> HashSet<usize> {
+        match (self,my_pos) {
+            (_,Position::Epsilon) => {
+                return HashSet::new();
+            },
+            (&Interaction::Create(ref lf_ids, ref i1),Position::Left(sub_pos)) => {
+                let mut created = i1.get_lifelines_created_on_path(sub_pos);
+                created.extend( lf_ids.iter().cloned() );
+                return created;
+            },
+            (&Interaction::Strict(ref i1, _),Position::Left(sub_pos)) | (&Interaction::Seq(ref i1, _),Position::Left(sub_pos))
+                    | (&Interaction::Alt(ref i1, _),Position::Left(sub_pos)) | (&Interaction::Par(ref i1, _),Position::Left(sub_pos))
+                    | (&Interaction::Loop(_, _, ref i1),Position::Left(sub_pos)) | (&Interaction::Opt(ref i1),Position::Left(sub_pos))
+                    | (&Interaction::Scope(_, ref i1),Position::Left(sub_pos)) | (&Interaction::Call(_, _, ref i1),Position::Left(sub_pos))
+                    | (&Interaction::Neg(_, ref i1),Position::Left(sub_pos)) | (&Interaction::Assert(_, ref i1),Position::Left(sub_pos))
+                    | (&Interaction::Break(_, ref i1),Position::Left(sub_pos)) | (&Interaction::Ref(_, ref i1),Position::Left(sub_pos)) => {
+                return i1.get_lifelines_created_on_path(sub_pos);
+            },
+            (&Interaction::Strict(_, ref i2),Position::Right(sub_pos)) | (&Interaction::Seq(_, ref i2),Position::Right(sub_pos))
+                    | (&Interaction::Alt(_, ref i2),Position::Right(sub_pos)) | (&Interaction::Par(_, ref i2),Position::Right(sub_pos)) => {
+                return i2.get_lifelines_created_on_path(sub_pos);
+            },
+            _ => {
+                panic!("undefined pos");
             }
         }
     }
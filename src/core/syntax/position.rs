@@ -92,7 +92,6 @@ impl Ord for Position {
     }
 }
 
-/*
 impl Position {
 
     pub fn as_text(&self) -> String {
@@ -113,11 +112,35 @@ impl Position {
         }
     }
 
-}
-
-
-
-
+    pub fn from_text(pos_text : &str) -> Option<Position> {
+        if pos_text.starts_with(SYNTAX_POSITION_EPSILON) {
+            if pos_text.len() == SYNTAX_POSITION_EPSILON.len() {
+                return Some( Position::Epsilon );
+            } else {
+                return None;
+            }
+        } else if pos_text.starts_with(SYNTAX_POSITION_LEFT) {
+            match Position::from_text( &pos_text[SYNTAX_POSITION_LEFT.len()..] ) {
+                None => {
+                    return None;
+                },
+                Some( sub_pos ) => {
+                    return Some( Position::Left( Box::new(sub_pos) ) );
+                }
+            }
+        } else if pos_text.starts_with(SYNTAX_POSITION_RIGHT) {
+            match Position::from_text( &pos_text[SYNTAX_POSITION_RIGHT.len()..] ) {
+                None => {
+                    return None;
+                },
+                Some( sub_pos ) => {
+                    return Some( Position::Right( Box::new(sub_pos) ) );
+                }
+            }
+        } else {
+            return None;
+        }
+    }
 
+}
 
-*/
@@ -14,7 +14,7 @@ use std::collections::HashSet;
 use crate::core::syntax::data::generic::TD_Generic;
 use crate::core::syntax::data::builtin::float::TD_Float;
 
-use crate::core::syntax::action::ObservableAction;
+use crate::core::syntax::action::{ObservableAction,CommunicationKind};
 
 #[derive(Clone, PartialEq, Debug)]
 pub enum TraceActionKind {
@@ -28,6 +28,7 @@ pub struct TraceAction {
     pub lf_id : usize,
     pub act_kind : TraceActionKind,
     pub ms_id : usize,
+    pub com_kind : CommunicationKind,
     pub arguments : Vec<TD_Generic>
 }
 
@@ -43,6 +44,9 @@ impl TraceAction {
         if self.act_kind != model_action.get_action_kind() {
             return false;
         }
+        if self.com_kind != model_action.com_kind {
+            return false;
+        }
         if self.arguments.len() != model_action.params.len() {
             return false;
         }
@@ -15,22 +15,27 @@ use pest::iterators::{Pair,Pairs};
 
 use crate::core::syntax::action::*;
 use crate::core::context::general::GeneralContext;
+use crate::core::syntax::data::td_type::TD_DataType;
+use crate::core::syntax::data::generic::TD_Generic;
+use crate::core::syntax::data::var_ref::VariableReference;
+use crate::core::syntax::data::builtin::bool::TD_Bool;
+use crate::core::syntax::data::builtin::integer::TD_Integer;
+use crate::core::syntax::data::builtin::float::TD_Float;
+use crate::core::syntax::data::builtin::string::TD_String;
 
 use crate::from_text::parser::*;
 use crate::from_text::error::HibouParsingError;
+use crate::from_text::reference::ReferenceExpansion;
 
 use crate::from_text::data::generic::parse_data;
 use crate::from_text::action::lf_act::parse_lifeline_action;
 
-use crate::core::syntax::data::generic::TD_Generic;
-use crate::core::syntax::data::td_type::TD_DataType;
-
+/*
+    Parameters are type checked against the message specification once the whole interaction is parsed
+*/
 fn parse_message_parameters(gen_ctx : &GeneralContext,
-                            msg_param_pair : Pair<Rule>,
-                            ms_name : &String,
-                            ms_specs : Vec<(TD_DataType,Option<String>)>) -> Result<Vec<ValueOrNewFresh>,HibouParsingError> {
+                            msg_param_pair : Pair<Rule>) -> Result<Vec<ValueOrNewFresh>,HibouParsingError> {
     let mut params : Vec<ValueOrNewFresh> = Vec::new();
-    let mut current_id : usize = 0;
     for value_or_new_fresh_pair in msg_param_pair.into_inner() {
         match value_or_new_fresh_pair.as_rule() {
             Rule::TD_VALUE => {
@@ -39,21 +44,7 @@ fn parse_message_parameters(gen_ctx : &GeneralContext,
                         return Err(e);
                     },
                     Ok( carried_data ) => {
-                        match ms_specs.get(current_id) {
-                            None => {
-                                return Err( HibouParsingError::UnknownMessageParameter(  ms_name.clone(), current_id ) );
-                            },
-                            Some( (expected_type,_) ) => {
-                                let got_type = carried_data.get_td_type();
-                                if &got_type != expected_type {
-                                    return Err( HibouParsingError::WrongMessageParameterType(expected_type.clone(),
-                                                                                             got_type,
-                                                                                             ms_name.clone(),
-                                                                                             format!("{:?}", ms_specs) ) );
-                                }
-                                params.push( ValueOrNewFresh::Value(carried_data) );
-                            }
-                        }
+                        params.push( ValueOrNewFresh::Value(carried_data) );
                     }
                 }
             },
@@ -64,32 +55,25 @@ fn parse_message_parameters(gen_ctx : &GeneralContext,
                 panic!("what rule then ? : {:?}", value_or_new_fresh_pair.as_rule() );
             }
         }
-        current_id = current_id + 1;
-    }
-    let arg_count = params.len();
-    if arg_count != ms_specs.len() {
-        return Err( HibouParsingError::WrongMessageParametersNumber(ms_specs.len(), arg_count,ms_name.clone()) );
     }
     return Ok( params );
 }
 
-pub fn parse_reception(gen_ctx : &GeneralContext, contents : &mut Pairs<Rule>) -> Result<ObservableAction,HibouParsingError> {
+pub fn parse_reception(gen_ctx : &GeneralContext, expansion : &ReferenceExpansion, contents : &mut Pairs<Rule>) -> Result<ObservableAction,HibouParsingError> {
     let message_name_pair = contents.next().unwrap();
-    let message_name : String = message_name_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect();
+    let message_name : String = expansion.rename( message_name_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect() );
     match gen_ctx.get_ms_id( &message_name ) {
         None => {
-            return Err( HibouParsingError::MissingMessageDeclarationError(message_name) );
+            return Err( HibouParsingError::MissingMessageDeclarationError(message_name).located_at(&message_name_pair) );
         },
         Some( ms_id ) => {
-            let ms_specs = gen_ctx.get_ms_spec(ms_id).unwrap();
-            // ***
             let params : Vec<ValueOrNewFresh>;
             let lf_act : LifelineAction;
             let next_pair =  contents.next().unwrap();
             let lf_pair : Pair<Rule>;
             match next_pair.as_rule() {
                 Rule::SD_MESSAGE_PARAMETERS => {
-                    match parse_message_parameters(gen_ctx, next_pair, &message_name, ms_specs) {
+                    match parse_message_parameters(gen_ctx, next_pair) {
                         Err(e) => {
                             return Err(e);
                         },
@@ -108,7 +92,7 @@ pub fn parse_reception(gen_ctx : &GeneralContext, contents : &mut Pairs<Rule>) -
                 }
             }
             // ***
-            match parse_lifeline_action(gen_ctx, lf_pair, &Some(ms_id)) {
+            match parse_lifeline_action(gen_ctx,expansion, lf_pair, &Some(ms_id)) {
                 Err(e) => {
                     return Err(e);
                 },
@@ -118,7 +102,9 @@ pub fn parse_reception(gen_ctx : &GeneralContext, contents : &mut Pairs<Rule>) -
                         act_kind:ObservableActionKind::Reception,
                         ms_id:ms_id,
                         params:params,
-                        original_position:None};
+                        com_kind:CommunicationKind::Asynchronous,
+                        original_position:None,
+                        tags:Vec::new()};
                     return Ok( reception_act );
                 }
             }
@@ -126,17 +112,18 @@ pub fn parse_reception(gen_ctx : &GeneralContext, contents : &mut Pairs<Rule>) -
     }
 }
 
-pub fn parse_emission(gen_ctx : &GeneralContext, contents : &mut Pairs<Rule>) -> Result<ObservableAction,HibouParsingError> {
+pub fn parse_emission(gen_ctx : &GeneralContext, expansion : &ReferenceExpansion, contents : &mut Pairs<Rule>) -> Result<ObservableAction,HibouParsingError> {
     // ***
-    match parse_lifeline_action(gen_ctx, contents.next().unwrap(),&None) {
+    match parse_lifeline_action(gen_ctx,expansion, contents.next().unwrap(),&None) {
         Err(e) => {
             return Err(e);
         },
         Ok( lf_act ) => {
-            let message_name : String = contents.next().unwrap().as_str().chars().filter(|c| !c.is_whitespace()).collect();
+            let message_name_pair = contents.next().unwrap();
+            let message_name : String = expansion.rename( message_name_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect() );
             match gen_ctx.get_ms_id( &message_name ) {
                 None => {
-                    return Err( HibouParsingError::MissingMessageDeclarationError( message_name ) );
+                    return Err( HibouParsingError::MissingMessageDeclarationError( message_name ).located_at(&message_name_pair) );
                 },
                 Some( ms_id ) => {
                     let mut targets : Vec<LifelineAction>;
@@ -150,8 +137,7 @@ pub fn parse_emission(gen_ctx : &GeneralContext, contents : &mut Pairs<Rule>) ->
                             let target_pair_opt : Option<Pair<Rule>>;
                             match next_pair.as_rule() {
                                 Rule::SD_MESSAGE_PARAMETERS => {
-                                    let ms_specs = gen_ctx.get_ms_spec(ms_id).unwrap();
-                                    match parse_message_parameters(gen_ctx, next_pair, &message_name, ms_specs) {
+                                    match parse_message_parameters(gen_ctx, next_pair) {
                                         Err(e) => {
                                             return Err(e);
                                         },
@@ -179,7 +165,7 @@ pub fn parse_emission(gen_ctx : &GeneralContext, contents : &mut Pairs<Rule>) ->
                                     let inner_target_pair = target_pair.into_inner().next().unwrap();
                                     match inner_target_pair.as_rule() {
                                         Rule::SD_LIFELINE_ACTION => {
-                                            match parse_lifeline_action(gen_ctx,inner_target_pair,&Some(ms_id)) {
+                                            match parse_lifeline_action(gen_ctx,expansion,inner_target_pair,&Some(ms_id)) {
                                                 Err(e) => {
                                                     return Err(e);
                                                 },
@@ -190,7 +176,7 @@ pub fn parse_emission(gen_ctx : &GeneralContext, contents : &mut Pairs<Rule>) ->
                                         },
                                         Rule::SD_EMISSION_TARGETS => {
                                             for targ_name in inner_target_pair.into_inner() {
-                                                match parse_lifeline_action(gen_ctx,targ_name, &Some(ms_id)) {
+                                                match parse_lifeline_action(gen_ctx,expansion,targ_name, &Some(ms_id)) {
                                                     Err(e) => {
                                                         return Err(e);
                                                     },
@@ -213,7 +199,9 @@ pub fn parse_emission(gen_ctx : &GeneralContext, contents : &mut Pairs<Rule>) ->
                         act_kind:ObservableActionKind::Emission(targets),
                         ms_id:ms_id,
                         params:params,
-                        original_position:None};
+                        com_kind:CommunicationKind::Asynchronous,
+                        original_position:None,
+                        tags:Vec::new()};
                     return Ok( emission_act );
                 }
             }
@@ -221,3 +209,180 @@ pub fn parse_emission(gen_ctx : &GeneralContext, contents : &mut Pairs<Rule>) ->
     }
 }
 
+fn parse_call_lifeline(gen_ctx : &GeneralContext, expansion : &ReferenceExpansion, lf_name_pair : &Pair<Rule>) -> Result<usize,HibouParsingError> {
+    let lf_name : String = expansion.rename( lf_name_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect() );
+    match gen_ctx.get_lf_id( &lf_name ) {
+        None => {
+            return Err( HibouParsingError::MissingLifelineDeclarationError(lf_name).located_at(lf_name_pair) );
+        },
+        Some( lf_id ) => {
+            return Ok( lf_id );
+        }
+    }
+}
+
+/*
+    On the caller, the result of the call is read from the only parameter of the reply
+*/
+fn returned_value_reference(ms_id : usize, return_type : &TD_DataType) -> TD_Generic {
+    let pr_ref = VariableReference::MSG_PARAMETER(ms_id,0);
+    match return_type {
+        TD_DataType::Bool => {
+            return TD_Generic::Bool( TD_Bool::Reference(pr_ref) );
+        },
+        TD_DataType::Integer => {
+            return TD_Generic::Integer( TD_Integer::Reference(pr_ref) );
+        },
+        TD_DataType::Float => {
+            return TD_Generic::Float( TD_Float::Reference(pr_ref) );
+        },
+        TD_DataType::String => {
+            return TD_Generic::String( TD_String::Reference(pr_ref) );
+        }
+    }
+}
+
+/*
+    Parses a synchronous call into the call itself, from the caller to the callee,
+    and the matching reply, from the callee back to the caller
+    The reply carries the value returned by the callee (evaluated on the callee)
+    and assigns it on the caller to the result variable, if any
+    Returns the identifier of the caller, which is blocked until it receives the reply
+*/
+pub fn parse_call(gen_ctx : &GeneralContext,
+                  expansion : &ReferenceExpansion,
+                  call_action_pair : Pair<Rule>,
+                  call_return_pair : Option<Pair<Rule>>) -> Result<(usize,ObservableAction,ObservableAction),HibouParsingError> {
+    let mut contents = call_action_pair.into_inner();
+    let mut next_pair = contents.next().unwrap();
+    // ***
+    let mut result_pair : Option<Pair<Rule>> = None;
+    if next_pair.as_rule() == Rule::SD_CALL_RESULT {
+        result_pair = Some( next_pair );
+        next_pair = contents.next().unwrap();
+    }
+    // ***
+    let caller_lf_id : usize;
+    match parse_call_lifeline(gen_ctx, expansion, &next_pair) {
+        Err(e) => {
+            return Err(e);
+        },
+        Ok( got_lf_id ) => {
+            caller_lf_id = got_lf_id;
+        }
+    }
+    // ***
+    let message_name_pair = contents.next().unwrap();
+    let message_name : String = expansion.rename( message_name_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect() );
+    let ms_id : usize;
+    match gen_ctx.get_ms_id( &message_name ) {
+        None => {
+            return Err( HibouParsingError::MissingMessageDeclarationError(message_name).located_at(&message_name_pair) );
+        },
+        Some( got_ms_id ) => {
+            ms_id = got_ms_id;
+        }
+    }
+    let return_type = gen_ctx.get_ms_return_type(ms_id);
+    // ***
+    let params : Vec<ValueOrNewFresh>;
+    next_pair = contents.next().unwrap();
+    match next_pair.as_rule() {
+        Rule::SD_MESSAGE_PARAMETERS => {
+            match parse_message_parameters(gen_ctx, next_pair) {
+                Err(e) => {
+                    return Err(e);
+                },
+                Ok( msg_parameters ) => {
+                    params = msg_parameters;
+                }
+            }
+            next_pair = contents.next().unwrap();
+        },
+        _ => {
+            params = Vec::new();
+        }
+    }
+    // ***
+    let callee_lf_id : usize;
+    match parse_call_lifeline(gen_ctx, expansion, &next_pair) {
+        Err(e) => {
+            return Err(e);
+        },
+        Ok( got_lf_id ) => {
+            callee_lf_id = got_lf_id;
+        }
+    }
+    if callee_lf_id == caller_lf_id {
+        return Err( HibouParsingError::MalformedCall( format!("lifeline '{}' cannot call itself", next_pair.as_str().trim()) ).located_at(&next_pair) );
+    }
+    // ***
+    let mut caller_postamble : Vec<ActionAmbleItem> = Vec::new();
+    if let Some( got_result_pair ) = result_pair {
+        let var_name_pair = got_result_pair.clone().into_inner().next().unwrap();
+        let var_name : String = var_name_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect();
+        match (gen_ctx.get_vr_id(&var_name),&return_type) {
+            (None,_) => {
+                return Err( HibouParsingError::MissingVariableDeclarationError(var_name).located_at(&var_name_pair) );
+            },
+            (Some(_),None) => {
+                return Err( HibouParsingError::MalformedCall( format!("message '{}' does not return a value to assign to '{}'", gen_ctx.get_ms_name(ms_id).unwrap(), var_name) ).located_at(&got_result_pair) );
+            },
+            (Some(vr_id),Some(got_return_type)) => {
+                let returned = returned_value_reference(ms_id, got_return_type);
+                caller_postamble.push( ActionAmbleItem::Assignment(vr_id, ValueOrNewFresh::Value(returned)) );
+            }
+        }
+    }
+    // ***
+    let mut reply_params : Vec<ValueOrNewFresh> = Vec::new();
+    match (call_return_pair,&return_type) {
+        (None,None) => {},
+        (None,Some(_)) => {
+            // the returned value is left unconstrained
+            reply_params.push( ValueOrNewFresh::NewFresh );
+        },
+        (Some(got_return_pair),None) => {
+            return Err( HibouParsingError::MalformedCall( format!("message '{}' does not return a value", gen_ctx.get_ms_name(ms_id).unwrap()) ).located_at(&got_return_pair) );
+        },
+        (Some(got_return_pair),Some(_)) => {
+            let value_or_new_fresh_pair = got_return_pair.into_inner().next().unwrap();
+            match value_or_new_fresh_pair.as_rule() {
+                Rule::TD_VALUE => {
+                    match parse_data(gen_ctx,value_or_new_fresh_pair,&None) {
+                        Err(e) => {
+                            return Err(e);
+                        },
+                        Ok( returned ) => {
+                            reply_params.push( ValueOrNewFresh::Value(returned) );
+                        }
+                    }
+                },
+                Rule::NEW_FRESH => {
+                    reply_params.push( ValueOrNewFresh::NewFresh );
+                },
+                _ => {
+                    panic!("what rule then ? : {:?}", value_or_new_fresh_pair.as_rule() );
+                }
+            }
+        }
+    }
+    // ***
+    let call_act = ObservableAction{
+        lf_act:LifelineAction{preamble:Vec::new(), lf_id:caller_lf_id, postamble:Vec::new()},
+        act_kind:ObservableActionKind::Emission( vec![LifelineAction{preamble:Vec::new(), lf_id:callee_lf_id, postamble:Vec::new()}] ),
+        ms_id:ms_id,
+        params:params,
+        com_kind:CommunicationKind::Call,
+        original_position:None,
+        tags:Vec::new()};
+    let reply_act = ObservableAction{
+        lf_act:LifelineAction{preamble:Vec::new(), lf_id:callee_lf_id, postamble:Vec::new()},
+        act_kind:ObservableActionKind::Emission( vec![LifelineAction{preamble:Vec::new(), lf_id:caller_lf_id, postamble:caller_postamble}] ),
+        ms_id:ms_id,
+        params:reply_params,
+        com_kind:CommunicationKind::Reply,
+        original_position:None,
+        tags:Vec::new()};
+    return Ok( (caller_lf_id,call_act,reply_act) );
+}
@@ -57,9 +57,10 @@ pub fn parse_amble(gen_ctx : &GeneralContext, amble_pair : Pair<Rule>, opt_ms_id
 fn parse_guards(gen_ctx : &GeneralContext, guard_pairs : Pair<Rule>, opt_ms_id : &Option<usize>) -> Result<Vec<ActionAmbleItem>,HibouParsingError> {
     let mut guards : Vec<ActionAmbleItem> = Vec::new();
     for guard_pair in guard_pairs.into_inner() {
+        let guard_location = guard_pair.clone();
         match parse_logic_expr(gen_ctx,guard_pair, opt_ms_id) {
             Err(e) => {
-                return Err(e);
+                return Err( e.located_at(&guard_location) );
             },
             Ok( td_bool ) => {
                 guards.push( ActionAmbleItem::Guard(td_bool) );
@@ -72,19 +73,20 @@ fn parse_guards(gen_ctx : &GeneralContext, guard_pairs : Pair<Rule>, opt_ms_id :
 fn parse_operations(gen_ctx : &GeneralContext, operations_pairs : Pair<Rule>, opt_ms_id : &Option<usize>) -> Result<Vec<ActionAmbleItem>,HibouParsingError> {
     let mut operations : Vec<ActionAmbleItem> = Vec::new();
     for operation_pair in operations_pairs.into_inner() {
+        let operation_location = operation_pair.clone();
         match operation_pair.as_rule() {
             Rule::OPERATION_RESET => {
                 let var_to_reset_pair = operation_pair.into_inner().next().unwrap();
                 let var_to_reset_name : String = var_to_reset_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect();
                 match gen_ctx.get_vr_id(&var_to_reset_name ) {
                     None => {
-                        return Err( HibouParsingError::MissingVariableDeclarationError( var_to_reset_name ) );
+                        return Err( HibouParsingError::MissingVariableDeclarationError( var_to_reset_name ).located_at(&operation_location) );
                     },
                     Some( vr_id ) => {
                         if gen_ctx.is_clock( vr_id ) {
                             operations.push( ActionAmbleItem::Reset(vr_id) );
                         } else {
-                            return Err( HibouParsingError::ClockMisuse( format!("can only reset clocks ; tried to reset '{:}' of index '{}' which is not a clock - clocks are {:?}", var_to_reset_name,vr_id,gen_ctx.get_clocks() ) ) );
+                            return Err( HibouParsingError::ClockMisuse( format!("can only reset clocks ; tried to reset '{:}' of index '{}' which is not a clock - clocks are {:?}", var_to_reset_name,vr_id,gen_ctx.get_clocks() ) ).located_at(&operation_location) );
                         }
                     }
                 }
@@ -95,7 +97,7 @@ fn parse_operations(gen_ctx : &GeneralContext, operations_pairs : Pair<Rule>, op
                 let value_or_newfresh_pair = op_content.next().unwrap();
                 match gen_ctx.get_vr_id(&vr_name) {
                     None => {
-                        return Err( HibouParsingError::MissingVariableDeclarationError( vr_name ) );
+                        return Err( HibouParsingError::MissingVariableDeclarationError( vr_name ).located_at(&operation_location) );
                     },
                     Some( vr_id ) => {
                         match value_or_newfresh_pair.as_rule() {
@@ -17,6 +17,7 @@ use crate::core::syntax::action::*;
 use crate::core::context::general::GeneralContext;
 
 use crate::from_text::error::HibouParsingError;
+use crate::from_text::reference::ReferenceExpansion;
 
 use crate::from_text::parser::*;
 use crate::from_text::action::amble::parse_amble;
@@ -24,6 +25,7 @@ use crate::from_text::data::generic::parse_data;
 
 
 pub fn parse_lifeline_action(gen_ctx : &GeneralContext,
+                            expansion : &ReferenceExpansion,
                             lifeline_action : Pair<Rule>,
                             opt_ms_id : &Option<usize>) -> Result<LifelineAction,HibouParsingError> {
 
@@ -42,10 +44,11 @@ pub fn parse_lifeline_action(gen_ctx : &GeneralContext,
                 },
                 Ok( amble ) => {
                     preamble = amble;
-                    let lf_name : String = lifeline_action_pairs.next().unwrap().as_str().chars().filter(|c| !c.is_whitespace()).collect();
+                    let lf_name_pair = lifeline_action_pairs.next().unwrap();
+                    let lf_name : String = expansion.rename( lf_name_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect() );
                     match gen_ctx.get_lf_id( &lf_name) {
                         None => {
-                            return Err(HibouParsingError::MissingLifelineDeclarationError(lf_name));
+                            return Err(HibouParsingError::MissingLifelineDeclarationError(lf_name).located_at(&lf_name_pair));
                         },
                         Some( got_lf_id ) => {
                             lf_id = got_lf_id;
@@ -56,10 +59,10 @@ pub fn parse_lifeline_action(gen_ctx : &GeneralContext,
         },
         Rule::LIFELINE_LABEL => {
             preamble = Vec::new();
-            let lf_name : String = first_arg.as_str().chars().filter(|c| !c.is_whitespace()).collect();
+            let lf_name : String = expansion.rename( first_arg.as_str().chars().filter(|c| !c.is_whitespace()).collect() );
             match gen_ctx.get_lf_id( &lf_name) {
                 None => {
-                    return Err(HibouParsingError::MissingLifelineDeclarationError(lf_name));
+                    return Err(HibouParsingError::MissingLifelineDeclarationError(lf_name).located_at(&first_arg));
                 },
                 Some( got_lf_id ) => {
                     lf_id = got_lf_id;
@@ -32,6 +32,20 @@ use crate::from_text::data::logic::parse_logic_expr;
 pub fn parse_data(gen_ctx : &GeneralContext,
                   td_value : Pair<Rule>,
                   opt_ms_id : &Option<usize>) -> Result<TD_Generic,HibouParsingError> {
+    let td_value_location = td_value.clone();
+    match parse_unlocated_data(gen_ctx, td_value, opt_ms_id) {
+        Err(e) => {
+            return Err( e.located_at(&td_value_location) );
+        },
+        Ok( td_generic ) => {
+            return Ok( td_generic );
+        }
+    }
+}
+
+fn parse_unlocated_data(gen_ctx : &GeneralContext,
+                        td_value : Pair<Rule>,
+                        opt_ms_id : &Option<usize>) -> Result<TD_Generic,HibouParsingError> {
     let data_pair = td_value.into_inner().next().unwrap();
     match data_pair.as_rule() {
         Rule::MSG_PRM_REF => {
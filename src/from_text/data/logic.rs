@@ -15,7 +15,6 @@ use pest::iterators::{Pair,Pairs};
 
 use crate::core::context::general::GeneralContext;
 
-use crate::core::syntax::data::td_type::TD_DataType;
 use crate::core::syntax::data::builtin::bool::{TD_Bool,Bool_Compare};
 use crate::core::syntax::data::var_ref::VariableReference;
 
@@ -62,18 +61,11 @@ pub fn parse_logic_expr(gen_ctx : &GeneralContext, logic_pair : Pair<Rule>, opt_
                                 Rule::PRM_LABEL => {
                                     let prm_label : String  = content.as_str().chars().filter(|c| !c.is_whitespace()).collect();
                                     let mut idx : usize = 0;
-                                    for (pr_type,opt_pr_name) in ms_spec {
+                                    for (_,opt_pr_name) in ms_spec {
                                         if let Some(got_pr_name) = opt_pr_name {
                                             if got_pr_name == prm_label {
-                                                let var_ref = VariableReference::MSG_PARAMETER(*ms_id,idx);
-                                                match pr_type {
-                                                    TD_DataType::Bool => {
-                                                        return Ok( TD_Bool::Reference(var_ref) );
-                                                    },
-                                                    _ => {
-                                                        panic!();
-                                                    }
-                                                }
+                                                // the type of the parameter is checked once the whole interaction is parsed
+                                                return Ok( TD_Bool::Reference( VariableReference::MSG_PARAMETER(*ms_id,idx) ) );
                                             }
                                         }
                                         idx = idx +1;
@@ -86,16 +78,8 @@ pub fn parse_logic_expr(gen_ctx : &GeneralContext, logic_pair : Pair<Rule>, opt_
                                         None => {
                                             return Err(HibouParsingError::ParameterUsageError( format!("no parameter number '{}' in message '{}'",pr_id,gen_ctx.get_ms_name(*ms_id).unwrap()) ));
                                         },
-                                        Some( (pr_type,opt_pr_name) ) => {
-                                            let var_ref = VariableReference::MSG_PARAMETER(*ms_id,pr_id);
-                                            match pr_type {
-                                                TD_DataType::Bool => {
-                                                    return Ok( TD_Bool::Reference(var_ref) );
-                                                },
-                                                _ => {
-                                                    panic!();
-                                                }
-                                            }
+                                        Some( _ ) => {
+                                            return Ok( TD_Bool::Reference( VariableReference::MSG_PARAMETER(*ms_id,pr_id) ) );
                                         }
                                     }
                                 },
@@ -115,15 +99,8 @@ pub fn parse_logic_expr(gen_ctx : &GeneralContext, logic_pair : Pair<Rule>, opt_
                     return Err( HibouParsingError::MissingVariableDeclarationError( content_str ) );
                 },
                 Some( vr_id ) => {
-                    let var_ref = VariableReference::VARIABLE(vr_id);
-                    match gen_ctx.get_vr_type( vr_id ).unwrap() {
-                        TD_DataType::Bool => {
-                            return Ok( TD_Bool::Reference(var_ref) );
-                        },
-                        _ => {
-                            return Err( HibouParsingError::MalformedLogicExpression( format!("variable '{}' is not a boolean and appears in a boolean expression",content_str) ));
-                        }
-                    }
+                    // the type of the variable is checked once the whole interaction is parsed
+                    return Ok( TD_Bool::Reference( VariableReference::VARIABLE(vr_id) ) );
                 }
             }
         },
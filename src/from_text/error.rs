@@ -11,13 +11,59 @@
 
 use std::fmt;
 
+use pest::iterators::Pair;
+
 use crate::core::syntax::data::td_type::TD_DataType;
+use crate::from_text::parser::Rule;
+
+/*
+    Position of a parsed element in a source file
+    The text of the line is kept so as to render an excerpt pointing at the element
+*/
+#[derive(Clone, PartialEq, Debug)]
+pub struct SourceLocation {
+    pub file : Option<String>,
+    pub line : usize,
+    pub column : usize,
+    pub line_text : String
+}
+
+impl SourceLocation {
+
+    pub fn from_pair(pair : &Pair<Rule>) -> SourceLocation {
+        let start_pos = pair.as_span().start_pos();
+        let (line,column) = start_pos.line_col();
+        let line_text = start_pos.line_of().trim_end_matches(|c| c == '\n' || c == '\r').to_string();
+        return SourceLocation{file:None,line,column,line_text};
+    }
+
+    pub fn get_excerpt(&self) -> String {
+        let line_number = self.line.to_string();
+        let margin = " ".repeat(line_number.len());
+        // tabulations are kept so that the caret is aligned with the element
+        let caret_offset : String = self.line_text.chars().take(self.column - 1).map(|c| if c == '\t' {'\t'} else {' '}).collect();
+        return format!("{}--> {}\n{} |\n{} | {}\n{} | {}^", margin, self, margin, line_number, self.line_text, margin, caret_offset);
+    }
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.file {
+            None => {
+                return write!(f, "{}:{}", self.line, self.column);
+            },
+            Some( file ) => {
+                return write!(f, "{}:{}:{}", file, self.line, self.column);
+            }
+        }
+    }
+}
 
 #[derive(Debug)]
 pub enum HibouParsingError {
     FileFormatError(String,String),
     FileError(String),
-    MatchError(String),
+    MatchError(pest::error::Error<Rule>),
     // ***
     MissingMessageDeclarationError(String),
     MissingLifelineDeclarationError(String),
@@ -28,6 +74,13 @@ pub enum HibouParsingError {
     MalformedArithmeticExpression(String),
     MalformedLogicExpression(String),
     ClockMisuse(String),
+    MalformedLoopBounds(String),
+    MalformedCall(String),
+    UnpairedReply(String),
+    MalformedCreation(String),
+    MalformedReference(String),
+    MalformedImport(String),
+    NameCollision(String),
     // ***
     UnknownMessageParameter(String,usize),
     WrongMessageParameterType(TD_DataType,TD_DataType,String,String),
@@ -35,7 +88,76 @@ pub enum HibouParsingError {
     TimedTraceAbsentDelay(String),
     // ***
     NonDisjointTraceComponents,
-    HsfSetupError(String)
+    HsfSetupError(String),
+    TypeMismatch(String),
+    // ***
+    Located(SourceLocation,Box<HibouParsingError>),
+    Imported(String,Box<HibouParsingError>),
+    Multiple(Vec<HibouParsingError>)
+}
+
+impl HibouParsingError {
+
+    /*
+        Attaches the location of a pair to the error
+        Errors that are already located keep their innermost (more precise) location
+    */
+    pub fn located_at(self, pair : &Pair<Rule>) -> HibouParsingError {
+        match self {
+            HibouParsingError::Located(_,_) | HibouParsingError::Multiple(_) | HibouParsingError::MatchError(_) => {
+                return self;
+            },
+            _ => {
+                return HibouParsingError::Located( SourceLocation::from_pair(pair), Box::new(self) );
+            }
+        }
+    }
+
+    /*
+        Sets the file in which the located errors occurred
+        Errors already located in a file, e.g. an imported one, keep it
+    */
+    pub fn in_file(self, file_path : &str) -> HibouParsingError {
+        match self {
+            HibouParsingError::MatchError( pest_error ) => {
+                return HibouParsingError::MatchError( pest_error.with_path(file_path) );
+            },
+            HibouParsingError::Located( mut location, sub_error ) => {
+                if location.file.is_none() {
+                    location.file = Some( file_path.to_string() );
+                }
+                return HibouParsingError::Located( location, Box::new(sub_error.in_file(file_path)) );
+            },
+            HibouParsingError::Multiple( sub_errors ) => {
+                return HibouParsingError::Multiple( sub_errors.into_iter().map(|e| e.in_file(file_path)).collect() );
+            },
+            _ => {
+                return self;
+            }
+        }
+    }
+
+    /*
+        Gathers the errors collected while parsing independent elements
+    */
+    pub fn from_several(mut errors : Vec<HibouParsingError>) -> HibouParsingError {
+        assert!(errors.len() > 0);
+        if errors.len() == 1 {
+            return errors.remove(0);
+        }
+        let mut flattened : Vec<HibouParsingError> = Vec::new();
+        for error in errors {
+            match error {
+                HibouParsingError::Multiple( mut sub_errors ) => {
+                    flattened.append( &mut sub_errors );
+                },
+                _ => {
+                    flattened.push( error );
+                }
+            }
+        }
+        return HibouParsingError::Multiple( flattened );
+    }
 }
 
 impl fmt::Display for HibouParsingError {
@@ -47,8 +169,8 @@ impl fmt::Display for HibouParsingError {
             HibouParsingError::FileError(sub_e) => {
                 return write!(f, "{}", format!("error while reading SD conf file : {:}", sub_e));
             },
-            HibouParsingError::MatchError(sub_e) => {
-                return write!(f, "{}", format!("error while parsing SD string : {:}", sub_e));
+            HibouParsingError::MatchError(pest_error) => {
+                return write!(f, "{}", format!("error while parsing SD string :\n{:}", pest_error));
             },
             HibouParsingError::MissingMessageDeclarationError(sub_e) => {
                 return write!(f, "{}", format!("error while parsing ; missing message declaration : {:}", sub_e));
@@ -71,6 +193,27 @@ impl fmt::Display for HibouParsingError {
             HibouParsingError::ClockMisuse(sub_e) => {
                 return write!(f, "{}", format!("error while parsing ; clock misuse : {:}", sub_e));
             },
+            HibouParsingError::MalformedLoopBounds(sub_e) => {
+                return write!(f, "{}", format!("error while parsing ; malformed loop bounds : {:}", sub_e));
+            },
+            HibouParsingError::MalformedCall(sub_e) => {
+                return write!(f, "{}", format!("error while parsing ; malformed call : {:}", sub_e));
+            },
+            HibouParsingError::UnpairedReply(sub_e) => {
+                return write!(f, "{}", format!("error while parsing trace; unpaired reply : {:}", sub_e));
+            },
+            HibouParsingError::MalformedCreation(sub_e) => {
+                return write!(f, "{}", format!("error while parsing ; malformed lifeline creation : {:}", sub_e));
+            },
+            HibouParsingError::MalformedReference(sub_e) => {
+                return write!(f, "{}", format!("error while parsing ; malformed interaction reference : {:}", sub_e));
+            },
+            HibouParsingError::MalformedImport(sub_e) => {
+                return write!(f, "{}", format!("error while parsing ; malformed import : {:}", sub_e));
+            },
+            HibouParsingError::NameCollision(sub_e) => {
+                return write!(f, "{}", format!("error while parsing ; name collision : {:}", sub_e));
+            },
             HibouParsingError::UnknownMessageParameter(ms_name,param_id) => {
                 return write!(f, "{}", format!("error while parsing; unknown message parameter : message {} does not have parameter number {}", ms_name, param_id));
             },
@@ -88,6 +231,22 @@ impl fmt::Display for HibouParsingError {
             },
             HibouParsingError::HsfSetupError(sub_e) => {
                 return write!(f, "{}", format!("error while parsing setup section of .hsf file : {:}", sub_e));
+            },
+            HibouParsingError::TypeMismatch(sub_e) => {
+                return write!(f, "{}", format!("error while parsing ; type mismatch : {:}", sub_e));
+            },
+            HibouParsingError::Located(location, sub_error) => {
+                return write!(f, "{}", format!("{:}\n{:}", sub_error, location.get_excerpt()));
+            },
+            HibouParsingError::Imported(file_path, sub_error) => {
+                return write!(f, "{}", format!("error in imported file '{:}' :\n{:}", file_path, sub_error));
+            },
+            HibouParsingError::Multiple(sub_errors) => {
+                let mut errors_str = format!("{} errors while parsing :", sub_errors.len());
+                for sub_error in sub_errors {
+                    errors_str.push_str( &format!("\n\n{:}", sub_error) );
+                }
+                return write!(f, "{}", errors_str);
             }
         }
     }
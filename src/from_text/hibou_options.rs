@@ -28,11 +28,14 @@ use crate::process::log::ProcessLogger;
 
 use crate::from_text::parser::*;
 use crate::rendering::process::graphic_logger::*;
+use crate::rendering::process::coverage_logger::CoverageProcessLogger;
+use crate::rendering::process::traceability_logger::TraceabilityProcessLogger;
 use crate::process::hibou_process::*;
 
 use crate::process::verdicts::GlobalVerdict;
 use crate::process::process_manager::ProcessPriorities;
 use crate::from_text::hsf_file::ProcessKind;
+use crate::from_text::interaction::parse_tag_label;
 
 
 pub struct HibouOptions {
@@ -40,6 +43,7 @@ pub struct HibouOptions {
     pub strategy : HibouSearchStrategy,
     pub pre_filters : Vec<HibouPreFilter>,
     pub temporality : HibouProcessTemporality,
+    pub engine : HibouProcessEngine,
     pub goal : Option<GlobalVerdict>,
     pub frontier_priorities : ProcessPriorities
 }
@@ -51,9 +55,10 @@ impl HibouOptions {
                strategy : HibouSearchStrategy,
                pre_filters : Vec<HibouPreFilter>,
                temporality : HibouProcessTemporality,
+               engine : HibouProcessEngine,
                goal:Option<GlobalVerdict>,
                frontier_priorities : ProcessPriorities) -> HibouOptions {
-        return HibouOptions{loggers,strategy,pre_filters,temporality,goal,frontier_priorities};
+        return HibouOptions{loggers,strategy,pre_filters,temporality,engine,goal,frontier_priorities};
     }
 
     pub fn default_explore() -> HibouOptions {
@@ -61,6 +66,7 @@ impl HibouOptions {
             strategy:HibouSearchStrategy::BFS,
             pre_filters:vec![HibouPreFilter::MaxLoopInstanciation(1)],
             temporality:HibouProcessTemporality::UnTimed,
+            engine:HibouProcessEngine::Symbolic,
             goal:None,
             frontier_priorities:ProcessPriorities::new(0,0,0)};
     }
@@ -70,6 +76,7 @@ impl HibouOptions {
             strategy:HibouSearchStrategy::BFS,
             pre_filters:Vec::new(),
             temporality:HibouProcessTemporality::UnTimed,
+            engine:HibouProcessEngine::Symbolic,
             goal:Some(GlobalVerdict::Pass),
             frontier_priorities:ProcessPriorities::new(0,0,0)};
     }
@@ -77,7 +84,9 @@ impl HibouOptions {
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 enum LoggerKinds {
-    graphic
+    graphic,
+    coverage,
+    traceability
 }
 
 pub fn parse_hibou_options(option_pair : Pair<Rule>,
@@ -88,6 +97,7 @@ pub fn parse_hibou_options(option_pair : Pair<Rule>,
     let mut frontier_priorities = ProcessPriorities::new(0,0,0);
     let mut pre_filters : Vec<HibouPreFilter> = Vec::new();
     let mut temporality : HibouProcessTemporality = HibouProcessTemporality::UnTimed;
+    let mut engine : HibouProcessEngine = HibouProcessEngine::Symbolic;
     let mut goal : Option<GlobalVerdict> = None;
     // ***
     let mut got_loggers   : bool = false;
@@ -95,29 +105,46 @@ pub fn parse_hibou_options(option_pair : Pair<Rule>,
     let mut got_frontier_priorities : bool = false;
     let mut got_pre_filters : bool = false;
     let mut got_temporality : bool = false;
+    let mut got_engine : bool = false;
     let mut got_goal : bool = false;
     // ***
     let mut declared_loggers : HashSet<LoggerKinds> = HashSet::new();
     // ***
+    let option_section_location = option_pair.clone();
     for option_decl_pair in option_pair.into_inner() {
+        let option_decl_location = option_decl_pair.clone();
         match option_decl_pair.as_rule() {
             Rule::OPTION_TEMPORALITY_TIMED => {
                 if got_temporality {
-                    return Err( HibouParsingError::HsfSetupError("several 'temporality=[X]' declared in the same '@X_option' section".to_string()));
+                    return Err( HibouParsingError::HsfSetupError("several 'temporality=[X]' declared in the same '@X_option' section".to_string()).located_at(&option_decl_location) );
                 }
                 got_temporality = true;
                 temporality = HibouProcessTemporality::Timed;
             },
             Rule::OPTION_TEMPORALITY_UNTIMED => {
                 if got_temporality {
-                    return Err( HibouParsingError::HsfSetupError("several 'temporality=[X]' declared in the same '@X_option' section".to_string()));
+                    return Err( HibouParsingError::HsfSetupError("several 'temporality=[X]' declared in the same '@X_option' section".to_string()).located_at(&option_decl_location) );
                 }
                 got_temporality = true;
                 temporality = HibouProcessTemporality::UnTimed;
             },
+            Rule::OPTION_ENGINE_SYMBOLIC => {
+                if got_engine {
+                    return Err( HibouParsingError::HsfSetupError("several 'engine=X' declared in the same '@X_option' section".to_string()).located_at(&option_decl_location) );
+                }
+                got_engine = true;
+                engine = HibouProcessEngine::Symbolic;
+            },
+            Rule::OPTION_ENGINE_CONCRETE => {
+                if got_engine {
+                    return Err( HibouParsingError::HsfSetupError("several 'engine=X' declared in the same '@X_option' section".to_string()).located_at(&option_decl_location) );
+                }
+                got_engine = true;
+                engine = HibouProcessEngine::Concrete;
+            },
             Rule::OPTION_LOGGER_DECL => {
                 if got_loggers {
-                    return Err( HibouParsingError::HsfSetupError("several 'loggers=[X]' declared in the same '@X_option' section".to_string()));
+                    return Err( HibouParsingError::HsfSetupError("several 'loggers=[X]' declared in the same '@X_option' section".to_string()).located_at(&option_decl_location) );
                 }
                 got_loggers = true;
                 // ***
@@ -125,7 +152,7 @@ pub fn parse_hibou_options(option_pair : Pair<Rule>,
                     match logger_kind_pair.as_rule() {
                         Rule::OPTION_GRAPHIC_LOGGER => {
                             if declared_loggers.contains(&LoggerKinds::graphic) {
-                                return Err( HibouParsingError::HsfSetupError("several 'graphic' loggers declared in the same '@X_option' section".to_string()));
+                                return Err( HibouParsingError::HsfSetupError("several 'graphic' loggers declared in the same '@X_option' section".to_string()).located_at(&option_decl_location) );
                             }
                             declared_loggers.insert( LoggerKinds::graphic );
                             let graphic_logger_pair = logger_kind_pair.into_inner().next();
@@ -148,6 +175,20 @@ pub fn parse_hibou_options(option_pair : Pair<Rule>,
                                 }
                             }
                         },
+                        Rule::OPTION_COVERAGE_LOGGER => {
+                            if declared_loggers.contains(&LoggerKinds::coverage) {
+                                return Err( HibouParsingError::HsfSetupError("several 'coverage' loggers declared in the same '@X_option' section".to_string()).located_at(&option_decl_location) );
+                            }
+                            declared_loggers.insert( LoggerKinds::coverage );
+                            loggers.push(Box::new(CoverageProcessLogger::new(file_name.to_string() ) ) );
+                        },
+                        Rule::OPTION_TRACEABILITY_LOGGER => {
+                            if declared_loggers.contains(&LoggerKinds::traceability) {
+                                return Err( HibouParsingError::HsfSetupError("several 'traceability' loggers declared in the same '@X_option' section".to_string()).located_at(&option_decl_location) );
+                            }
+                            declared_loggers.insert( LoggerKinds::traceability );
+                            loggers.push(Box::new(TraceabilityProcessLogger::new(file_name.to_string() ) ) );
+                        },
                         _ => {
                             panic!("what rule then ? : {:?}", logger_kind_pair.as_rule() );
                         }
@@ -156,7 +197,7 @@ pub fn parse_hibou_options(option_pair : Pair<Rule>,
             },
             Rule::OPTION_STRATEGY_DECL => {
                 if got_strategy {
-                    return Err( HibouParsingError::HsfSetupError("several 'strategy=X' declared in the same '@X_option' section".to_string()));
+                    return Err( HibouParsingError::HsfSetupError("several 'strategy=X' declared in the same '@X_option' section".to_string()).located_at(&option_decl_location) );
                 }
                 got_strategy = true;
                 // ***
@@ -168,6 +209,75 @@ pub fn parse_hibou_options(option_pair : Pair<Rule>,
                     Rule::OPTION_STRATEGY_DFS => {
                         strategy = HibouSearchStrategy::DFS;
                     },
+                    Rule::OPTION_STRATEGY_BESTFS => {
+                        match process_kind {
+                            ProcessKind::Analyze => {
+                                strategy = HibouSearchStrategy::BestFS;
+                            },
+                            _ => {
+                                return Err( HibouParsingError::HsfSetupError("'strategy=BestFS' is only available in the '@analyze_option' section".to_string()).located_at(&option_decl_location) );
+                            }
+                        }
+                    },
+                    Rule::OPTION_STRATEGY_IDFS => {
+                        let mut step : u32 = 1;
+                        match strategy_pair.into_inner().next() {
+                            None => {},
+                            Some( step_pair ) => {
+                                let step_str : String = step_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect();
+                                match step_str.parse::<u32>() {
+                                    Ok( got ) => {
+                                        step = got;
+                                    },
+                                    Err(_) => {
+                                        return Err( HibouParsingError::HsfSetupError(format!("IDFS step '{}' does not fit in 32 bits",step_str)).located_at(&option_decl_location) );
+                                    }
+                                }
+                            }
+                        }
+                        if step == 0 {
+                            return Err( HibouParsingError::HsfSetupError("the step of 'strategy=IDFS' must be strictly positive".to_string()).located_at(&option_decl_location) );
+                        }
+                        strategy = HibouSearchStrategy::IterativeDeepening(step);
+                    },
+                    Rule::OPTION_STRATEGY_RANDOM_WALK => {
+                        let mut seed : u64 = 0;
+                        let mut walks : u32 = 1;
+                        for walk_param_pair in strategy_pair.into_inner() {
+                            let param_rule = walk_param_pair.as_rule();
+                            let content = walk_param_pair.into_inner().next().unwrap();
+                            let content_str : String = content.as_str().chars().filter(|c| !c.is_whitespace()).collect();
+                            match param_rule {
+                                Rule::OPTION_STRATEGY_SEED => {
+                                    match content_str.parse::<u64>() {
+                                        Ok( got ) => {
+                                            seed = got;
+                                        },
+                                        Err(_) => {
+                                            return Err( HibouParsingError::HsfSetupError(format!("random walk seed '{}' does not fit in 64 bits",content_str)).located_at(&option_decl_location) );
+                                        }
+                                    }
+                                },
+                                Rule::OPTION_STRATEGY_WALKS => {
+                                    match content_str.parse::<u32>() {
+                                        Ok( got ) => {
+                                            walks = got;
+                                        },
+                                        Err(_) => {
+                                            return Err( HibouParsingError::HsfSetupError(format!("random walk number of walks '{}' does not fit in 32 bits",content_str)).located_at(&option_decl_location) );
+                                        }
+                                    }
+                                },
+                                _ => {
+                                    panic!("what rule then ? : {:?}", param_rule );
+                                }
+                            }
+                        }
+                        if walks == 0 {
+                            return Err( HibouParsingError::HsfSetupError("the number of walks of 'strategy=RandomWalk' must be strictly positive".to_string()).located_at(&option_decl_location) );
+                        }
+                        strategy = HibouSearchStrategy::RandomWalk(seed,walks);
+                    },
                     _ => {
                         panic!("what rule then ? : {:?}", strategy_pair.as_rule() );
                     }
@@ -175,7 +285,7 @@ pub fn parse_hibou_options(option_pair : Pair<Rule>,
             },
             Rule::OPTION_PRIORITIES_DECL => {
                 if got_frontier_priorities {
-                    return Err( HibouParsingError::HsfSetupError("several 'frontier_priorities=X' declared in the same '@X_option' section".to_string()));
+                    return Err( HibouParsingError::HsfSetupError("several 'frontier_priorities=X' declared in the same '@X_option' section".to_string()).located_at(&option_decl_location) );
                 }
                 got_frontier_priorities = true;
                 // ***
@@ -197,6 +307,24 @@ pub fn parse_hibou_options(option_pair : Pair<Rule>,
                         Rule::OPTION_PRIORITY_loop => {
                             frontier_priorities.in_loop = priority_level;
                         },
+                        Rule::OPTION_PRIORITY_env_emission => {
+                            frontier_priorities.env_emission = priority_level;
+                        },
+                        Rule::OPTION_PRIORITY_broadcast => {
+                            frontier_priorities.broadcast = priority_level;
+                        },
+                        Rule::OPTION_PRIORITY_lifeline => {
+                            let lf_name : String = priority_kind_pair.into_inner().next().unwrap().as_str().to_string();
+                            frontier_priorities.lifelines.insert(lf_name,priority_level);
+                        },
+                        Rule::OPTION_PRIORITY_message => {
+                            let ms_name : String = priority_kind_pair.into_inner().next().unwrap().as_str().to_string();
+                            frontier_priorities.messages.insert(ms_name,priority_level);
+                        },
+                        Rule::OPTION_PRIORITY_tag => {
+                            let tag : String = parse_tag_label( priority_kind_pair.into_inner().next().unwrap() );
+                            frontier_priorities.tags.insert(tag,priority_level);
+                        },
                         _ => {
                             panic!("what rule then ? : {:?}", priority_kind_pair.as_rule() );
                         }
@@ -205,7 +333,7 @@ pub fn parse_hibou_options(option_pair : Pair<Rule>,
             },
             Rule::OPTION_PREFILTERS_DECL => {
                 if got_pre_filters {
-                    return Err( HibouParsingError::HsfSetupError("several 'pre_filters=[X]' declared in the same '@X_option' section".to_string()));
+                    return Err( HibouParsingError::HsfSetupError("several 'pre_filters=[X]' declared in the same '@X_option' section".to_string()).located_at(&option_decl_location) );
                 }
                 got_pre_filters = true;
                 // ***
@@ -229,6 +357,30 @@ pub fn parse_hibou_options(option_pair : Pair<Rule>,
                             let my_val : u32 = content_str.parse::<u32>().unwrap();
                             pre_filters.push(HibouPreFilter::MaxNodeNumber(my_val));
                         },
+                        Rule::OPTION_PREFILTER_MAX_TIME  => {
+                            let content = pre_filter_pair.into_inner().next().unwrap();
+                            let content_str : String = content.as_str().chars().filter(|c| !c.is_whitespace()).collect();
+                            let my_val : u32 = content_str.parse::<u32>().unwrap();
+                            pre_filters.push(HibouPreFilter::MaxTime(my_val));
+                        },
+                        Rule::OPTION_PREFILTER_MAX_SOLVER_CALLS  => {
+                            let content = pre_filter_pair.into_inner().next().unwrap();
+                            let content_str : String = content.as_str().chars().filter(|c| !c.is_whitespace()).collect();
+                            let my_val : u32 = content_str.parse::<u32>().unwrap();
+                            pre_filters.push(HibouPreFilter::MaxSolverCalls(my_val));
+                        },
+                        Rule::OPTION_PREFILTER_MAX_SYMBOLS  => {
+                            let content = pre_filter_pair.into_inner().next().unwrap();
+                            let content_str : String = content.as_str().chars().filter(|c| !c.is_whitespace()).collect();
+                            let my_val : u32 = content_str.parse::<u32>().unwrap();
+                            pre_filters.push(HibouPreFilter::MaxSymbols(my_val));
+                        },
+                        Rule::OPTION_PREFILTER_MAX_PATH_CONDITION_SIZE  => {
+                            let content = pre_filter_pair.into_inner().next().unwrap();
+                            let content_str : String = content.as_str().chars().filter(|c| !c.is_whitespace()).collect();
+                            let my_val : u32 = content_str.parse::<u32>().unwrap();
+                            pre_filters.push(HibouPreFilter::MaxPathConditionSize(my_val));
+                        },
                         _ => {
                             panic!("what rule then ? : {:?}", pre_filter_pair.as_rule() );
                         }
@@ -237,7 +389,7 @@ pub fn parse_hibou_options(option_pair : Pair<Rule>,
             },
             Rule::OPTION_GOAL_DECL => {
                 if got_goal {
-                    return Err( HibouParsingError::HsfSetupError("several 'goal=X' declared in the same '@X_option' section".to_string()));
+                    return Err( HibouParsingError::HsfSetupError("several 'goal=X' declared in the same '@X_option' section".to_string()).located_at(&option_decl_location) );
                 }
                 got_goal = true;
                 // ***
@@ -260,6 +412,14 @@ pub fn parse_hibou_options(option_pair : Pair<Rule>,
         }
     }
 
+    match (&engine,&temporality) {
+        (HibouProcessEngine::Concrete,HibouProcessTemporality::Timed) => {
+            // delays are symbols that only DIVERSITY can handle
+            return Err( HibouParsingError::HsfSetupError("'engine=concrete' cannot be used with 'temporality=timed'".to_string()).located_at(&option_section_location) );
+        },
+        _ => {}
+    }
+    // ***
     match process_kind {
         ProcessKind::Analyze => {
             let ana_goal : GlobalVerdict;
@@ -272,10 +432,10 @@ pub fn parse_hibou_options(option_pair : Pair<Rule>,
                 }
             }
             // ***
-            return Ok( HibouOptions::new(loggers,strategy,pre_filters,temporality, Some(ana_goal),frontier_priorities) );
+            return Ok( HibouOptions::new(loggers,strategy,pre_filters,temporality,engine, Some(ana_goal),frontier_priorities) );
         },
         _ => {
-            return Ok( HibouOptions::new(loggers,strategy,pre_filters,temporality, None,frontier_priorities) );
+            return Ok( HibouOptions::new(loggers,strategy,pre_filters,temporality,engine, None,frontier_priorities) );
         }
     }
 }
\ No newline at end of file
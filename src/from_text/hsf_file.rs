@@ -12,7 +12,7 @@
 use std::fs;
 use std::collections::{HashSet,HashMap};
 use std::collections::btree_map::BTreeMap;
-use std::path::Path;
+use std::path::{Path,PathBuf};
 
 use pest::iterators::Pair;
 
@@ -41,6 +41,8 @@ use crate::from_text::hibou_options::{HibouOptions,parse_hibou_options};
 
 use crate::process::hibou_process::HibouPreFilter;
 use crate::from_text::setup::{InterpretationItemPlan,parse_setup};
+use crate::from_text::type_check::type_check_interpretation_plan;
+use crate::from_text::reference::{InteractionDefinitions,ReferenceExpansion,parse_definition};
 
 pub static HIBOU_MODEL_FILE_EXTENSION : &'static str = "hxsf";
 
@@ -59,7 +61,14 @@ pub fn parse_hsf_file(file_path : &str, process_kind : &ProcessKind) -> Result<(
     let file_name : &str = path_object.file_stem().unwrap().to_str().unwrap();
     match fs::read_to_string(file_path) {
         Ok( unparsed_hsf_str ) => {
-            return parse_hsf_string(unparsed_hsf_str, file_name, process_kind);
+            match parse_hsf_string(unparsed_hsf_str, file_name, path_object, process_kind) {
+                Err(e) => {
+                    return Err( e.in_file(file_path) );
+                },
+                Ok( parsed ) => {
+                    return Ok( parsed );
+                }
+            }
         },
         Err(e) => {
             return Err( HibouParsingError::FileError(e.to_string()) );
@@ -67,20 +76,39 @@ pub fn parse_hsf_file(file_path : &str, process_kind : &ProcessKind) -> Result<(
     }
 }
 
+/*
+    The path of the model is that relatively to which its '@import' sections are resolved
+*/
 pub fn parse_hsf_string(sd_string : String,
                         name : &str,
+                        file_path : &Path,
                         process_kind : &ProcessKind) -> Result<(GeneralContext,ExecutionContext,Interaction,HibouOptions),HibouParsingError> {
+    // imported files are read beforehand so that the definitions they contain may refer to their contents
+    let mut imported_files : Vec<ImportedFile> = Vec::new();
     match SDParser::parse(Rule::HSF_PEST_FILE, &sd_string) {
         Ok( ref mut sd_cfg_pair ) => {
             let mut content = sd_cfg_pair.next().unwrap().into_inner();
             let first_pair = content.next().unwrap();
             match first_pair.as_rule() {
                 Rule::HIBOU_MODEL_SETUP => {
+                    let mut importing : Vec<PathBuf> = Vec::new();
+                    match fs::canonicalize(file_path) {
+                        Err(_) => {},
+                        Ok( canonical_path ) => {
+                            importing.push( canonical_path );
+                        }
+                    }
+                    match load_imports(first_pair.clone(), file_path, &mut importing, &mut imported_files) {
+                        Err(e) => {
+                            return Err(e);
+                        },
+                        Ok( _ ) => {}
+                    }
                     let second_pair = content.next().unwrap();
-                    return parse_sd(second_pair,Some(first_pair),name,process_kind);
+                    return parse_sd(second_pair,Some(first_pair),&imported_files,name,process_kind);
                 },
                 Rule::SD_INTERACTION => {
-                    return parse_sd(first_pair, None,name,process_kind);
+                    return parse_sd(first_pair, None,&imported_files,name,process_kind);
                 },
                 _ => {
                     unreachable!();
@@ -88,23 +116,142 @@ pub fn parse_hsf_string(sd_string : String,
             }
         },
         Err(e) => {
-            return Err( HibouParsingError::MatchError(e.to_string()) );
+            return Err( HibouParsingError::MatchError(e) );
         }
     }
 }
 
+/*
+    A file imported, directly or not, by the model
+*/
+struct ImportedFile {
+    path : String,
+    canonical_path : PathBuf,
+    content : String
+}
 
-fn parse_sd(interaction_pair : Pair<Rule>,
-            setup_pair_opt : Option< Pair<Rule> >,
+/*
+    Reads the files imported in a setup section, each of them being placed after the ones it imports itself
+    A file imported several times is read only once, and a file cannot import itself, even indirectly
+*/
+fn load_imports(setup_pair : Pair<Rule>,
+                importing_path : &Path,
+                importing : &mut Vec<PathBuf>,
+                imported_files : &mut Vec<ImportedFile>) -> Result<(),HibouParsingError> {
+    let mut errors : Vec<HibouParsingError> = Vec::new();
+    for section_pair in setup_pair.into_inner() {
+        if section_pair.as_rule() != Rule::HIBOU_MODEL_IMPORT {
+            continue;
+        }
+        let relative_path : &str = section_pair.clone().into_inner().next().unwrap().as_str();
+        let import_path : PathBuf;
+        match importing_path.parent() {
+            None => {
+                import_path = PathBuf::from(relative_path);
+            },
+            Some( directory ) => {
+                import_path = directory.join(relative_path);
+            }
+        }
+        let import_path_str : String = import_path.to_string_lossy().to_string();
+        // ***
+        let canonical_path : PathBuf;
+        match fs::canonicalize(&import_path) {
+            Err(e) => {
+                errors.push( HibouParsingError::MalformedImport( format!("cannot read '{}' : {}", import_path_str, e) ).located_at(&section_pair) );
+                continue;
+            },
+            Ok( got_path ) => {
+                canonical_path = got_path;
+            }
+        }
+        if importing.contains(&canonical_path) {
+            errors.push( HibouParsingError::MalformedImport( format!("cyclic import of '{}'", import_path_str) ).located_at(&section_pair) );
+            continue;
+        }
+        if imported_files.iter().any(|imp| imp.canonical_path == canonical_path) {
+            continue;
+        }
+        // ***
+        let content : String;
+        match fs::read_to_string(&canonical_path) {
+            Err(e) => {
+                errors.push( HibouParsingError::MalformedImport( format!("cannot read '{}' : {}", import_path_str, e) ).located_at(&section_pair) );
+                continue;
+            },
+            Ok( got_content ) => {
+                content = got_content;
+            }
+        }
+        importing.push( canonical_path.clone() );
+        let loaded = load_imported_file(&content, &import_path, importing, imported_files);
+        importing.pop();
+        match loaded {
+            Err(e) => {
+                let imported_error = HibouParsingError::Imported( import_path_str.clone(), Box::new(e.in_file(&import_path_str)) );
+                errors.push( HibouParsingError::MalformedImport( format!("error while importing '{}'", import_path_str) ).located_at(&section_pair) );
+                errors.push( imported_error );
+            },
+            Ok( _ ) => {
+                imported_files.push( ImportedFile{path:import_path_str,canonical_path,content} );
+            }
+        }
+    }
+    if errors.len() > 0 {
+        return Err( HibouParsingError::from_several(errors) );
+    }
+    return Ok(());
+}
+
+/*
+    Imported files only contain setup sections, except for options which are those of the importing model
+*/
+fn load_imported_file(content : &str,
+                      import_path : &Path,
+                      importing : &mut Vec<PathBuf>,
+                      imported_files : &mut Vec<ImportedFile>) -> Result<(),HibouParsingError> {
+    match SDParser::parse(Rule::HSF_IMPORTED_PEST_FILE, content) {
+        Err(e) => {
+            return Err( HibouParsingError::MatchError(e) );
+        },
+        Ok( ref mut imported_pair ) => {
+            let setup_pair = imported_pair.next().unwrap().into_inner().next().unwrap();
+            for section_pair in setup_pair.clone().into_inner() {
+                match section_pair.as_rule() {
+                    Rule::EXPLORE_OPTION_SECTION | Rule::ANALYZE_OPTION_SECTION => {
+                        return Err( HibouParsingError::HsfSetupError("option sections cannot be declared in an imported file".to_string()).located_at(&section_pair) );
+                    },
+                    _ => {}
+                }
+            }
+            return load_imports(setup_pair, import_path, importing, imported_files);
+        }
+    }
+}
+
+fn parse_sd<'i>(interaction_pair : Pair<'i,Rule>,
+            setup_pair_opt : Option< Pair<'i,Rule> >,
+            imported_files : &'i Vec<ImportedFile>,
             name : &str,
             process_kind : &ProcessKind)
         -> Result<(GeneralContext,ExecutionContext,Interaction,HibouOptions),HibouParsingError> {
     let mut gen_ctx = GeneralContext::new();
-    let interpretation_plan : Vec<InterpretationItemPlan>;
+    let mut definitions : InteractionDefinitions<'i> = InteractionDefinitions::new();
+    let mut interpretation_plan : Vec<InterpretationItemPlan> = Vec::new();
     let hibou_options : HibouOptions;
+    // declarations of the imported files come first, in the order in which they were loaded
+    for imported_file in imported_files {
+        match parse_imported_file(imported_file, &mut gen_ctx, &mut definitions, name, process_kind) {
+            Err(e) => {
+                return Err( HibouParsingError::Imported( imported_file.path.clone(), Box::new(e.in_file(&imported_file.path)) ) );
+            },
+            Ok( mut intplan ) => {
+                interpretation_plan.append( &mut intplan );
+            }
+        }
+    }
     match setup_pair_opt {
         None => {
-            interpretation_plan = Vec::new();
             match process_kind {
                 ProcessKind::Analyze => {
                     hibou_options = HibouOptions::default_analyze();
@@ -115,22 +262,56 @@ fn parse_sd(interaction_pair : Pair<Rule>,
             }
         },
         Some( setup_pair ) => {
+            for section_pair in setup_pair.clone().into_inner() {
+                if section_pair.as_rule() == Rule::HIBOU_MODEL_DEFINE {
+                    match parse_definition(section_pair, None, &mut definitions) {
+                        Err(e) => {
+                            return Err(e);
+                        },
+                        Ok( _ ) => {}
+                    }
+                }
+            }
             match parse_setup(setup_pair, &mut gen_ctx, name, process_kind) {
                 Err(e) => {
                     return Err(e);
                 },
-                Ok( (intplan, hoptions) ) => {
-                    interpretation_plan = intplan;
+                Ok( (mut intplan, hoptions) ) => {
+                    interpretation_plan.append( &mut intplan );
                     hibou_options = hoptions;
                 }
             }
         }
     }
-    match parse_interaction(&gen_ctx, interaction_pair) {
+    // lifelines and messages may be declared after the options so their names are checked once the whole setup is parsed
+    for lf_name in hibou_options.frontier_priorities.lifelines.keys() {
+        if gen_ctx.get_lf_id(lf_name).is_none() {
+            return Err( HibouParsingError::MissingLifelineDeclarationError(lf_name.clone()) );
+        }
+    }
+    for ms_name in hibou_options.frontier_priorities.messages.keys() {
+        if gen_ctx.get_ms_id(ms_name).is_none() {
+            return Err( HibouParsingError::MissingMessageDeclarationError(ms_name.clone()) );
+        }
+    }
+    // the actions are type checked as they are parsed
+    let mut typing_errors = type_check_interpretation_plan(&gen_ctx, &interpretation_plan);
+    match parse_interaction(&mut gen_ctx, &definitions, &ReferenceExpansion::new(), interaction_pair) {
         Err(e) => {
-            return Err(e);
+            typing_errors.push(e);
+            return Err( HibouParsingError::from_several(typing_errors) );
         },
         Ok( interaction ) => {
+            // tags need not be declared, those given priorities must however annotate some action
+            for tag in hibou_options.frontier_priorities.tags.keys() {
+                if gen_ctx.get_tag_id(tag).is_none() {
+                    return Err( HibouParsingError::HsfSetupError( format!("frontier priority given to tag '{}' which annotates no action", tag) ) );
+                }
+            }
+            if typing_errors.len() > 0 {
+                return Err( HibouParsingError::from_several(typing_errors) );
+            }
+            // ***
             let mut interpretation : BTreeMap<usize, BTreeMap<usize,TD_Generic> > = BTreeMap::new();
             let mut symb_count : usize = 1;
             let mut symb_types : BTreeMap<usize,TD_DataType> = BTreeMap::new();
@@ -182,4 +363,44 @@ fn parse_sd(interaction_pair : Pair<Rule>,
             return Ok( (gen_ctx,exe_ctx,interaction.decorate_with_initial_positions(Vec::new() ),hibou_options) );
         }
     }
-}
\ No newline at end of file
+}
+
+/*
+    Declarations and definitions of an imported file are added to those of the model
+    Its '@init' items are located in the imported file
+*/
+fn parse_imported_file<'i>(imported_file : &'i ImportedFile,
+                           gen_ctx : &mut GeneralContext,
+                           definitions : &mut InteractionDefinitions<'i>,
+                           name : &str,
+                           process_kind : &ProcessKind) -> Result<Vec<InterpretationItemPlan>,HibouParsingError> {
+    match SDParser::parse(Rule::HSF_IMPORTED_PEST_FILE, &imported_file.content) {
+        Err(e) => {
+            return Err( HibouParsingError::MatchError(e) );
+        },
+        Ok( ref mut imported_pair ) => {
+            let setup_pair = imported_pair.next().unwrap().into_inner().next().unwrap();
+            for section_pair in setup_pair.clone().into_inner() {
+                if section_pair.as_rule() == Rule::HIBOU_MODEL_DEFINE {
+                    match parse_definition(section_pair, Some(imported_file.path.clone()), definitions) {
+                        Err(e) => {
+                            return Err(e);
+                        },
+                        Ok( _ ) => {}
+                    }
+                }
+            }
+            match parse_setup(setup_pair, gen_ctx, name, process_kind) {
+                Err(e) => {
+                    return Err(e);
+                },
+                Ok( (mut intplan, _) ) => {
+                    for iip in intplan.iter_mut() {
+                        iip.location.file = Some( imported_file.path.clone() );
+                    }
+                    return Ok( intplan );
+                }
+            }
+        }
+    }
+}
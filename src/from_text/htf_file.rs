@@ -10,7 +10,7 @@
  ********************************************************************************/
 
 use std::path::Path;
-use std::collections::{HashMap,HashSet};
+use std::collections::{HashMap,HashSet,BTreeMap};
 use std::fs;
 use pest::Parser;
 use pest::iterators::{Pair,Pairs};
@@ -18,9 +18,12 @@ use std::iter::FromIterator;
 
 use crate::core::trace::*;
 use crate::core::context::general::GeneralContext;
+use crate::core::syntax::action::CommunicationKind;
 
 use crate::core::syntax::data::generic::TD_Generic;
-use crate::from_text::error::HibouParsingError;
+use crate::core::syntax::data::td_type::TD_DataType;
+use crate::core::syntax::data::evaluation::{Valuation,evaluate_generic};
+use crate::from_text::error::{HibouParsingError,SourceLocation};
 
 use crate::from_text::parser::*;
 
@@ -43,7 +46,14 @@ pub fn parse_htf_file(file_path : &str,
     let file_name : &str = path_object.file_stem().unwrap().to_str().unwrap();
     match fs::read_to_string(file_path) {
         Ok( unparsed_htf_str ) => {
-            return multitrace_from_text(&unparsed_htf_str, gen_ctx, temporality);
+            match multitrace_from_text(&unparsed_htf_str, gen_ctx, temporality) {
+                Err(e) => {
+                    return Err( e.in_file(file_path) );
+                },
+                Ok( multi_trace ) => {
+                    return Ok( multi_trace );
+                }
+            }
         },
         Err(e) => {
             return Err( HibouParsingError::FileError(e.to_string()) );
@@ -72,7 +82,7 @@ pub fn multitrace_from_text(multitrace_str : &String,
                             temporality : &HibouProcessTemporality) -> Result<AnalysableMultiTrace,HibouParsingError> {
     match SDParser::parse(Rule::HTF_PEST_FILE, multitrace_str) {
         Err(e) => {
-            panic!(e);
+            return Err( HibouParsingError::MatchError(e) );
         },
         Ok( ref mut htf_pair ) => {
             let mut content = htf_pair.next().unwrap().into_inner();
@@ -94,10 +104,11 @@ pub fn multitrace_from_text(multitrace_str : &String,
                 Rule::MULTI_TRACE => {
                     let mut unavailable_lifelines : HashSet<usize> = HashSet::new();
                     let mut canals : Vec<MultiTraceCanal> = Vec::new();
+                    let mut errors : Vec<HibouParsingError> = Vec::new();
                     for trace_pair in first_pair.into_inner() {
                         match trace_canal_from_pair(trace_pair,gen_ctx,&unavailable_lifelines,temporality) {
                             Err(e) => {
-                                return Err(e);
+                                errors.push(e);
                             },
                             Ok( trace_canal ) => {
                                 unavailable_lifelines = unavailable_lifelines.union( &trace_canal.lifelines ).cloned().collect();
@@ -105,6 +116,9 @@ pub fn multitrace_from_text(multitrace_str : &String,
                             }
                         }
                     }
+                    if errors.len() > 0 {
+                        return Err( HibouParsingError::from_several(errors) );
+                    }
                     complete_canals_up_to_defined_lifelines(&mut canals,gen_ctx);
                     return Ok( AnalysableMultiTrace::new(canals) );
                 },
@@ -156,7 +170,15 @@ pub fn trace_canal_from_pair(trace_pair : Pair<Rule>,
                         let lf_name : String  = trace_lf_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect();
                         match gen_ctx.get_lf_id(&lf_name) {
                             None => {
-                                return Err( HibouParsingError::MissingLifelineDeclarationError(lf_name));
+                                // a lifeline group stands for all of its members
+                                match gen_ctx.get_lgr_id(&lf_name) {
+                                    None => {
+                                        return Err( HibouParsingError::MissingLifelineDeclarationError(lf_name).located_at(&trace_lf_pair) );
+                                    },
+                                    Some( lgr_id ) => {
+                                        lifelines.extend( gen_ctx.get_lgr_lifelines(lgr_id) );
+                                    }
+                                }
                             },
                             Some( lf_id ) => {
                                 lifelines.insert(lf_id);
@@ -189,26 +211,89 @@ pub fn inner_trace_from_pairs(content : &mut Pairs<Rule>,
                               lifelines : &mut HashSet<usize>,
                               add_lfs : bool,
                               temporality : &HibouProcessTemporality) -> Result<(),HibouParsingError> {
+    // errors in an action do not prevent parsing the other ones
+    let mut errors : Vec<HibouParsingError> = Vec::new();
+    // calls which are still waiting for their reply, per lifeline
+    let mut pending_calls : HashMap<usize,PendingCalls> = HashMap::new();
     for action_pair in content {
+        let action_location = action_pair.clone();
         match trace_action_from_text(action_pair,gen_ctx,temporality) {
             Err(e) => {
-                return Err(e);
+                errors.push( e.located_at(&action_location) );
             },
             Ok( action ) => {
                 if unavailable_lifelines.contains(&action.lf_id) {
-                    return Err( HibouParsingError::NonDisjointTraceComponents );
+                    errors.push( HibouParsingError::NonDisjointTraceComponents.located_at(&action_location) );
                 } else {
                     if add_lfs {
                         lifelines.insert( action.lf_id);
                     }
                 }
+                let lf_pending_calls = pending_calls.entry(action.lf_id).or_insert( PendingCalls{outgoing:Vec::new(),incoming:Vec::new()} );
+                match lf_pending_calls.pair_with_call(gen_ctx, &action) {
+                    Err(e) => {
+                        errors.push( e.located_at(&action_location) );
+                    },
+                    Ok(_) => {}
+                }
                 trace.push( action );
             }
         }
     }
+    if errors.len() > 0 {
+        return Err( HibouParsingError::from_several(errors) );
+    }
     return Ok( () );
 }
 
+struct PendingCalls {
+    outgoing : Vec<usize>,
+    incoming : Vec<usize>
+}
+
+impl PendingCalls {
+
+    /*
+        Calls are nested on each lifeline so that a reply always answers the last call which has not been answered yet :
+        - the caller emits the call and then receives the reply
+        - the callee receives the call and then emits the reply
+    */
+    fn pair_with_call(&mut self, gen_ctx : &GeneralContext, action : &TraceAction) -> Result<(),HibouParsingError> {
+        let answered : Option<usize>;
+        match (&action.act_kind,&action.com_kind) {
+            (_,CommunicationKind::Asynchronous) => {
+                return Ok(());
+            },
+            (TraceActionKind::Emission,CommunicationKind::Call) => {
+                self.outgoing.push( action.ms_id );
+                return Ok(());
+            },
+            (TraceActionKind::Reception,CommunicationKind::Call) => {
+                self.incoming.push( action.ms_id );
+                return Ok(());
+            },
+            (TraceActionKind::Reception,CommunicationKind::Reply) => {
+                answered = self.outgoing.pop();
+            },
+            (TraceActionKind::Emission,CommunicationKind::Reply) => {
+                answered = self.incoming.pop();
+            }
+        }
+        let ms_name = gen_ctx.get_ms_name(action.ms_id).unwrap();
+        let lf_name = gen_ctx.get_lf_name(action.lf_id).unwrap();
+        match answered {
+            None => {
+                return Err( HibouParsingError::UnpairedReply( format!("reply of message '{}' on lifeline '{}' does not answer any pending call", ms_name, lf_name) ) );
+            },
+            Some( call_ms_id ) => {
+                if call_ms_id != action.ms_id {
+                    return Err( HibouParsingError::UnpairedReply( format!("reply of message '{}' on lifeline '{}' while the pending call is of message '{}'", ms_name, lf_name, gen_ctx.get_ms_name(call_ms_id).unwrap()) ) );
+                }
+                return Ok(());
+            }
+        }
+    }
+}
 
 fn trace_action_from_text(action_pair : Pair<Rule>,
                           gen_ctx : &GeneralContext,
@@ -240,12 +325,12 @@ fn trace_action_from_text(action_pair : Pair<Rule>,
         Err(e) => {
             return Err(e);
         },
-        Ok( (lf_id,act_kind,ms_id,arguments) ) => {
+        Ok( (lf_id,act_kind,ms_id,com_kind,arguments) ) => {
             match raw_delay_opt {
                 None => {
                     match temporality {
                         HibouProcessTemporality::UnTimed => {
-                            return Ok( TraceAction{delay:None,lf_id,act_kind,ms_id,arguments} );
+                            return Ok( TraceAction{delay:None,lf_id,act_kind,ms_id,com_kind,arguments} );
                         },
                         HibouProcessTemporality::Timed => {
                             return Err( HibouParsingError::TimedTraceAbsentDelay(original_pair_as_string) );
@@ -256,10 +341,10 @@ fn trace_action_from_text(action_pair : Pair<Rule>,
                     match temporality {
                         HibouProcessTemporality::UnTimed => {
                             println!("WARNNG : unused timed trace delay information due to analysis in untimed mode");
-                            return Ok( TraceAction{delay:None,lf_id,act_kind,ms_id,arguments} );
+                            return Ok( TraceAction{delay:None,lf_id,act_kind,ms_id,com_kind,arguments} );
                         },
                         HibouProcessTemporality::Timed => {
-                            return Ok( TraceAction{delay:Some(TD_Float::Value(raw_delay)),lf_id,act_kind,ms_id,arguments} );
+                            return Ok( TraceAction{delay:Some(TD_Float::Value(raw_delay)),lf_id,act_kind,ms_id,com_kind,arguments} );
                         }
                     }
                 }
@@ -270,12 +355,12 @@ fn trace_action_from_text(action_pair : Pair<Rule>,
 
 fn untimed_trace_action_from_text(  lf_pair : Pair<Rule>,
                                     action_contents : &mut Pairs<Rule>,
-                                    gen_ctx : &GeneralContext) -> Result<(usize,TraceActionKind,usize,Vec<TD_Generic>),HibouParsingError> {
+                                    gen_ctx : &GeneralContext) -> Result<(usize,TraceActionKind,usize,CommunicationKind,Vec<TD_Generic>),HibouParsingError> {
     // ***
     let lf_name : String  = lf_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect();
     match gen_ctx.get_lf_id(&lf_name) {
         None => {
-            return Err( HibouParsingError::MissingLifelineDeclarationError(lf_name) );
+            return Err( HibouParsingError::MissingLifelineDeclarationError(lf_name).located_at(&lf_pair) );
         },
         Some( lf_id ) => {
             // ***
@@ -293,14 +378,45 @@ fn untimed_trace_action_from_text(  lf_pair : Pair<Rule>,
                 }
             }
             // ***
-            let ms_pair : Pair<Rule> = action_contents.next().unwrap();
+            let mut ms_pair : Pair<Rule> = action_contents.next().unwrap();
+            let com_kind : CommunicationKind;
+            match ms_pair.as_rule() {
+                Rule::TRACE_CALL_SYMBOL => {
+                    com_kind = CommunicationKind::Call;
+                    ms_pair = action_contents.next().unwrap();
+                },
+                Rule::TRACE_REPLY_SYMBOL => {
+                    com_kind = CommunicationKind::Reply;
+                    ms_pair = action_contents.next().unwrap();
+                },
+                _ => {
+                    com_kind = CommunicationKind::Asynchronous;
+                }
+            }
+            // ***
             let ms_name : String  = ms_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect();
             match gen_ctx.get_ms_id(&ms_name) {
                 None => {
-                    return Err( HibouParsingError::MissingMessageDeclarationError(ms_name) );
+                    return Err( HibouParsingError::MissingMessageDeclarationError(ms_name).located_at(&ms_pair) );
                 },
                 Some( ms_id ) => {
-                    let ms_specs = gen_ctx.get_ms_spec(ms_id).unwrap();
+                    let ms_specs : Vec<(TD_DataType,Option<String>)>;
+                    match com_kind {
+                        CommunicationKind::Reply => {
+                            // a reply only carries the returned value
+                            match gen_ctx.get_ms_return_type(ms_id) {
+                                None => {
+                                    ms_specs = Vec::new();
+                                },
+                                Some( return_type ) => {
+                                    ms_specs = vec![(return_type,None)];
+                                }
+                            }
+                        },
+                        _ => {
+                            ms_specs = gen_ctx.get_ms_spec(ms_id).unwrap();
+                        }
+                    }
                     // ***
                     let mut arg_count : usize = 0;
                     let mut arguments : Vec<TD_Generic> = Vec::new();
@@ -312,19 +428,35 @@ fn untimed_trace_action_from_text(  lf_pair : Pair<Rule>,
                                 // ***
                                 match SDParser::parse(Rule::TD_VALUE, &argument_string) {
                                     Err(e) => {
-                                        panic!(e);
+                                        return Err( HibouParsingError::Located( SourceLocation::from_pair(&arg_pair), Box::new(HibouParsingError::MatchError(e)) ) );
                                     },
                                     Ok( mut argument_as_td_val ) => {
                                         match ms_specs.get(arg_count) {
                                             None => {
-                                                return Err( HibouParsingError::UnknownMessageParameter(ms_name,arg_count) );
+                                                return Err( HibouParsingError::UnknownMessageParameter(ms_name,arg_count).located_at(&arg_pair) );
                                             },
                                             Some( (expected_type ,_) ) => {
                                                 let mut content = argument_as_td_val.next().unwrap();
-                                                let trace_action_param_td_gen : TD_Generic = parse_data(gen_ctx,content,&Some(ms_id)).unwrap();
+                                                // the argument is re-parsed from a standalone string so its errors are located at the original argument
+                                                let mut trace_action_param_td_gen : TD_Generic;
+                                                match parse_data(gen_ctx,content,&Some(ms_id)) {
+                                                    Err(e) => {
+                                                        return Err( strip_location(e).located_at(&arg_pair) );
+                                                    },
+                                                    Ok( td_gen ) => {
+                                                        trace_action_param_td_gen = td_gen;
+                                                    }
+                                                }
+                                                // negative numbers are parsed as arithmetic expressions which are folded back into literals
+                                                match evaluate_generic(&trace_action_param_td_gen,&Valuation::new(&BTreeMap::new(),&BTreeMap::new())) {
+                                                    Ok( literal ) => {
+                                                        trace_action_param_td_gen = literal;
+                                                    },
+                                                    Err(_) => {}
+                                                }
                                                 let got_type = trace_action_param_td_gen.get_td_type();
                                                 if &got_type != expected_type {
-                                                    return Err( HibouParsingError::WrongMessageParameterType(expected_type.clone(), got_type, ms_name, format!("{:?}", ms_specs) ) );
+                                                    return Err( HibouParsingError::WrongMessageParameterType(expected_type.clone(), got_type, ms_name, format!("{:?}", ms_specs) ).located_at(&arg_pair) );
                                                 }
                                                 arguments.push(trace_action_param_td_gen);
                                                 arg_count = arg_count +1;
@@ -337,20 +469,23 @@ fn untimed_trace_action_from_text(  lf_pair : Pair<Rule>,
                     }
                     // ***
                     if arg_count != ms_specs.len() {
-                        return Err( HibouParsingError::WrongMessageParametersNumber(ms_specs.len(), arg_count,ms_name) );
+                        return Err( HibouParsingError::WrongMessageParametersNumber(ms_specs.len(), arg_count,ms_name).located_at(&ms_pair) );
                     }
                     // ***
-                    return Ok( (lf_id,act_kind,ms_id,arguments) );
+                    return Ok( (lf_id,act_kind,ms_id,com_kind,arguments) );
                 }
             }
         }
     }
 }
 
-
-
-
-
-
-
-
+fn strip_location(error : HibouParsingError) -> HibouParsingError {
+    match error {
+        HibouParsingError::Located(_, sub_error) => {
+            return *sub_error;
+        },
+        _ => {
+            return error;
+        }
+    }
+}
@@ -17,40 +17,99 @@ use crate::from_text::parser::*;
 
 use crate::core::syntax::action::*;
 use crate::core::context::general::GeneralContext;
-use crate::core::syntax::interaction::{Interaction,ScheduleOperatorKind};
+use crate::core::syntax::interaction::{Interaction,ScheduleOperatorKind,LoopBounds};
 
 use crate::from_text::error::HibouParsingError;
-use crate::from_text::action::action::{parse_emission,parse_reception};
+use crate::from_text::action::action::{parse_emission,parse_reception,parse_call};
+use crate::from_text::reference::{InteractionDefinitions,ReferenceExpansion,get_reference_label};
+use crate::from_text::type_check::type_check_parsed_actions;
 
 
-pub fn parse_interaction(gen_ctx : &GeneralContext, sd_interaction_pair : Pair<Rule>) -> Result<Interaction,HibouParsingError> {
-    let sd_content_pair = sd_interaction_pair.into_inner().next().unwrap();
+pub fn parse_interaction(gen_ctx : &mut GeneralContext,
+                         definitions : &InteractionDefinitions,
+                         expansion : &ReferenceExpansion,
+                         sd_interaction_pair : Pair<Rule>) -> Result<Interaction,HibouParsingError> {
+    let mut tags : Vec<usize> = Vec::new();
+    let mut sd_content_pair_opt : Option<Pair<Rule>> = None;
+    for sub_pair in sd_interaction_pair.into_inner() {
+        match sub_pair.as_rule() {
+            Rule::SD_TAG => {
+                for tag_label_pair in sub_pair.into_inner() {
+                    tags.push( gen_ctx.add_tag( parse_tag_label(tag_label_pair) ) );
+                }
+            },
+            _ => {
+                sd_content_pair_opt = Some( sub_pair );
+            }
+        }
+    }
+    match parse_interaction_content(gen_ctx, definitions, expansion, sd_content_pair_opt.unwrap()) {
+        Err(e) => {
+            return Err(e);
+        },
+        Ok( interaction ) => {
+            if tags.len() > 0 {
+                return Ok( interaction.add_tags(&tags) );
+            } else {
+                return Ok( interaction );
+            }
+        }
+    }
+}
+
+/*
+    A tag is either given as a string or as a label
+*/
+pub fn parse_tag_label(tag_label_pair : Pair<Rule>) -> String {
+    return tag_label_pair.into_inner().next().unwrap().as_str().to_string();
+}
+
+fn parse_interaction_content(gen_ctx : &mut GeneralContext,
+                             definitions : &InteractionDefinitions,
+                             expansion : &ReferenceExpansion,
+                             sd_content_pair : Pair<Rule>) -> Result<Interaction,HibouParsingError> {
     match sd_content_pair.as_rule() {
         Rule::SD_EMPTY_INTERACTION => {
             return Ok( Interaction::Empty );
         },
         Rule::SD_ACTION_RECEPTION => {
-            match parse_reception(gen_ctx,&mut sd_content_pair.into_inner()) {
+            let action_location = sd_content_pair.clone();
+            match parse_reception(gen_ctx,expansion,&mut sd_content_pair.into_inner()) {
                 Err(e) => {
-                    return Err(e);
+                    return Err( e.located_at(&action_location) );
                 },
                 Ok( observable_action ) => {
-                    return Ok( Interaction::Action(observable_action) );
+                    match type_check_parsed_actions(gen_ctx,vec![&observable_action],&action_location) {
+                        Some( typing_error ) => {
+                            return Err( typing_error );
+                        },
+                        None => {
+                            return Ok( Interaction::Action(observable_action) );
+                        }
+                    }
                 }
             }
         },
         Rule::SD_ACTION_EMISSION => {
-            match parse_emission(gen_ctx,&mut sd_content_pair.into_inner()) {
+            let action_location = sd_content_pair.clone();
+            match parse_emission(gen_ctx,expansion,&mut sd_content_pair.into_inner()) {
                 Err(e) => {
-                    return Err(e);
+                    return Err( e.located_at(&action_location) );
                 },
                 Ok( observable_action ) => {
-                    return Ok( Interaction::Action(observable_action) );
+                    match type_check_parsed_actions(gen_ctx,vec![&observable_action],&action_location) {
+                        Some( typing_error ) => {
+                            return Err( typing_error );
+                        },
+                        None => {
+                            return Ok( Interaction::Action(observable_action) );
+                        }
+                    }
                 }
             }
         },
         Rule::SD_STRICT_INT => {
-            match get_nary_sub_interactions(gen_ctx, sd_content_pair) {
+            match get_nary_sub_interactions(gen_ctx, definitions, expansion, sd_content_pair) {
                 Err(e) => {
                     return Err(e);
                 },
@@ -60,7 +119,7 @@ pub fn parse_interaction(gen_ctx : &GeneralContext, sd_interaction_pair : Pair<R
             }
         },
         Rule::SD_SEQ_INT => {
-            match get_nary_sub_interactions(gen_ctx, sd_content_pair) {
+            match get_nary_sub_interactions(gen_ctx, definitions, expansion, sd_content_pair) {
                 Err(e) => {
                     return Err(e);
                 },
@@ -70,7 +129,7 @@ pub fn parse_interaction(gen_ctx : &GeneralContext, sd_interaction_pair : Pair<R
             }
         },
         Rule::SD_ALT_INT => {
-            match get_nary_sub_interactions(gen_ctx, sd_content_pair) {
+            match get_nary_sub_interactions(gen_ctx, definitions, expansion, sd_content_pair) {
                 Err(e) => {
                     return Err(e);
                 },
@@ -80,7 +139,7 @@ pub fn parse_interaction(gen_ctx : &GeneralContext, sd_interaction_pair : Pair<R
             }
         },
         Rule::SD_PAR_INT => {
-            match get_nary_sub_interactions(gen_ctx, sd_content_pair) {
+            match get_nary_sub_interactions(gen_ctx, definitions, expansion, sd_content_pair) {
                 Err(e) => {
                     return Err(e);
                 },
@@ -92,20 +151,33 @@ pub fn parse_interaction(gen_ctx : &GeneralContext, sd_interaction_pair : Pair<R
         Rule::SD_LOOP_INT => {
             let mut loop_content = sd_content_pair.into_inner();
             let loop_kind_pair = loop_content.next().unwrap();
-            match parse_interaction(gen_ctx,loop_content.next().unwrap()) {
+            let mut sub_int_pair = loop_content.next().unwrap();
+            let mut bounds = LoopBounds::unbounded();
+            if sub_int_pair.as_rule() == Rule::SD_LOOP_BOUNDS {
+                match parse_loop_bounds(&sub_int_pair) {
+                    Err(e) => {
+                        return Err( e.located_at(&sub_int_pair) );
+                    },
+                    Ok( got_bounds ) => {
+                        bounds = got_bounds;
+                    }
+                }
+                sub_int_pair = loop_content.next().unwrap();
+            }
+            match parse_interaction(gen_ctx,definitions,expansion,sub_int_pair) {
                 Err(e) => {
                     return Err(e);
                 },
                 Ok( sub_int ) => {
                     match loop_kind_pair.as_rule() {
                         Rule::SD_LOOPX => {
-                            return Ok( Interaction::Loop(ScheduleOperatorKind::Strict,Box::new(sub_int)) );
+                            return Ok( Interaction::Loop(ScheduleOperatorKind::Strict,bounds,Box::new(sub_int)) );
                         },
                         Rule::SD_LOOPH => {
-                            return Ok( Interaction::Loop(ScheduleOperatorKind::Seq,Box::new(sub_int)) );
+                            return Ok( Interaction::Loop(ScheduleOperatorKind::Seq,bounds,Box::new(sub_int)) );
                         },
                         Rule::SD_LOOPP => {
-                            return Ok( Interaction::Loop(ScheduleOperatorKind::Par,Box::new(sub_int)) );
+                            return Ok( Interaction::Loop(ScheduleOperatorKind::Par,bounds,Box::new(sub_int)) );
                         },
                         _ => {
                             unreachable!();
@@ -114,18 +186,68 @@ pub fn parse_interaction(gen_ctx : &GeneralContext, sd_interaction_pair : Pair<R
                 }
             }
         },
+        Rule::SD_OPT_INT => {
+            let mut opt_content = sd_content_pair.into_inner();
+            opt_content.next(); // get rid of the operator name
+            match parse_interaction(gen_ctx,definitions,expansion,opt_content.next().unwrap()) {
+                Err(e) => {
+                    return Err(e);
+                },
+                Ok( sub_int ) => {
+                    return Ok( Interaction::Opt(Box::new(sub_int)) );
+                }
+            }
+        },
+        Rule::SD_BREAK_INT => {
+            let mut break_content = sd_content_pair.into_inner();
+            break_content.next(); // get rid of the operator name
+            match parse_interaction(gen_ctx,definitions,expansion,break_content.next().unwrap()) {
+                Err(e) => {
+                    return Err(e);
+                },
+                Ok( sub_int ) => {
+                    // the loop the break exits is only known once the whole interaction is parsed
+                    return Ok( Interaction::Break(Vec::new(),Box::new(sub_int)) );
+                }
+            }
+        },
+        Rule::SD_NEG_INT => {
+            let mut neg_content = sd_content_pair.into_inner();
+            neg_content.next(); // get rid of the operator name
+            match parse_interaction(gen_ctx,definitions,expansion,neg_content.next().unwrap()) {
+                Err(e) => {
+                    return Err(e);
+                },
+                Ok( sub_int ) => {
+                    return Ok( Interaction::Neg(false,Box::new(sub_int)) );
+                }
+            }
+        },
+        Rule::SD_ASSERT_INT => {
+            let mut assert_content = sd_content_pair.into_inner();
+            assert_content.next(); // get rid of the operator name
+            match parse_interaction(gen_ctx,definitions,expansion,assert_content.next().unwrap()) {
+                Err(e) => {
+                    return Err(e);
+                },
+                Ok( sub_int ) => {
+                    return Ok( Interaction::Assert(false,Box::new(sub_int)) );
+                }
+            }
+        },
         Rule::SD_SCOPE_INT => {
             let mut scope_content = sd_content_pair.into_inner();
             scope_content.next(); // get rid of the operator name
             // ***
             let mut scoped_vr_ids : Vec<usize> = Vec::new();
             let mut scoped_parameters = scope_content.next().unwrap().into_inner();
+            let mut errors : Vec<HibouParsingError> = Vec::new();
             // ***
             for scoped_var_pair in scoped_parameters {
                 let scoped_var_name : String = scoped_var_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect();
                 match gen_ctx.get_vr_id( &scoped_var_name ) {
                     None => {
-                        return Err( HibouParsingError::MissingVariableDeclarationError( scoped_var_name ) );
+                        errors.push( HibouParsingError::MissingVariableDeclarationError( scoped_var_name ).located_at(&scoped_var_pair) );
                     },
                     Some( vr_id ) => {
                         scoped_vr_ids.push( vr_id );
@@ -133,74 +255,278 @@ pub fn parse_interaction(gen_ctx : &GeneralContext, sd_interaction_pair : Pair<R
                 }
             }
             // ***
-            match parse_interaction(gen_ctx,scope_content.next().unwrap()) {
+            match parse_interaction(gen_ctx,definitions,expansion,scope_content.next().unwrap()) {
                 Err(e) => {
-                    return Err(e);
+                    errors.push(e);
+                    return Err( HibouParsingError::from_several(errors) );
                 },
                 Ok( parsed_sub_int ) => {
+                    if errors.len() > 0 {
+                        return Err( HibouParsingError::from_several(errors) );
+                    }
                     return Ok( Interaction::Scope( scoped_vr_ids, Box::new(parsed_sub_int) ) );
                 }
             }
-        },/*
+        },
+        Rule::SD_CALL_INT => {
+            let mut call_content = sd_content_pair.into_inner();
+            call_content.next(); // get rid of the operator name
+            let call_action_pair = call_content.next().unwrap();
+            let action_location = call_action_pair.clone();
+            // ***
+            let mut body_pair : Option<Pair<Rule>> = None;
+            let mut return_pair : Option<Pair<Rule>> = None;
+            for call_pair in call_content {
+                match call_pair.as_rule() {
+                    Rule::SD_INTERACTION => {
+                        body_pair = Some( call_pair );
+                    },
+                    Rule::SD_CALL_RETURN => {
+                        return_pair = Some( call_pair );
+                    },
+                    _ => {
+                        panic!("what rule then ? : {:?}", call_pair.as_rule() );
+                    }
+                }
+            }
+            // ***
+            match parse_call(gen_ctx,expansion,call_action_pair,return_pair) {
+                Err(e) => {
+                    return Err( e.located_at(&action_location) );
+                },
+                Ok( (caller_lf_id,call_act,reply_act) ) => {
+                    match type_check_parsed_actions(gen_ctx,vec![&call_act,&reply_act],&action_location) {
+                        Some( typing_error ) => {
+                            return Err( typing_error );
+                        },
+                        None => {}
+                    }
+                    let call_reply : Interaction;
+                    match body_pair {
+                        None => {
+                            call_reply = Interaction::Strict( Box::new(Interaction::Action(call_act)),
+                                                              Box::new(Interaction::Action(reply_act)) );
+                        },
+                        Some( got_body_pair ) => {
+                            match parse_interaction(gen_ctx,definitions,expansion,got_body_pair) {
+                                Err(e) => {
+                                    return Err(e);
+                                },
+                                Ok( body ) => {
+                                    call_reply = Interaction::Strict( Box::new(Interaction::Action(call_act)),
+                                                                      Box::new(Interaction::Strict( Box::new(body),
+                                                                                                    Box::new(Interaction::Action(reply_act)) )) );
+                                }
+                            }
+                        }
+                    }
+                    return Ok( Interaction::Call(caller_lf_id, false, Box::new(call_reply)) );
+                }
+            }
+        },
         Rule::SD_CREATE_INT => {
             let mut create_content = sd_content_pair.into_inner();
             create_content.next(); // get rid of the operator name
             // ***
-            let mut create_lf_ids : Vec<(usize,Option<usize>)> = Vec::new();
-            let mut create_parameters = create_content.next().unwrap().into_inner();
+            let mut created_lf_ids : Vec<usize> = Vec::new();
+            let created_lifelines = create_content.next().unwrap().into_inner();
+            let mut errors : Vec<HibouParsingError> = Vec::new();
             // ***
-            for create_lf_pair in create_parameters {
-                match create_lf_pair.as_rule() {
+            for created_lf_pair in created_lifelines {
+                match created_lf_pair.as_rule() {
                     Rule::SD_CREATE_IN_GROUP => {
-                        let mut create_in_group_contents = create_lf_pair.into_inner();
-                        let create_lf_name_pair = create_in_group_contents.next().unwrap();
-                        let create_lf_name : String = create_lf_name_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect();
-                        let create_gp_name_pair = create_in_group_contents.next().unwrap();
-                        let create_gp_name : String = create_gp_name_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect();
-                        let lgr_id = gen_ctx.add_lf_group(create_gp_name);
-                        let lf_id = gen_ctx.add_lf(create_lf_name);
-                        create_lf_ids.push( (lf_id,Some(lgr_id)) );
+                        let mut create_in_group_contents = created_lf_pair.clone().into_inner();
+                        let lf_name : String = expansion.rename( create_in_group_contents.next().unwrap().as_str().chars().filter(|c| !c.is_whitespace()).collect() );
+                        let lgr_name : String = expansion.rename( create_in_group_contents.next().unwrap().as_str().chars().filter(|c| !c.is_whitespace()).collect() );
+                        match parse_created_lifeline(gen_ctx, lf_name, Some(lgr_name)) {
+                            Err(e) => {
+                                errors.push( e.located_at(&created_lf_pair) );
+                            },
+                            Ok( lf_id ) => {
+                                created_lf_ids.push( lf_id );
+                            }
+                        }
                     },
-                    Rule::SD_LIFELINE_OR_GROUP => {
-                        let create_lf_name : String = create_lf_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect();
-                        let lf_id = gen_ctx.add_lf(create_lf_name);
-                        create_lf_ids.push((lf_id,None) );
+                    Rule::SD_CREATE_LIFELINE => {
+                        let lf_name : String = expansion.rename( created_lf_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect() );
+                        match parse_created_lifeline(gen_ctx, lf_name, None) {
+                            Err(e) => {
+                                errors.push( e.located_at(&created_lf_pair) );
+                            },
+                            Ok( lf_id ) => {
+                                created_lf_ids.push( lf_id );
+                            }
+                        }
                     },
                     _ => {
-                        panic!("what rule then ? : {:?}", create_lf_pair.as_rule() );
+                        panic!("what rule then ? : {:?}", created_lf_pair.as_rule() );
                     }
                 }
             }
             // ***
-            match parse_interaction(gen_ctx,create_content.next().unwrap()) {
+            match parse_interaction(gen_ctx,definitions,expansion,create_content.next().unwrap()) {
                 Err(e) => {
-                    return Err(e);
+                    errors.push(e);
+                    return Err( HibouParsingError::from_several(errors) );
                 },
                 Ok( parsed_sub_int ) => {
-                    return Ok( Interaction::Create( create_lf_ids, Box::new(parsed_sub_int) ) );
+                    if errors.len() > 0 {
+                        return Err( HibouParsingError::from_several(errors) );
+                    }
+                    return Ok( Interaction::Create( created_lf_ids, Box::new(parsed_sub_int) ) );
+                }
+            }
+        },
+        Rule::SD_REF_INT => {
+            let ref_location = sd_content_pair.clone();
+            let mut ref_content = sd_content_pair.into_inner();
+            ref_content.next(); // get rid of the operator name
+            let def_name : String = ref_content.next().unwrap().as_str().chars().filter(|c| !c.is_whitespace()).collect();
+            let mut arguments : Vec<String> = Vec::new();
+            match ref_content.next() {
+                None => {},
+                Some( arguments_pair ) => {
+                    for argument_pair in arguments_pair.into_inner() {
+                        arguments.push( expansion.rename( argument_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect() ) );
+                    }
+                }
+            }
+            // ***
+            match definitions.get(&def_name) {
+                None => {
+                    return Err( HibouParsingError::MalformedReference( format!("'{}' is not defined", def_name) ).located_at(&ref_location) );
+                },
+                Some( definition ) => {
+                    match expansion.enter(&def_name, definition, &arguments) {
+                        Err(e) => {
+                            return Err( e.located_at(&ref_location) );
+                        },
+                        Ok( inner_expansion ) => {
+                            let ref_label = get_reference_label(&def_name, &arguments);
+                            match parse_interaction(gen_ctx,definitions,&inner_expansion,definition.body.clone()) {
+                                Err(mut e) => {
+                                    // errors are located in the definition, the reference which led to them is given as well
+                                    if let Some( def_file ) = &definition.file {
+                                        e = e.in_file(def_file);
+                                    }
+                                    let ref_error = HibouParsingError::MalformedReference( format!("error while expanding '{}'", ref_label) ).located_at(&ref_location);
+                                    return Err( HibouParsingError::from_several(vec![e,ref_error]) );
+                                },
+                                Ok( body ) => {
+                                    let ref_id = gen_ctx.add_ref_label(ref_label);
+                                    return Ok( Interaction::Ref( ref_id, Box::new(body) ) );
+                                }
+                            }
+                        }
+                    }
                 }
             }
-        },*/
+        },
         _ => {
             panic!("what rule then ? : {:?}", sd_content_pair.as_rule());
         }
     }
 }
 
-fn get_nary_sub_interactions(gen_ctx : &GeneralContext, sd_content_pair : Pair<Rule>) -> Result<Vec<Interaction>,HibouParsingError> {
+/*
+    Created lifelines need not be declared beforehand
+    A lifeline may be created in at most one group and the names of groups and lifelines must be distinct
+*/
+fn parse_created_lifeline(gen_ctx : &mut GeneralContext,
+                          lf_name : String,
+                          lgr_name : Option<String>) -> Result<usize,HibouParsingError> {
+    match gen_ctx.get_lgr_id(&lf_name) {
+        None => {},
+        Some( _ ) => {
+            return Err( HibouParsingError::MalformedCreation( format!("'{}' is a lifeline group and cannot be created as a lifeline", lf_name) ) );
+        }
+    }
+    let lf_id = gen_ctx.add_lf(lf_name.clone());
+    gen_ctx.add_created_lf(lf_id);
+    match lgr_name {
+        None => {},
+        Some( lgr_name ) => {
+            match gen_ctx.get_lf_id(&lgr_name) {
+                None => {},
+                Some( _ ) => {
+                    return Err( HibouParsingError::MalformedCreation( format!("'{}' is a lifeline and cannot be used as a lifeline group", lgr_name) ) );
+                }
+            }
+            let lgr_id = gen_ctx.add_lf_group(lgr_name.clone());
+            match gen_ctx.get_lf_group(lf_id) {
+                None => {
+                    gen_ctx.set_lf_group(lf_id, lgr_id);
+                },
+                Some( other_lgr_id ) => {
+                    if other_lgr_id != lgr_id {
+                        return Err( HibouParsingError::MalformedCreation( format!("lifeline '{}' is created in both groups '{}' and '{}'",
+                                                                                 lf_name, gen_ctx.get_lgr_name(other_lgr_id).unwrap(), lgr_name) ) );
+                    }
+                }
+            }
+        }
+    }
+    return Ok( lf_id );
+}
+
+fn parse_loop_bounds(bounds_pair : &Pair<Rule>) -> Result<LoopBounds,HibouParsingError> {
+    let mut bounds_content = bounds_pair.clone().into_inner();
+    let min_pair = bounds_content.next().unwrap();
+    let min : u32;
+    match min_pair.as_str().parse::<u32>() {
+        Err(_) => {
+            return Err( HibouParsingError::MalformedLoopBounds(format!("could not parse minimum number of iterations '{}'", min_pair.as_str())) );
+        },
+        Ok( got_min ) => {
+            min = got_min;
+        }
+    }
+    let max_pair = bounds_content.next().unwrap();
+    match max_pair.as_rule() {
+        Rule::SD_LOOP_UNBOUNDED_MAX => {
+            return Ok( LoopBounds{min,max:None} );
+        },
+        _ => {
+            match max_pair.as_str().parse::<u32>() {
+                Err(_) => {
+                    return Err( HibouParsingError::MalformedLoopBounds(format!("could not parse maximum number of iterations '{}'", max_pair.as_str())) );
+                },
+                Ok( max ) => {
+                    if max == 0 {
+                        return Err( HibouParsingError::MalformedLoopBounds("maximum number of iterations must be at least 1".to_string()) );
+                    }
+                    if min > max {
+                        return Err( HibouParsingError::MalformedLoopBounds(format!("minimum number of iterations {} is greater than maximum {}", min, max)) );
+                    }
+                    return Ok( LoopBounds{min,max:Some(max)} );
+                }
+            }
+        }
+    }
+}
+
+fn get_nary_sub_interactions(gen_ctx : &mut GeneralContext,
+                             definitions : &InteractionDefinitions,
+                             expansion : &ReferenceExpansion,
+                             sd_content_pair : Pair<Rule>) -> Result<Vec<Interaction>,HibouParsingError> {
     let mut strict_content = sd_content_pair.into_inner();
     strict_content.next(); // get rid of the operator name
     let mut sub_ints : Vec<Interaction> = Vec::new();
+    // errors in a sub-interaction do not prevent parsing the other ones
+    let mut errors : Vec<HibouParsingError> = Vec::new();
     for sub_interaction in strict_content {
-        match parse_interaction(gen_ctx,sub_interaction) {
+        match parse_interaction(gen_ctx,definitions,expansion,sub_interaction) {
             Err(e) => {
-                return Err(e);
+                errors.push(e);
             },
             Ok( parsed_sub_int ) => {
                 sub_ints.push( parsed_sub_int );
             }
         }
     }
+    if errors.len() > 0 {
+        return Err( HibouParsingError::from_several(errors) );
+    }
     return Ok( sub_ints );
 }
 
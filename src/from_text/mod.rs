@@ -18,4 +18,6 @@ pub mod hsf_file;
 pub mod htf_file;
 mod hibou_options;
 mod setup;
+mod type_check;
+mod reference;
 
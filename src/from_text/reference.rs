@@ -0,0 +1,121 @@
+/********************************************************************************
+ * Copyright (c) 2020 Erwan Mahe (github.com/erwanM974)
+ *
+ * This program and the accompanying materials are made available under the
+ * terms of the Eclipse Public License 2.0 which is available at
+ * http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+ * which is available at https://www.apache.org/licenses/LICENSE-2.0.
+ *
+ * SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+ ********************************************************************************/
+
+use std::collections::HashMap;
+
+use pest::iterators::Pair;
+
+use crate::from_text::parser::*;
+use crate::from_text::error::HibouParsingError;
+
+
+/*
+    A named sub-interaction declared in a '@define' section
+    Its body is kept unparsed so that it can be parsed anew for each reference, with its parameters renamed
+    The file is that from which the definition is imported, if any
+*/
+pub struct InteractionDefinition<'i> {
+    pub parameters : Vec<String>,
+    pub body : Pair<'i,Rule>,
+    pub file : Option<String>
+}
+
+pub type InteractionDefinitions<'i> = HashMap<String,InteractionDefinition<'i>>;
+
+/*
+    Names of lifelines and messages as they are to be understood when parsing the body of a definition
+    together with the definitions being expanded, so as to reject recursive references
+*/
+#[derive(Clone)]
+pub struct ReferenceExpansion {
+    renaming : HashMap<String,String>,
+    expanded : Vec<String>
+}
+
+impl ReferenceExpansion {
+
+    pub fn new() -> ReferenceExpansion {
+        return ReferenceExpansion{renaming:HashMap::new(),expanded:Vec::new()};
+    }
+
+    pub fn rename(&self, name : String) -> String {
+        match self.renaming.get(&name) {
+            None => {
+                return name;
+            },
+            Some( renamed ) => {
+                return renamed.clone();
+            }
+        }
+    }
+
+    /*
+        The arguments are expected to be already renamed w.r.t. the current expansion
+        so that a definition may forward its own parameters to the definitions it refers to
+    */
+    pub fn enter(&self,
+                 def_name : &str,
+                 definition : &InteractionDefinition,
+                 arguments : &Vec<String>) -> Result<ReferenceExpansion,HibouParsingError> {
+        if self.expanded.iter().any(|exp| exp == def_name) {
+            return Err( HibouParsingError::MalformedReference( format!("'{}' is referred to within its own definition", def_name) ) );
+        }
+        if definition.parameters.len() != arguments.len() {
+            return Err( HibouParsingError::MalformedReference( format!("'{}' expects {} arguments but {} are given",
+                                                                       def_name, definition.parameters.len(), arguments.len()) ) );
+        }
+        let mut renaming : HashMap<String,String> = HashMap::new();
+        for (param,arg) in definition.parameters.iter().zip(arguments.iter()) {
+            renaming.insert( param.clone(), arg.clone() );
+        }
+        let mut expanded = self.expanded.clone();
+        expanded.push( def_name.to_string() );
+        return Ok( ReferenceExpansion{renaming,expanded} );
+    }
+}
+
+pub fn parse_definition<'i>(define_pair : Pair<'i,Rule>,
+                            file : Option<String>,
+                            definitions : &mut InteractionDefinitions<'i>) -> Result<(),HibouParsingError> {
+    let mut define_content = define_pair.into_inner();
+    let name_pair = define_content.next().unwrap();
+    let def_name : String = name_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect();
+    if definitions.contains_key(&def_name) {
+        return Err( HibouParsingError::MalformedReference( format!("'{}' is defined several times", def_name) ).located_at(&name_pair) );
+    }
+    // ***
+    let mut parameters : Vec<String> = Vec::new();
+    let mut next_pair = define_content.next().unwrap();
+    if next_pair.as_rule() == Rule::DEFINE_PARAMETERS {
+        for param_pair in next_pair.into_inner() {
+            let param_name : String = param_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect();
+            if parameters.contains(&param_name) {
+                return Err( HibouParsingError::MalformedReference( format!("parameter '{}' of '{}' is declared several times", param_name, def_name) ).located_at(&param_pair) );
+            }
+            parameters.push( param_name );
+        }
+        next_pair = define_content.next().unwrap();
+    }
+    // ***
+    definitions.insert( def_name, InteractionDefinition{parameters,body:next_pair,file} );
+    return Ok(());
+}
+
+/*
+    The label of a reference is its name followed by its arguments, as they are after renaming
+*/
+pub fn get_reference_label(def_name : &str, arguments : &Vec<String>) -> String {
+    if arguments.len() == 0 {
+        return def_name.to_string();
+    } else {
+        return format!("{}({})", def_name, arguments.join(","));
+    }
+}
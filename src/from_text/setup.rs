@@ -30,7 +30,7 @@ use crate::core::syntax::data::builtin::float::TD_Float;
 use crate::core::syntax::data::builtin::integer::TD_Integer;
 use crate::core::syntax::data::builtin::string::TD_String;
 use crate::core::syntax::data::builtin::bool::TD_Bool;
-use crate::from_text::error::HibouParsingError;
+use crate::from_text::error::{HibouParsingError,SourceLocation};
 use crate::process::log::ProcessLogger;
 
 use crate::from_text::parser::*;
@@ -45,7 +45,8 @@ use crate::from_text::hsf_file::ProcessKind;
 pub struct InterpretationItemPlan {
     pub lf_id : usize,
     pub vr_id : usize,
-    pub assignment : ValueOrNewFresh
+    pub assignment : ValueOrNewFresh,
+    pub location : SourceLocation
 }
 
 pub fn parse_setup(setup_pair : Pair<Rule>,
@@ -62,20 +63,22 @@ pub fn parse_setup(setup_pair : Pair<Rule>,
     // ***
     let mut interpretation_plan : Vec<InterpretationItemPlan> = Vec::new();
     let mut hibou_options_opt : Option<HibouOptions> = None;
+    // errors in a section do not prevent parsing the other ones
+    let mut errors : Vec<HibouParsingError> = Vec::new();
     // ***
     let mut contents = setup_pair.into_inner();
     while let Some(current_pair) = contents.next() {
         match current_pair.as_rule() {
             Rule::EXPLORE_OPTION_SECTION => {
                 if got_section_explore_options {
-                    return Err( HibouParsingError::HsfSetupError("several '@explore_option' sections declared".to_string()));
+                    return Err( HibouParsingError::HsfSetupError("several '@explore_option' sections declared".to_string()).located_at(&current_pair) );
                 }
                 got_section_explore_options = true;
                 match process_kind {
                     &ProcessKind::Explore => {
                         match parse_hibou_options(current_pair,file_name, process_kind) {
                             Err(e) => {
-                                return Err(e);
+                                errors.push(e);
                             },
                             Ok( hoptions ) => {
                                 hibou_options_opt = Some(hoptions);
@@ -87,14 +90,14 @@ pub fn parse_setup(setup_pair : Pair<Rule>,
             },
             Rule::ANALYZE_OPTION_SECTION => {
                 if got_section_analyze_options {
-                    return Err( HibouParsingError::HsfSetupError("several '@analyze_option' sections declared".to_string()));
+                    return Err( HibouParsingError::HsfSetupError("several '@analyze_option' sections declared".to_string()).located_at(&current_pair) );
                 }
                 got_section_analyze_options = true;
                 match process_kind {
                     &ProcessKind::Analyze => {
                         match parse_hibou_options(current_pair,file_name, process_kind) {
                             Err(e) => {
-                                return Err(e);
+                                errors.push(e);
                             },
                             Ok( hoptions ) => {
                                 hibou_options_opt = Some(hoptions);
@@ -106,44 +109,68 @@ pub fn parse_setup(setup_pair : Pair<Rule>,
             },
             Rule::HIBOU_MODEL_MS_DECL => {
                 if got_section_messages {
-                    return Err( HibouParsingError::HsfSetupError("several '@message' sections declared".to_string()));
+                    return Err( HibouParsingError::HsfSetupError("several '@message' sections declared".to_string()).located_at(&current_pair) );
                 }
                 got_section_messages = true;
-                parse_message_decl(current_pair,gen_ctx);
+                match parse_message_decl(current_pair,gen_ctx) {
+                    Err(e) => {
+                        errors.push(e);
+                    },
+                    Ok( _ ) => {}
+                }
             },
             Rule::HIBOU_MODEL_VAR_DECL => {
                 if got_section_variables {
-                    return Err( HibouParsingError::HsfSetupError("several '@variable' sections declared".to_string()));
+                    return Err( HibouParsingError::HsfSetupError("several '@variable' sections declared".to_string()).located_at(&current_pair) );
                 }
                 got_section_variables = true;
-                parse_variable_decl(current_pair,gen_ctx);
+                match parse_variable_decl(current_pair,gen_ctx) {
+                    Err(e) => {
+                        errors.push(e);
+                    },
+                    Ok( _ ) => {}
+                }
             },
             Rule::HIBOU_MODEL_LF_DECL => {
                 if got_section_lifelines {
-                    return Err( HibouParsingError::HsfSetupError("several '@lifeline' sections declared".to_string()));
+                    return Err( HibouParsingError::HsfSetupError("several '@lifeline' sections declared".to_string()).located_at(&current_pair) );
                 }
                 got_section_lifelines = true;
-                parse_lifeline_decl(current_pair,gen_ctx);
+                match parse_lifeline_decl(current_pair,gen_ctx) {
+                    Err(e) => {
+                        errors.push(e);
+                    },
+                    Ok( _ ) => {}
+                }
             },
             Rule::HIBOU_MODEL_VAR_INIT => {
                 if got_section_init {
-                    return Err( HibouParsingError::HsfSetupError("several '@init' sections declared".to_string()));
+                    return Err( HibouParsingError::HsfSetupError("several '@init' sections declared".to_string()).located_at(&current_pair) );
                 }
                 got_section_init = true;
                 match parse_initialization(current_pair,gen_ctx) {
                     Err(e) => {
-                        return Err(e);
+                        errors.push(e);
                     },
                     Ok( iip ) => {
                         interpretation_plan = iip;
                     }
                 }
             },
+            Rule::HIBOU_MODEL_DEFINE => {
+                // definitions are only needed when parsing the interaction
+            },
+            Rule::HIBOU_MODEL_IMPORT => {
+                // imported files are parsed beforehand
+            },
             _ => {
                 unreachable!();
             }
         }
     }
+    if errors.len() > 0 {
+        return Err( HibouParsingError::from_several(errors) );
+    }
     match hibou_options_opt {
         None => {
             match process_kind {
@@ -162,65 +189,115 @@ pub fn parse_setup(setup_pair : Pair<Rule>,
 }
 
 fn parse_message_decl(ms_decl_pair : Pair<Rule>, gen_ctx : &mut GeneralContext) -> Result<(),HibouParsingError> {
+    let mut errors : Vec<HibouParsingError> = Vec::new();
     for msg_decl_pair in ms_decl_pair.into_inner() {
         let mut msg_decl_content = msg_decl_pair.into_inner();
-        let ms_name : String = msg_decl_content.next().unwrap().as_str().chars().filter(|c| !c.is_whitespace()).collect();
+        let ms_name_pair = msg_decl_content.next().unwrap();
+        let ms_name : String = ms_name_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect();
         let mut ms_spec : Vec<(TD_DataType,Option<String>)> = Vec::new();
-        match msg_decl_content.next() {
-            None => {},
-            Some( msg_pr_decl ) => {
-                for pr_decl_pair in msg_pr_decl.into_inner() {
-                    match pr_decl_pair.as_rule() {
-                        Rule::PRM_DECLARATION => {
-                            let mut var_decl_content = pr_decl_pair.into_inner();
-                            let vr_name : String = var_decl_content.next().unwrap().as_str().chars().filter(|c| !c.is_whitespace()).collect();
-                            let td_type_pair = var_decl_content.next().unwrap();
-                            let (td_type, is_clock) = parse_type(td_type_pair);
-                            if is_clock {
-                                panic!("message parameter argument cannot be a clock");
-                            }
-                            ms_spec.push( (td_type,Some(vr_name)) );
-                        },
-                        _ => {
-                            let (td_type, is_clock) = parse_type(pr_decl_pair);
-                            if is_clock {
-                                panic!("message parameter argument cannot be a clock");
+        let mut return_type : Option<TD_DataType> = None;
+        for msg_decl_item in msg_decl_content {
+            match msg_decl_item.as_rule() {
+                Rule::MSG_RETURN_DECL => {
+                    let td_type_pair = msg_decl_item.into_inner().next().unwrap();
+                    let (td_type, is_clock) = parse_type(td_type_pair.clone());
+                    if is_clock {
+                        errors.push( HibouParsingError::ClockMisuse("message return type cannot be a clock".to_string()).located_at(&td_type_pair) );
+                    }
+                    return_type = Some( td_type );
+                },
+                _ => {
+                    for pr_decl_pair in msg_decl_item.into_inner() {
+                        match pr_decl_pair.as_rule() {
+                            Rule::PRM_DECLARATION => {
+                                let mut var_decl_content = pr_decl_pair.into_inner();
+                                let vr_name : String = var_decl_content.next().unwrap().as_str().chars().filter(|c| !c.is_whitespace()).collect();
+                                let td_type_pair = var_decl_content.next().unwrap();
+                                let (td_type, is_clock) = parse_type(td_type_pair.clone());
+                                if is_clock {
+                                    errors.push( HibouParsingError::ClockMisuse("message parameter argument cannot be a clock".to_string()).located_at(&td_type_pair) );
+                                }
+                                ms_spec.push( (td_type,Some(vr_name)) );
+                            },
+                            _ => {
+                                let (td_type, is_clock) = parse_type(pr_decl_pair.clone());
+                                if is_clock {
+                                    errors.push( HibouParsingError::ClockMisuse("message parameter argument cannot be a clock".to_string()).located_at(&pr_decl_pair) );
+                                }
+                                ms_spec.push( (td_type,None) );
                             }
-                            ms_spec.push( (td_type,None) );
                         }
                     }
                 }
             }
         }
-        gen_ctx.add_msg(ms_name,ms_spec);
+        match gen_ctx.add_msg(ms_name,ms_spec) {
+            Err(_) => {
+                errors.push( HibouParsingError::NameCollision( format!("message '{}' is declared several times", ms_name_pair.as_str().trim()) ).located_at(&ms_name_pair) );
+            },
+            Ok( ms_id ) => {
+                if let Some( got_return_type ) = return_type {
+                    gen_ctx.set_ms_return_type(ms_id,got_return_type);
+                }
+            }
+        }
+    }
+    if errors.len() > 0 {
+        return Err( HibouParsingError::from_several(errors) );
     }
     return Ok(());
 }
 
-fn parse_variable_decl(vr_decl_pair : Pair<Rule>, gen_ctx : &mut GeneralContext ) {
+fn parse_variable_decl(vr_decl_pair : Pair<Rule>, gen_ctx : &mut GeneralContext ) -> Result<(),HibouParsingError> {
+    let mut errors : Vec<HibouParsingError> = Vec::new();
     for var_decl_pair in vr_decl_pair.into_inner() {
         let mut var_decl_content = var_decl_pair.into_inner();
-        let vr_name : String = var_decl_content.next().unwrap().as_str().chars().filter(|c| !c.is_whitespace()).collect();
+        let vr_name_pair = var_decl_content.next().unwrap();
+        let vr_name : String = vr_name_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect();
         let td_type_pair = var_decl_content.next().unwrap();
         let (td_type, is_clock) = parse_type(td_type_pair);
-        let new_vr_id = gen_ctx.get_vr_num();
-        gen_ctx.add_vr( vr_name.clone(), td_type);
-        if is_clock {
-            gen_ctx.add_as_clock( new_vr_id );
+        match gen_ctx.add_vr( vr_name, td_type) {
+            Err(_) => {
+                errors.push( HibouParsingError::NameCollision( format!("variable '{}' is declared several times", vr_name_pair.as_str().trim()) ).located_at(&vr_name_pair) );
+            },
+            Ok( new_vr_id ) => {
+                if is_clock {
+                    gen_ctx.add_as_clock( new_vr_id );
+                }
+            }
         }
     }
+    if errors.len() > 0 {
+        return Err( HibouParsingError::from_several(errors) );
+    }
+    return Ok(());
 }
 
-fn parse_lifeline_decl(lf_decl_pair : Pair<Rule>, gen_ctx : &mut GeneralContext ) {
+fn parse_lifeline_decl(lf_decl_pair : Pair<Rule>, gen_ctx : &mut GeneralContext ) -> Result<(),HibouParsingError> {
+    let mut errors : Vec<HibouParsingError> = Vec::new();
     for lf_pair in lf_decl_pair.into_inner() {
         let lf_name : String = lf_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect();
-        gen_ctx.add_lf(lf_name);
+        // lifelines created in the interaction may be declared as well, but only once
+        match gen_ctx.get_lf_id(&lf_name) {
+            None => {
+                gen_ctx.add_lf(lf_name);
+            },
+            Some( _ ) => {
+                errors.push( HibouParsingError::NameCollision( format!("lifeline '{}' is declared several times", lf_name) ).located_at(&lf_pair) );
+            }
+        }
+    }
+    if errors.len() > 0 {
+        return Err( HibouParsingError::from_several(errors) );
     }
+    return Ok(());
 }
 
 fn parse_initialization(init_pair : Pair<Rule>, gen_ctx : &GeneralContext ) -> Result<Vec<InterpretationItemPlan>, HibouParsingError> {
     let mut interpretation_plan : Vec<InterpretationItemPlan> = Vec::new();
+    let mut errors : Vec<HibouParsingError> = Vec::new();
     for var_init_pair in init_pair.into_inner() {
+        let location = SourceLocation::from_pair(&var_init_pair);
         let mut var_init_contents = var_init_pair.into_inner();
         // ***
         let lf_id : usize;
@@ -229,7 +306,8 @@ fn parse_initialization(init_pair : Pair<Rule>, gen_ctx : &GeneralContext ) -> R
             let lf_name : String = lf_name_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect();
             match gen_ctx.get_lf_id( &lf_name) {
                 None => {
-                    return Err( HibouParsingError::MissingLifelineDeclarationError(lf_name) );
+                    errors.push( HibouParsingError::MissingLifelineDeclarationError(lf_name).located_at(&lf_name_pair) );
+                    continue;
                 },
                 Some( got_lf_id ) => {
                     lf_id = got_lf_id;
@@ -243,7 +321,8 @@ fn parse_initialization(init_pair : Pair<Rule>, gen_ctx : &GeneralContext ) -> R
             let vr_name : String = vr_name_pair.as_str().chars().filter(|c| !c.is_whitespace()).collect();
             match gen_ctx.get_vr_id( &vr_name) {
                 None => {
-                    return Err( HibouParsingError::MissingVariableDeclarationError(vr_name) );
+                    errors.push( HibouParsingError::MissingVariableDeclarationError(vr_name).located_at(&vr_name_pair) );
+                    continue;
                 },
                 Some( got_vr_id ) => {
                     vr_id = got_vr_id;
@@ -257,15 +336,15 @@ fn parse_initialization(init_pair : Pair<Rule>, gen_ctx : &GeneralContext ) -> R
                 Rule::TD_VALUE => {
                     match parse_data(gen_ctx,assignment_pair,&None) {
                         Err(e) => {
-                            return Err(e);
+                            errors.push(e);
                         },
                         Ok( td_generic ) => {
-                            interpretation_plan.push( InterpretationItemPlan{lf_id,vr_id,assignment:ValueOrNewFresh::Value(td_generic)});
+                            interpretation_plan.push( InterpretationItemPlan{lf_id,vr_id,assignment:ValueOrNewFresh::Value(td_generic),location});
                         }
                     }
                 },
                 Rule::NEW_FRESH => {
-                    interpretation_plan.push( InterpretationItemPlan{lf_id,vr_id,assignment:ValueOrNewFresh::NewFresh});
+                    interpretation_plan.push( InterpretationItemPlan{lf_id,vr_id,assignment:ValueOrNewFresh::NewFresh,location});
                 },
                 _ => {
                     panic!("what rule then ? : {:?}", assignment_pair.as_rule() );
@@ -274,5 +353,8 @@ fn parse_initialization(init_pair : Pair<Rule>, gen_ctx : &GeneralContext ) -> R
         }
         // ***
     }
+    if errors.len() > 0 {
+        return Err( HibouParsingError::from_several(errors) );
+    }
     return Ok(interpretation_plan);
 }
\ No newline at end of file
@@ -0,0 +1,302 @@
+/********************************************************************************
+ * Copyright (c) 2020 Erwan Mahe (github.com/erwanM974)
+ *
+ * This program and the accompanying materials are made available under the
+ * terms of the Eclipse Public License 2.0 which is available at
+ * http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+ * which is available at https://www.apache.org/licenses/LICENSE-2.0.
+ *
+ * SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+ ********************************************************************************/
+
+use pest::iterators::Pair;
+
+use crate::core::context::general::GeneralContext;
+use crate::core::syntax::action::*;
+use crate::core::syntax::data::td_type::TD_DataType;
+use crate::core::syntax::data::generic::TD_Generic;
+use crate::core::syntax::data::builtin::bool::{TD_Bool,Bool_Compare};
+use crate::core::syntax::data::builtin::integer::TD_Integer;
+use crate::core::syntax::data::builtin::float::TD_Float;
+use crate::core::syntax::data::builtin::string::TD_String;
+use crate::core::syntax::data::var_ref::VariableReference;
+
+use crate::from_text::parser::*;
+use crate::from_text::error::HibouParsingError;
+use crate::from_text::setup::InterpretationItemPlan;
+
+/*
+    Checks that the '@init' values are consistent with the types declared in the general context
+    Returns every typing error found, located at the '@init' item in which it occurs
+*/
+pub fn type_check_interpretation_plan(gen_ctx : &GeneralContext,
+                                      interpretation_plan : &Vec<InterpretationItemPlan>) -> Vec<HibouParsingError> {
+    let mut typing_errors : Vec<HibouParsingError> = Vec::new();
+    for iip in interpretation_plan {
+        let mut errors : Vec<String> = Vec::new();
+        match &iip.assignment {
+            ValueOrNewFresh::NewFresh => {},
+            ValueOrNewFresh::Value( td_gen ) => {
+                check_assigned_value(gen_ctx, iip.vr_id, td_gen, "initialisation", &mut errors);
+            }
+        }
+        for error in errors {
+            typing_errors.push( HibouParsingError::Located(iip.location.clone(), Box::new(HibouParsingError::TypeMismatch(error))) );
+        }
+    }
+    return typing_errors;
+}
+
+/*
+    Checks that the guards, assignments and message parameters of actions are consistent with the types declared in the general context
+    Done as soon as the actions are parsed so that the errors are located at the pair from which they are parsed
+    A call is parsed into the call action and the reply, both located at the call
+*/
+pub fn type_check_parsed_actions(gen_ctx : &GeneralContext,
+                                 actions : Vec<&ObservableAction>,
+                                 action_pair : &Pair<Rule>) -> Option<HibouParsingError> {
+    let mut typing_errors : Vec<HibouParsingError> = Vec::new();
+    for action in actions {
+        let mut errors : Vec<String> = Vec::new();
+        type_check_action(gen_ctx, action, &mut errors);
+        for error in errors {
+            typing_errors.push( HibouParsingError::TypeMismatch(error).located_at(action_pair) );
+        }
+    }
+    if typing_errors.len() > 0 {
+        return Some( HibouParsingError::from_several(typing_errors) );
+    } else {
+        return None;
+    }
+}
+
+fn type_check_action(gen_ctx : &GeneralContext, action : &ObservableAction, errors : &mut Vec<String>) {
+    match action.com_kind {
+        CommunicationKind::Reply => {
+            type_check_reply(gen_ctx, action, errors);
+            return;
+        },
+        _ => {}
+    }
+    let ms_name = gen_ctx.get_ms_name(action.ms_id).unwrap();
+    let ms_spec = gen_ctx.get_ms_spec(action.ms_id).unwrap();
+    // parameters may be left out altogether, which the linter reports
+    if action.params.len() > 0 && action.params.len() != ms_spec.len() {
+        errors.push( format!("message '{}' expects {} parameter(s) and is given {}", ms_name, ms_spec.len(), action.params.len()) );
+    }
+    for (pr_id,(param,(pr_type,_))) in action.params.iter().zip(ms_spec.iter()).enumerate() {
+        match param {
+            ValueOrNewFresh::NewFresh => {},
+            ValueOrNewFresh::Value( td_gen ) => {
+                check_generic(gen_ctx, td_gen, errors);
+                let got_type = td_gen.get_td_type();
+                if &got_type != pr_type {
+                    errors.push( format!("parameter {} of message '{}' is of type {:?} and is given a term of type {:?}", pr_id, ms_name, pr_type, got_type) );
+                }
+            }
+        }
+    }
+    // ***
+    check_lifeline_action(gen_ctx, &action.lf_act, errors);
+    match &action.act_kind {
+        ObservableActionKind::Reception => {},
+        ObservableActionKind::Emission( targets ) => {
+            for target in targets {
+                check_lifeline_action(gen_ctx, target, errors);
+            }
+        }
+    }
+}
+
+/*
+    The parameter of a reply is the value returned by the callee, of the return type of the message,
+    and the ambles of a reply are only made of the assignment of that value on the caller
+*/
+fn type_check_reply(gen_ctx : &GeneralContext, action : &ObservableAction, errors : &mut Vec<String>) {
+    let ms_name = gen_ctx.get_ms_name(action.ms_id).unwrap();
+    match gen_ctx.get_ms_return_type(action.ms_id) {
+        None => {},
+        Some( return_type ) => {
+            match action.params.get(0) {
+                Some( ValueOrNewFresh::Value(td_gen) ) => {
+                    check_generic(gen_ctx, td_gen, errors);
+                    let got_type = td_gen.get_td_type();
+                    if got_type != return_type {
+                        errors.push( format!("call of message '{}' returns a value of type {:?} and is given a term of type {:?}", ms_name, return_type, got_type) );
+                    }
+                },
+                _ => {}
+            }
+            match &action.act_kind {
+                ObservableActionKind::Reception => {},
+                ObservableActionKind::Emission( targets ) => {
+                    for target in targets {
+                        for amble_item in &target.postamble {
+                            match amble_item {
+                                ActionAmbleItem::Assignment( vr_id, _ ) => {
+                                    check_not_a_clock(gen_ctx, *vr_id, errors);
+                                    let vr_type = gen_ctx.get_vr_type(*vr_id).unwrap();
+                                    if vr_type != return_type {
+                                        errors.push( format!("result of the call of message '{}' of type {:?} is assigned to variable '{}' of type {:?}", ms_name, return_type, gen_ctx.get_vr_name(*vr_id).unwrap(), vr_type) );
+                                    }
+                                },
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn check_lifeline_action(gen_ctx : &GeneralContext, lf_act : &LifelineAction, errors : &mut Vec<String>) {
+    for amble_item in lf_act.preamble.iter().chain(lf_act.postamble.iter()) {
+        match amble_item {
+            ActionAmbleItem::Guard( td_bool ) => {
+                check_bool(gen_ctx, td_bool, errors);
+            },
+            ActionAmbleItem::Assignment( vr_id, ValueOrNewFresh::NewFresh ) => {
+                check_not_a_clock(gen_ctx, *vr_id, errors);
+            },
+            ActionAmbleItem::Assignment( vr_id, ValueOrNewFresh::Value(td_gen) ) => {
+                check_not_a_clock(gen_ctx, *vr_id, errors);
+                check_assigned_value(gen_ctx, *vr_id, td_gen, "assignment", errors);
+            },
+            ActionAmbleItem::Reset(_) => {}
+        }
+    }
+}
+
+fn check_not_a_clock(gen_ctx : &GeneralContext, vr_id : usize, errors : &mut Vec<String>) {
+    if gen_ctx.is_clock(vr_id) {
+        errors.push( format!("clock '{}' is assigned a value ; clocks can only be reset", gen_ctx.get_vr_name(vr_id).unwrap()) );
+    }
+}
+
+fn check_assigned_value(gen_ctx : &GeneralContext, vr_id : usize, td_gen : &TD_Generic, what : &str, errors : &mut Vec<String>) {
+    check_generic(gen_ctx, td_gen, errors);
+    let vr_type = gen_ctx.get_vr_type(vr_id).unwrap();
+    let got_type = td_gen.get_td_type();
+    if got_type != vr_type {
+        errors.push( format!("{} of variable '{}' of type {:?} with a term of type {:?}", what, gen_ctx.get_vr_name(vr_id).unwrap(), vr_type, got_type) );
+    }
+}
+
+fn check_generic(gen_ctx : &GeneralContext, td_gen : &TD_Generic, errors : &mut Vec<String>) {
+    match td_gen {
+        TD_Generic::Bool( td_bool ) => {
+            check_bool(gen_ctx, td_bool, errors);
+        },
+        TD_Generic::Integer( td_int ) => {
+            check_integer(gen_ctx, td_int, errors);
+        },
+        TD_Generic::Float( td_float ) => {
+            check_float(gen_ctx, td_float, errors);
+        },
+        TD_Generic::String( td_string ) => {
+            match td_string {
+                TD_String::Value(_) => {},
+                TD_String::Reference( var_ref ) => {
+                    check_reference(gen_ctx, var_ref, TD_DataType::String, errors);
+                }
+            }
+        }
+    }
+}
+
+fn check_bool(gen_ctx : &GeneralContext, td_bool : &TD_Bool, errors : &mut Vec<String>) {
+    match td_bool {
+        TD_Bool::TRUE | TD_Bool::FALSE => {},
+        TD_Bool::AND( sub_bools ) | TD_Bool::OR( sub_bools ) => {
+            for sub_bool in sub_bools {
+                check_bool(gen_ctx, sub_bool, errors);
+            }
+        },
+        TD_Bool::NOT( sub_bool ) => {
+            check_bool(gen_ctx, sub_bool, errors);
+        },
+        TD_Bool::COMPARE( kind, first, second ) => {
+            check_generic(gen_ctx, first, errors);
+            check_generic(gen_ctx, second, errors);
+            let first_type = first.get_td_type();
+            let second_type = second.get_td_type();
+            if first_type != second_type {
+                errors.push( format!("comparison between a term of type {:?} and a term of type {:?}", first_type, second_type) );
+            } else if first_type == TD_DataType::Bool {
+                match kind {
+                    Bool_Compare::Equal | Bool_Compare::Different => {},
+                    _ => {
+                        errors.push( "booleans can only be compared for equality or difference".to_string() );
+                    }
+                }
+            }
+        },
+        TD_Bool::Reference( var_ref ) => {
+            check_reference(gen_ctx, var_ref, TD_DataType::Bool, errors);
+        }
+    }
+}
+
+fn check_integer(gen_ctx : &GeneralContext, td_int : &TD_Integer, errors : &mut Vec<String>) {
+    match td_int {
+        TD_Integer::Value(_) => {},
+        TD_Integer::Minus( sub_int ) => {
+            check_integer(gen_ctx, sub_int, errors);
+        },
+        TD_Integer::Factor( factors ) => {
+            for (_,sub_int) in factors {
+                check_integer(gen_ctx, sub_int, errors);
+            }
+        },
+        TD_Integer::Add( terms ) => {
+            for (_,sub_int) in terms {
+                check_integer(gen_ctx, sub_int, errors);
+            }
+        },
+        TD_Integer::Reference( var_ref ) => {
+            check_reference(gen_ctx, var_ref, TD_DataType::Integer, errors);
+        }
+    }
+}
+
+fn check_float(gen_ctx : &GeneralContext, td_float : &TD_Float, errors : &mut Vec<String>) {
+    match td_float {
+        TD_Float::Value(_) => {},
+        TD_Float::Minus( sub_float ) => {
+            check_float(gen_ctx, sub_float, errors);
+        },
+        TD_Float::Factor( factors ) => {
+            for (_,sub_float) in factors {
+                check_float(gen_ctx, sub_float, errors);
+            }
+        },
+        TD_Float::Add( terms ) => {
+            for (_,sub_float) in terms {
+                check_float(gen_ctx, sub_float, errors);
+            }
+        },
+        TD_Float::Reference( var_ref ) => {
+            check_reference(gen_ctx, var_ref, TD_DataType::Float, errors);
+        }
+    }
+}
+
+fn check_reference(gen_ctx : &GeneralContext, var_ref : &VariableReference, expected : TD_DataType, errors : &mut Vec<String>) {
+    match var_ref {
+        VariableReference::VARIABLE( vr_id ) => {
+            let vr_type = gen_ctx.get_vr_type(*vr_id).unwrap();
+            if vr_type != expected {
+                errors.push( format!("variable '{}' of type {:?} is used where a term of type {:?} is expected", gen_ctx.get_vr_name(*vr_id).unwrap(), vr_type, expected) );
+            }
+        },
+        VariableReference::MSG_PARAMETER( ms_id, pr_id ) => {
+            let pr_type = gen_ctx.get_pr_type(*ms_id,*pr_id).unwrap();
+            if pr_type != expected {
+                errors.push( format!("parameter {} of message '{}' of type {:?} is used where a term of type {:?} is expected", pr_id, gen_ctx.get_ms_name(*ms_id).unwrap(), pr_type, expected) );
+            }
+        },
+        // symbols only appear once the model is being executed
+        VariableReference::SYMBOL(_) => {}
+    }
+}
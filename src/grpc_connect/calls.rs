@@ -23,6 +23,7 @@ use crate::core::syntax::data::generic::TD_Generic;
 use crate::core::syntax::data::builtin::bool::TD_Bool;
 use crate::grpc_connect::to_grpc::{td_generic_to_grpc,td_bool_to_grpc};
 use crate::grpc_connect::from_grpc::expression_from_grpc;
+use crate::core::syntax::data::simplification::simplify_bool;
 use crate::grpc_connect::xlia_reference_name_tools::{action_diversity_fqn,variable_diversity_fqn,open_scopes_action_diversity_fqn};
 
 
@@ -109,14 +110,14 @@ pub async fn symbex_fire_action(    gen_ctx : &GeneralContext,
         // ***
         let path_condition_expr = eval_machine_reply.path_condition.unwrap();
         let pc_td_gen = expression_from_grpc(gen_ctx,exe_ctx,&path_condition_expr, &TD_DataType::Bool).unwrap();
-        let path_condition : TD_Bool = pc_td_gen.as_td_bool();
+        let path_condition : TD_Bool = simplify_bool( &pc_td_gen.as_td_bool() );
         println!("DIVERSITY provided the path condition '{:?}'\nin the new context {:?}...", &path_condition,&new_diversity_ec_id);
         exe_ctx.set_path_condition(path_condition);
         println!("...updated in HIBOU context");
         // ***
         let firing_condition_expr = eval_machine_reply.other_condition.unwrap();
         let fc_td_gen = expression_from_grpc(gen_ctx,exe_ctx,&firing_condition_expr, &TD_DataType::Bool).unwrap();
-        let firing_condition : TD_Bool = fc_td_gen.as_td_bool();
+        let firing_condition : TD_Bool = simplify_bool( &fc_td_gen.as_td_bool() );
         // ***
         let symbex_success = SymbexResultSuccess{new_diversity_ec_id,firing_condition};
         return SymbexResult::Success(symbex_success);
@@ -20,6 +20,7 @@ use crate::core::syntax::data::builtin::number::*;
 use crate::core::syntax::data::builtin::integer::TD_Integer;
 use crate::core::syntax::data::builtin::float::TD_Float;
 use crate::core::syntax::data::builtin::string::TD_String;
+use crate::core::syntax::data::simplification::simplify_bool;
 
 use crate::diversity::*;
 use crate::diversity::expression::ExpressionAlt;
@@ -188,6 +189,10 @@ fn td_string_to_grpc(gen_ctx : &GeneralContext, exe_ctx : &ExecutionContext, lf_
 
 
 pub fn td_bool_to_grpc(gen_ctx : &GeneralContext, exe_ctx : &ExecutionContext, lf_id : usize, td_bool : &TD_Bool) -> Expression {
+    return simplified_td_bool_to_grpc(gen_ctx,exe_ctx,lf_id,&simplify_bool(td_bool));
+}
+
+fn simplified_td_bool_to_grpc(gen_ctx : &GeneralContext, exe_ctx : &ExecutionContext, lf_id : usize, td_bool : &TD_Bool) -> Expression {
     match td_bool {
         TD_Bool::TRUE => {
             let expr_kind = ExpressionAlt::RawBool(true);
@@ -200,7 +205,7 @@ pub fn td_bool_to_grpc(gen_ctx : &GeneralContext, exe_ctx : &ExecutionContext, l
         TD_Bool::AND(sub_bools) => {
             let mut sub_exprs : Vec<Expression> = Vec::new();
             for sub_bool in sub_bools {
-                sub_exprs.push( td_bool_to_grpc(gen_ctx,exe_ctx,lf_id,sub_bool) );
+                sub_exprs.push( simplified_td_bool_to_grpc(gen_ctx,exe_ctx,lf_id,sub_bool) );
             }
             let operation = Operation{operator_kind: (OperatorKind::And as i32) , operand:sub_exprs};
             let expr_kind = ExpressionAlt::Operation(operation);
@@ -209,14 +214,14 @@ pub fn td_bool_to_grpc(gen_ctx : &GeneralContext, exe_ctx : &ExecutionContext, l
         TD_Bool::OR(sub_bools) => {
             let mut sub_exprs : Vec<Expression> = Vec::new();
             for sub_bool in sub_bools {
-                sub_exprs.push( td_bool_to_grpc(gen_ctx,exe_ctx,lf_id,sub_bool) );
+                sub_exprs.push( simplified_td_bool_to_grpc(gen_ctx,exe_ctx,lf_id,sub_bool) );
             }
             let operation = Operation{operator_kind: (OperatorKind::Or as i32) , operand:sub_exprs};
             let expr_kind = ExpressionAlt::Operation(operation);
             return Expression{expression_alt:Some(expr_kind)};
         },
         TD_Bool::NOT(sub_bool) => {
-            let operation = Operation{operator_kind: (OperatorKind::Not as i32) , operand:vec![ td_bool_to_grpc(gen_ctx,exe_ctx,lf_id,&*sub_bool) ]};
+            let operation = Operation{operator_kind: (OperatorKind::Not as i32) , operand:vec![ simplified_td_bool_to_grpc(gen_ctx,exe_ctx,lf_id,&*sub_bool) ]};
             let expr_kind = ExpressionAlt::Operation(operation);
             return Expression{expression_alt:Some(expr_kind)};
         },
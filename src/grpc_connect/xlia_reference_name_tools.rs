@@ -24,10 +24,17 @@ pub fn action_diversity_fqn(gen_ctx : &GeneralContext, exe_ctx : &ExecutionConte
     return format!("{}.action_{}",lf_name,fold_vec_to_string(relative_position));
 }
 
-pub fn trace_action_compare_diversity_fqn(gen_ctx : &GeneralContext, exe_ctx : &ExecutionContext, lf_id : usize, ms_id : usize) -> String {
+pub fn trace_action_compare_diversity_fqn(gen_ctx : &GeneralContext, exe_ctx : &ExecutionContext, lf_id : usize, ms_id : usize, com_kind : &CommunicationKind) -> String {
     let lf_name = exe_ctx.get_lf_name(gen_ctx,lf_id).unwrap();
     let ms_name= gen_ctx.get_ms_name(ms_id).unwrap();
-    return format!("{}.action_compare_ms_{}",lf_name,ms_name);
+    match com_kind {
+        CommunicationKind::Reply => {
+            return format!("{}.action_compare_rp_{}",lf_name,ms_name);
+        },
+        _ => {
+            return format!("{}.action_compare_ms_{}",lf_name,ms_name);
+        }
+    }
 }
 
 pub fn open_scopes_action_diversity_fqn(gen_ctx : &GeneralContext, lf_id : usize) -> String {
@@ -47,6 +54,35 @@ pub fn trace_message_parameter_diversity_fqn(gen_ctx : &GeneralContext, exe_ctx
     return format!("{}.trace_ms_{}_pr_{}",lf_name,ms_name,pr_id);
 }
 
+/*
+    The value returned by a synchronous call is carried by its reply in variables of its own
+*/
+pub fn action_parameter_diversity_fqn(gen_ctx : &GeneralContext, exe_ctx : &ExecutionContext, lf_id : usize, ms_id : usize, com_kind : &CommunicationKind, pr_id : usize) -> String {
+    match com_kind {
+        CommunicationKind::Reply => {
+            let lf_name = exe_ctx.get_lf_name(gen_ctx,lf_id).unwrap();
+            let ms_name= gen_ctx.get_ms_name(ms_id).unwrap();
+            return format!("{}.rp_{}_val",lf_name,ms_name);
+        },
+        _ => {
+            return message_parameter_diversity_fqn(gen_ctx,exe_ctx,lf_id,ms_id,pr_id);
+        }
+    }
+}
+
+pub fn trace_action_parameter_diversity_fqn(gen_ctx : &GeneralContext, exe_ctx : &ExecutionContext, lf_id : usize, ms_id : usize, com_kind : &CommunicationKind, pr_id : usize) -> String {
+    match com_kind {
+        CommunicationKind::Reply => {
+            let lf_name = exe_ctx.get_lf_name(gen_ctx,lf_id).unwrap();
+            let ms_name= gen_ctx.get_ms_name(ms_id).unwrap();
+            return format!("{}.trace_rp_{}_val",lf_name,ms_name);
+        },
+        _ => {
+            return trace_message_parameter_diversity_fqn(gen_ctx,exe_ctx,lf_id,ms_id,pr_id);
+        }
+    }
+}
+
 pub fn trace_delay_diversity_fqn(gen_ctx : &GeneralContext, exe_ctx : &ExecutionContext, lf_id : usize) -> String {
     let lf_name = exe_ctx.get_lf_name(gen_ctx,lf_id).unwrap();
     return format!("{}.trace_delay",lf_name);
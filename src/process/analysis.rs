@@ -28,7 +28,7 @@ use crate::core::syntax::data::builtin::bool::TD_Bool;
 use crate::core::syntax::data::generic::TD_Generic;
 
 use crate::core::trace::*;
-use crate::core::semantics::frontier::make_frontier;
+use crate::core::semantics::frontier::make_frontier_in_context;
 use crate::core::semantics::shape_execute::shape_execute;
 
 use crate::process::verdicts::*;
@@ -37,6 +37,8 @@ use crate::process::hibou_process::*;
 use crate::process::process_manager::*;
 use crate::process::deploy_receptions::deploy_original_action_followup;
 use crate::process::queue::ProcessQueue;
+use crate::process::checkpoint::{CheckpointOptions,ProcessCheckpoint};
+use crate::core::error::HibouCoreError;
 
 use crate::xlia::model::generate_xlia_model;
 
@@ -54,118 +56,256 @@ pub async fn analyze(interaction : Interaction,
                gen_ctx : GeneralContext,
                exe_ctx : ExecutionContext,
                temporality : HibouProcessTemporality,
+               engine : HibouProcessEngine,
                pre_filters : Vec<HibouPreFilter>,
                strategy : HibouSearchStrategy,
                frontier_priorities : ProcessPriorities,
                loggers : Vec<Box<dyn ProcessLogger>>,
-               goal:GlobalVerdict) -> GlobalVerdict {
+               goal:GlobalVerdict,
+               checkpoint_options : Option<CheckpointOptions>,
+               resume_from : Option<ProcessCheckpoint>) -> Result<GlobalVerdict,HibouCoreError> {
     // ***
     let mut first_context = exe_ctx;
-    let xlia_model_string = generate_xlia_model(&gen_ctx,&first_context,&interaction, &temporality);
-    let model_file_path = "xlia_model.xlia".to_string();
-    let mut file = File::create(&model_file_path).unwrap();
-    file.write( xlia_model_string.as_bytes() );
-    println!("generated xlia model :\n{}",xlia_model_string);
-    // ***
-    let (mut client,mut initial_div_ec_id) = symbex_init_model(&gen_ctx,&mut first_context,xlia_model_string).await;
-    // ***
-    initial_div_ec_id = symbex_fire_lifeline_initializations(&mut client, &gen_ctx,&mut first_context,initial_div_ec_id).await;
+    let mut client : Option<SymbexClient<tonic::transport::Channel>>;
+    let initial_div_ec_id : u32;
+    match &engine {
+        HibouProcessEngine::Symbolic => {
+            let xlia_model_string = generate_xlia_model(&gen_ctx,&first_context,&interaction, &temporality);
+            let model_file_path = "xlia_model.xlia".to_string();
+            let mut file = File::create(&model_file_path).unwrap();
+            file.write( xlia_model_string.as_bytes() );
+            println!("generated xlia model :\n{}",xlia_model_string);
+            // ***
+            let (mut got_client,got_div_ec_id) = symbex_init_model(&gen_ctx,&mut first_context,xlia_model_string).await;
+            // ***
+            initial_div_ec_id = symbex_fire_lifeline_initializations(&mut got_client, &gen_ctx,&mut first_context,got_div_ec_id).await;
+            client = Some(got_client);
+        },
+        HibouProcessEngine::Concrete => {
+            // no DIVERSITY execution context is ever used
+            initial_div_ec_id = 0;
+            client = None;
+        }
+    }
     // ***
     let mut manager = HibouProcessManager::new(gen_ctx,
                                                strategy,
                                                temporality,
+                                               engine,
                                                pre_filters,
                                                HashMap::new(),
                                                ProcessQueue::new(),
                                                frontier_priorities,
                                                loggers);
     // ***
-    let multi_trace_option = Some(multi_trace);
-    manager.init_loggers(&first_context,&interaction,&multi_trace_option);
-    let multi_trace = multi_trace_option.unwrap();
-    // ***
-    let mut next_state_id : u32 = 1;
-    let mut node_counter : u32 = 0;
-    let mut global_verdict = GlobalVerdict::Fail;
-    // ***
-    match enqueue_next_node_in_analysis(&mut manager,
-                                        next_state_id,
-                                        initial_div_ec_id,
-                                        first_context,
-                                        interaction,
-                                        multi_trace,0,0) {
-        None => {},
-        Some( coverage_verdict ) => {
-            global_verdict = update_global_verdict_from_new_coverage_verdict(global_verdict, coverage_verdict);
-        }
-    }
-    next_state_id = next_state_id +1;
-    node_counter = node_counter +1;
+    let mut next_state_id : u32;
+    let mut node_counter : u32;
+    let mut global_verdict : GlobalVerdict;
     // ***
-    if global_verdict < goal {
-        while let Some(next_to_process) = manager.extract_from_queue() {
-            let new_state_id = next_state_id;
-            next_state_id = next_state_id + 1;
+    match resume_from {
+        None => {
+            manager.init_loggers(&first_context,&interaction,&Some(multi_trace.clone()));
             // ***
-            let mut parent_state = manager.get_memorized_state(next_to_process.state_id).unwrap().clone();
+            next_state_id = 1;
+            node_counter = 0;
+            global_verdict = GlobalVerdict::Fail;
             // ***
-            match manager.process_next(&mut client,
-                                       &parent_state,
-                                       &next_to_process,
-                                       new_state_id,
-                                       node_counter).await {
+            match enqueue_next_node_in_analysis(&mut manager,
+                                                &global_verdict,
+                                                next_state_id,
+                                                initial_div_ec_id,
+                                                first_context.clone(),
+                                                interaction.clone(),
+                                                multi_trace.clone(),
+                                                Vec::new(),0,0) {
                 None => {},
-                Some( (new_interaction,new_exe_ctx,new_div_ec_id,new_multi_trace,new_depth,new_loop_depth) ) => {
-                    node_counter = node_counter + 1;
-                    match enqueue_next_node_in_analysis(&mut manager,
-                                                        new_state_id,
-                                                        new_div_ec_id,
-                                                        new_exe_ctx,
-                                                        new_interaction,
-                                                        new_multi_trace.unwrap(),
-                                                        new_depth,
-                                                        new_loop_depth) {
-                        None => {},
-                        Some( coverage_verdict ) => {
-                            global_verdict = update_global_verdict_from_new_coverage_verdict(global_verdict, coverage_verdict);
-                            if global_verdict >= goal {
-                                break;
+                Some( coverage_verdict ) => {
+                    global_verdict = update_global_verdict_from_new_coverage_verdict(global_verdict, coverage_verdict);
+                }
+            }
+            next_state_id = next_state_id +1;
+            node_counter = node_counter +1;
+        },
+        Some( checkpoint ) => {
+            match manager.resume_loggers(&first_context,&interaction,&Some(multi_trace.clone()),&checkpoint.loggers) {
+                Err(e) => {
+                    return Err(e);
+                },
+                Ok(_) => {}
+            }
+            manager.restore_from_checkpoint(&mut client,
+                                            &interaction,
+                                            &first_context,
+                                            initial_div_ec_id,
+                                            &Some(multi_trace.clone()),
+                                            &checkpoint).await?;
+            next_state_id = checkpoint.next_state_id;
+            node_counter = checkpoint.node_counter;
+            global_verdict = checkpoint.global_verdict.unwrap_or(GlobalVerdict::Fail);
+        }
+    }
+    // ***
+    let mut processed_since_checkpoint : u32 = 0;
+    // ***
+    // a path leading to a NegFail or an AssertFail may remain to be found once the goal is reached
+    let may_fail_after_goal = interaction.contains_neg_or_assert();
+    let may_neg_fail = interaction.contains_neg();
+    if !is_analysis_over(&global_verdict,&goal,may_fail_after_goal,may_neg_fail) {
+        loop {
+            while let Some(next_to_process) = manager.extract_from_queue() {
+                let new_state_id = next_state_id;
+                next_state_id = next_state_id + 1;
+                // ***
+                let mut parent_state = manager.get_memorized_state(next_to_process.state_id).unwrap().clone();
+                // ***
+                match manager.process_next(&mut client,
+                                           &parent_state,
+                                           &next_to_process,
+                                           new_state_id,
+                                           node_counter).await? {
+                    None => {},
+                    Some( (new_interaction,new_exe_ctx,new_div_ec_id,new_multi_trace,new_depth,new_loop_depth) ) => {
+                        node_counter = node_counter + 1;
+                        manager.abandon_siblings_after_step(next_to_process.state_id,&mut parent_state);
+                        let mut new_path = parent_state.path.clone();
+                        match &next_to_process.kind {
+                            NextToProcessKind::Execute( ref position ) => {
+                                new_path.push( position.clone() );
+                            }
+                        }
+                        match enqueue_next_node_in_analysis(&mut manager,
+                                                            &global_verdict,
+                                                            new_state_id,
+                                                            new_div_ec_id,
+                                                            new_exe_ctx,
+                                                            new_interaction,
+                                                            new_multi_trace.unwrap(),
+                                                            new_path,
+                                                            new_depth,
+                                                            new_loop_depth) {
+                            None => {},
+                            Some( coverage_verdict ) => {
+                                global_verdict = update_global_verdict_from_new_coverage_verdict(global_verdict, coverage_verdict);
+                                if is_analysis_over(&global_verdict,&goal,may_fail_after_goal,may_neg_fail) {
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                }
+                // ***
+                parent_state.remaining_ids_to_process.remove(&next_to_process.id_as_child);
+                if parent_state.remaining_ids_to_process.len() == 0 {
+                    manager.forget_state(next_to_process.state_id);
+                } else {
+                    manager.remember_state(next_to_process.state_id,parent_state);
+                }
+                // ***
+                match &checkpoint_options {
+                    None => {},
+                    Some( ckpt_opts ) => {
+                        processed_since_checkpoint = processed_since_checkpoint + 1;
+                        if processed_since_checkpoint >= ckpt_opts.period {
+                            match manager.make_checkpoint(next_state_id,node_counter,Some(global_verdict.clone())).write_to_file(&ckpt_opts.file_path) {
+                                Err(e) => {
+                                    // the process goes on and the previous checkpoint, if any, can still be used to resume it
+                                    println!("could not write checkpoint : {}", e);
+                                },
+                                Ok(_) => {}
                             }
+                            processed_since_checkpoint = 0;
                         }
                     }
                 }
+                // ***
             }
             // ***
-            parent_state.remaining_ids_to_process.remove(&next_to_process.id_as_child);
-            if parent_state.remaining_ids_to_process.len() == 0 {
-                manager.forget_state(next_to_process.state_id);
-            } else {
-                manager.remember_state(next_to_process.state_id,parent_state);
+            if is_analysis_over(&global_verdict,&goal,may_fail_after_goal,may_neg_fail) || !manager.start_next_iteration() {
+                break;
+            }
+            // the initial state always has the id 1
+            match enqueue_next_node_in_analysis(&mut manager,
+                                                &global_verdict,
+                                                1,
+                                                initial_div_ec_id,
+                                                first_context.clone(),
+                                                interaction.clone(),
+                                                multi_trace.clone(),
+                                                Vec::new(),0,0) {
+                None => {},
+                Some( coverage_verdict ) => {
+                    global_verdict = update_global_verdict_from_new_coverage_verdict(global_verdict, coverage_verdict);
+                }
             }
-            // ***
         }
     }
     // ***
-    symbex_post_process(&mut client).await;
+    match client.as_mut() {
+        None => {},
+        Some( got_client ) => {
+            symbex_post_process(got_client).await;
+        }
+    }
     // ***
     manager.term_loggers(Some((&goal,&global_verdict)) );
     // ***
-    return global_verdict;
+    match &checkpoint_options {
+        None => {},
+        Some( ckpt_opts ) => {
+            // the process terminated normally so there is nothing to resume
+            // the result is ignored because no checkpoint is written if the process ends before a full period
+            let _ = fs::remove_file(&ckpt_opts.file_path);
+        }
+    }
+    // ***
+    return Ok(global_verdict);
+}
+
+/*
+    The analysis ends on a definitive verdict, or once the goal is reached
+    provided no neg or assert fragment can overturn it afterwards.
+    Once an AssertFail is found only a NegFail can overturn it
+    so the analysis also ends if the interaction contains no neg fragment
+*/
+fn is_analysis_over(global_verdict : &GlobalVerdict,
+                    goal : &GlobalVerdict,
+                    may_fail_after_goal : bool,
+                    may_neg_fail : bool) -> bool {
+    if global_verdict.is_definitive() {
+        return true;
+    }
+    if global_verdict == &GlobalVerdict::AssertFail && !may_neg_fail {
+        return true;
+    }
+    if may_fail_after_goal {
+        return false;
+    }
+    return global_verdict >= goal;
 }
 
 fn enqueue_next_node_in_analysis(manager     : &mut HibouProcessManager,
+                                 global_verdict : &GlobalVerdict,
                                  state_id    : u32,
                                  diversity_ec_id : u32,
                                  exe_ctx : ExecutionContext,
                                  interaction : Interaction,
                                  multi_trace : AnalysableMultiTrace,
+                                 path        : Vec<Position>,
                                  depth       : u32,
                                  loop_depth  : u32) -> Option<CoverageVerdict> {
     // ***
+    if global_verdict == &GlobalVerdict::AssertFail && !interaction.contains_neg() {
+        // only a NegFail can overturn an AssertFail and it cannot be reached from this state
+        manager.forget_state(state_id);
+        return None;
+    }
+    // ***
     let mut next_child_id : u32 = 0;
     // ***
     let mut to_enqueue : Vec<(u32,NextToProcessKind)> = Vec::new();
-    for front_pos in make_frontier(&interaction) {
+    // once a forbidden behaviour has been exhibited there is no need to go further
+    let frontier = if interaction.exhibits_negative() {Vec::new()} else {make_frontier_in_context(&interaction,&exe_ctx)};
+    for front_pos in frontier {
         let front_act = interaction.get_sub_interaction(&front_pos).as_leaf();
         for canal in &multi_trace.canals {
             if canal.trace.len() > 0 {
@@ -187,6 +327,7 @@ fn enqueue_next_node_in_analysis(manager     : &mut HibouProcessManager,
                                              diversity_ec_id,
                                              Some(multi_trace),
                                              rem_child_ids,
+                                             path,
                                              loop_depth, depth);
         manager.remember_state( state_id, memo_state );
         manager.enqueue_executions(state_id,to_enqueue);
@@ -194,6 +335,7 @@ fn enqueue_next_node_in_analysis(manager     : &mut HibouProcessManager,
     } else {
         let verdict = manager.get_coverage_verdict(&interaction,&multi_trace);
         manager.verdict_loggers(&verdict,state_id);
+        manager.forget_state(state_id);
         return Some( verdict );
     }
 }
@@ -0,0 +1,366 @@
+/********************************************************************************
+ * Copyright (c) 2020 Erwan Mahe (github.com/erwanM974)
+ *
+ * This program and the accompanying materials are made available under the
+ * terms of the Eclipse Public License 2.0 which is available at
+ * http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+ * which is available at https://www.apache.org/licenses/LICENSE-2.0.
+ *
+ * SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+ ********************************************************************************/
+
+
+use std::fmt;
+use std::fs;
+use std::collections::HashSet;
+
+use crate::core::syntax::position::Position;
+use crate::process::hibou_process::{NextToProcess,NextToProcessKind,SearchProgress};
+use crate::process::verdicts::GlobalVerdict;
+
+
+pub struct CheckpointOptions {
+    pub file_path : String,
+    pub period : u32 // number of processed nodes between two checkpoints
+}
+
+impl CheckpointOptions {
+    pub fn new(file_path : String, period : u32) -> CheckpointOptions {
+        return CheckpointOptions{file_path,period};
+    }
+}
+
+pub struct CheckpointedState {
+    pub state_id : u32,
+    pub path : Vec<Position>,
+    pub remaining_ids_to_process : HashSet<u32>,
+    pub loop_depth : u32,
+    pub depth : u32
+}
+
+pub struct ProcessCheckpoint {
+    pub next_state_id : u32,
+    pub node_counter : u32,
+    pub global_verdict : Option<GlobalVerdict>,
+    pub search_progress : SearchProgress,
+    pub elapsed_millis : u64, // time spent by the process, against the 'max_time' filter
+    pub solver_calls : u32,   // against the 'max_solver_calls' filter
+    pub states : Vec<CheckpointedState>,
+    pub queue : Vec<NextToProcess>,
+    pub loggers : Vec<Vec<String>> // the state saved by each logger, in the order in which the loggers are declared
+}
+
+#[derive(Debug)]
+pub enum CheckpointError {
+    FileError(String),
+    MalformedCheckpoint(String)
+}
+
+impl fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CheckpointError::FileError(sub_e) => {
+                return write!(f, "{}", format!("error while accessing checkpoint file : {:}", sub_e));
+            },
+            CheckpointError::MalformedCheckpoint(sub_e) => {
+                return write!(f, "{}", format!("malformed checkpoint : {:}", sub_e));
+            }
+        }
+    }
+}
+
+static CHECKPOINT_EMPTY_ITEM : &'static str = "-";
+
+fn u32_list_to_text(ids : &Vec<u32>) -> String {
+    if ids.len() == 0 {
+        return CHECKPOINT_EMPTY_ITEM.to_string();
+    }
+    let as_strs : Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+    return as_strs.join(",");
+}
+
+fn path_to_text(path : &Vec<Position>) -> String {
+    if path.len() == 0 {
+        return CHECKPOINT_EMPTY_ITEM.to_string();
+    }
+    let as_strs : Vec<String> = path.iter().map(|pos| pos.as_text()).collect();
+    return as_strs.join(",");
+}
+
+fn parse_u32(item : &str) -> Result<u32,CheckpointError> {
+    match item.parse::<u32>() {
+        Err(_) => {
+            return Err( CheckpointError::MalformedCheckpoint(format!("expected integer and got '{}'", item)) );
+        },
+        Ok( got ) => {
+            return Ok(got);
+        }
+    }
+}
+
+fn parse_u64(item : &str) -> Result<u64,CheckpointError> {
+    match item.parse::<u64>() {
+        Err(_) => {
+            return Err( CheckpointError::MalformedCheckpoint(format!("expected integer and got '{}'", item)) );
+        },
+        Ok( got ) => {
+            return Ok(got);
+        }
+    }
+}
+
+fn parse_bool(item : &str) -> Result<bool,CheckpointError> {
+    match item {
+        "0" => {
+            return Ok(false);
+        },
+        "1" => {
+            return Ok(true);
+        },
+        _ => {
+            return Err( CheckpointError::MalformedCheckpoint(format!("expected 0 or 1 and got '{}'", item)) );
+        }
+    }
+}
+
+fn parse_position(item : &str) -> Result<Position,CheckpointError> {
+    match Position::from_text(item) {
+        None => {
+            return Err( CheckpointError::MalformedCheckpoint(format!("expected position and got '{}'", item)) );
+        },
+        Some( pos ) => {
+            return Ok(pos);
+        }
+    }
+}
+
+fn parse_global_verdict(item : &str) -> Result<Option<GlobalVerdict>,CheckpointError> {
+    if item == CHECKPOINT_EMPTY_ITEM {
+        return Ok(None);
+    }
+    for verdict in vec![GlobalVerdict::NegFail,GlobalVerdict::AssertFail,GlobalVerdict::Fail,GlobalVerdict::Inconc,GlobalVerdict::WeakPass,GlobalVerdict::Pass] {
+        if verdict.to_string() == item {
+            return Ok( Some(verdict) );
+        }
+    }
+    return Err( CheckpointError::MalformedCheckpoint(format!("unknown verdict '{}'", item)) );
+}
+
+/*
+    The loggers which save their state in checkpoints identify the actions of the initial interaction
+    by their original positions, saved as the following items
+*/
+static ORIGINAL_POSITION_PREFIX : &'static str = "p";
+
+pub fn parse_original_position(item : &str) -> Result<Vec<u32>,String> {
+    let mut orig_pos : Vec<u32> = Vec::new();
+    if !item.starts_with(ORIGINAL_POSITION_PREFIX) {
+        return Err( format!("expected original position and got '{}'", item) );
+    }
+    let indices = &item[ORIGINAL_POSITION_PREFIX.len()..];
+    if indices.len() > 0 {
+        for idx_str in indices.split(",") {
+            match idx_str.parse::<u32>() {
+                Err(_) => {
+                    return Err( format!("expected original position and got '{}'", item) );
+                },
+                Ok( idx ) => {
+                    orig_pos.push( idx );
+                }
+            }
+        }
+    }
+    return Ok( orig_pos );
+}
+
+pub fn original_position_to_checkpoint_item(orig_pos : &Vec<u32>) -> String {
+    let pos_strs : Vec<String> = orig_pos.iter().map(|idx| idx.to_string()).collect();
+    return format!("{}{}", ORIGINAL_POSITION_PREFIX, pos_strs.join(","));
+}
+
+impl ProcessCheckpoint {
+
+    /*
+        One item per line :
+        - "counters <next_state_id> <node_counter> <global_verdict>"
+        - "search <depth_limit> <depth_limit_hit> <rng_draws> <completed_walks>"
+        - "budget <elapsed_millis> <solver_calls>"
+        - "state <state_id> <depth> <loop_depth> <remaining_ids_to_process> <path>"
+        - "queue <state_id> <id_as_child> <position>"
+        - "logger <logger_index> <line>" for each line of the state saved by a logger
+        in which lists are comma-separated and empty lists or absent verdicts are noted "-"
+    */
+    pub fn to_text(&self) -> String {
+        let mut my_str = String::new();
+        let verdict_str : String;
+        match &self.global_verdict {
+            None => {
+                verdict_str = CHECKPOINT_EMPTY_ITEM.to_string();
+            },
+            Some( verdict ) => {
+                verdict_str = verdict.to_string();
+            }
+        }
+        my_str.push_str( &format!("counters {} {} {}\n", self.next_state_id, self.node_counter, verdict_str) );
+        let depth_limit_str : String;
+        match &self.search_progress.depth_limit {
+            None => {
+                depth_limit_str = CHECKPOINT_EMPTY_ITEM.to_string();
+            },
+            Some( depth_limit ) => {
+                depth_limit_str = depth_limit.to_string();
+            }
+        }
+        my_str.push_str( &format!("search {} {} {} {}\n",
+                                  depth_limit_str,
+                                  self.search_progress.depth_limit_hit as u32,
+                                  self.search_progress.rng_draws,
+                                  self.search_progress.completed_walks) );
+        my_str.push_str( &format!("budget {} {}\n", self.elapsed_millis, self.solver_calls) );
+        for state in &self.states {
+            let mut remaining : Vec<u32> = state.remaining_ids_to_process.iter().cloned().collect();
+            remaining.sort();
+            my_str.push_str( &format!("state {} {} {} {} {}\n",
+                                      state.state_id,
+                                      state.depth,
+                                      state.loop_depth,
+                                      u32_list_to_text(&remaining),
+                                      path_to_text(&state.path)) );
+        }
+        for to_process in &self.queue {
+            match &to_process.kind {
+                NextToProcessKind::Execute( ref position ) => {
+                    my_str.push_str( &format!("queue {} {} {}\n",
+                                              to_process.state_id,
+                                              to_process.id_as_child,
+                                              position.as_text()) );
+                }
+            }
+        }
+        for (logger_index,logger_lines) in self.loggers.iter().enumerate() {
+            for logger_line in logger_lines {
+                my_str.push_str( &format!("logger {} {}\n", logger_index, logger_line) );
+            }
+        }
+        return my_str;
+    }
+
+    pub fn from_text(ckpt_str : &str) -> Result<ProcessCheckpoint,CheckpointError> {
+        let mut counters : Option<(u32,u32,Option<GlobalVerdict>)> = None;
+        let mut search_progress : Option<SearchProgress> = None;
+        let mut budget : Option<(u64,u32)> = None;
+        let mut states : Vec<CheckpointedState> = Vec::new();
+        let mut queue : Vec<NextToProcess> = Vec::new();
+        let mut loggers : Vec<Vec<String>> = Vec::new();
+        for line in ckpt_str.lines() {
+            // the state saved by a logger is free text and is thus not split on whitespaces
+            if line.starts_with("logger ") {
+                let logger_items : Vec<&str> = line.splitn(3,' ').collect();
+                if logger_items.len() != 3 {
+                    return Err( CheckpointError::MalformedCheckpoint(format!("unexpected line '{}'", line)) );
+                }
+                let logger_index = parse_u32(logger_items[1])? as usize;
+                while loggers.len() <= logger_index {
+                    loggers.push( Vec::new() );
+                }
+                loggers[logger_index].push( logger_items[2].to_string() );
+                continue;
+            }
+            let items : Vec<&str> = line.split_whitespace().collect();
+            if items.len() == 0 {
+                continue;
+            }
+            match (items[0],items.len()) {
+                ("counters",4) => {
+                    counters = Some( (parse_u32(items[1])?, parse_u32(items[2])?, parse_global_verdict(items[3])?) );
+                },
+                ("search",5) => {
+                    let depth_limit : Option<u32>;
+                    if items[1] == CHECKPOINT_EMPTY_ITEM {
+                        depth_limit = None;
+                    } else {
+                        depth_limit = Some( parse_u32(items[1])? );
+                    }
+                    search_progress = Some( SearchProgress{depth_limit,
+                        depth_limit_hit:parse_bool(items[2])?,
+                        rng_draws:parse_u64(items[3])?,
+                        completed_walks:parse_u32(items[4])?} );
+                },
+                ("budget",3) => {
+                    budget = Some( (parse_u64(items[1])?, parse_u32(items[2])?) );
+                },
+                ("state",6) => {
+                    let mut remaining_ids_to_process : HashSet<u32> = HashSet::new();
+                    if items[4] != CHECKPOINT_EMPTY_ITEM {
+                        for id_str in items[4].split(",") {
+                            remaining_ids_to_process.insert( parse_u32(id_str)? );
+                        }
+                    }
+                    let mut path : Vec<Position> = Vec::new();
+                    if items[5] != CHECKPOINT_EMPTY_ITEM {
+                        for pos_str in items[5].split(",") {
+                            path.push( parse_position(pos_str)? );
+                        }
+                    }
+                    states.push( CheckpointedState{state_id:parse_u32(items[1])?,
+                        path,
+                        remaining_ids_to_process,
+                        loop_depth:parse_u32(items[3])?,
+                        depth:parse_u32(items[2])?} );
+                },
+                ("queue",4) => {
+                    let kind = NextToProcessKind::Execute( parse_position(items[3])? );
+                    queue.push( NextToProcess::new(parse_u32(items[1])?, parse_u32(items[2])?, kind) );
+                },
+                _ => {
+                    return Err( CheckpointError::MalformedCheckpoint(format!("unexpected line '{}'", line)) );
+                }
+            }
+        }
+        match (counters,search_progress,budget) {
+            (None,_,_) => {
+                return Err( CheckpointError::MalformedCheckpoint("missing counters".to_string()) );
+            },
+            (_,None,_) => {
+                return Err( CheckpointError::MalformedCheckpoint("missing search progress".to_string()) );
+            },
+            (_,_,None) => {
+                return Err( CheckpointError::MalformedCheckpoint("missing budget".to_string()) );
+            },
+            (Some( (next_state_id,node_counter,global_verdict) ),Some(search_progress),Some( (elapsed_millis,solver_calls) )) => {
+                return Ok( ProcessCheckpoint{next_state_id,node_counter,global_verdict,search_progress,elapsed_millis,solver_calls,states,queue,loggers} );
+            }
+        }
+    }
+
+    pub fn write_to_file(&self, file_path : &str) -> Result<(),CheckpointError> {
+        // written in a temporary file first so that a crash during writing does not corrupt the last checkpoint
+        let temp_file_path = format!("{}.tmp", file_path);
+        match fs::write(&temp_file_path, self.to_text()) {
+            Err(e) => {
+                return Err( CheckpointError::FileError(e.to_string()) );
+            },
+            Ok(_) => {}
+        }
+        match fs::rename(&temp_file_path, file_path) {
+            Err(e) => {
+                return Err( CheckpointError::FileError(e.to_string()) );
+            },
+            Ok(_) => {
+                return Ok(());
+            }
+        }
+    }
+
+    pub fn read_from_file(file_path : &str) -> Result<ProcessCheckpoint,CheckpointError> {
+        match fs::read_to_string(file_path) {
+            Err(e) => {
+                return Err( CheckpointError::FileError(e.to_string()) );
+            },
+            Ok( ckpt_str ) => {
+                return ProcessCheckpoint::from_text(&ckpt_str);
+            }
+        }
+    }
+
+}
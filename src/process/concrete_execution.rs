@@ -0,0 +1,329 @@
+/********************************************************************************
+ * Copyright (c) 2020 Erwan Mahe (github.com/erwanM974)
+ *
+ * This program and the accompanying materials are made available under the
+ * terms of the Eclipse Public License 2.0 which is available at
+ * http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+ * which is available at https://www.apache.org/licenses/LICENSE-2.0.
+ *
+ * SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+ ********************************************************************************/
+
+use std::cmp::Ordering;
+use std::collections::btree_map::BTreeMap;
+
+use crate::core::context::general::GeneralContext;
+use crate::core::context::execution::ExecutionContext;
+use crate::core::error::HibouCoreError;
+use crate::core::syntax::action::*;
+use crate::core::trace::TraceAction;
+use crate::core::syntax::data::td_type::TD_DataType;
+use crate::core::syntax::data::generic::TD_Generic;
+use crate::core::syntax::data::var_ref::VariableReference;
+use crate::core::syntax::data::builtin::bool::TD_Bool;
+use crate::core::syntax::data::builtin::integer::TD_Integer;
+use crate::core::syntax::data::builtin::float::TD_Float;
+use crate::core::syntax::data::builtin::string::TD_String;
+use crate::core::syntax::data::evaluation::*;
+
+
+pub enum ConcreteExecutionResult {
+    Sat(Vec<TD_Generic>), // effective parameters
+    UnSat
+}
+
+/*
+    Executes an action without DIVERSITY
+    by concretely evaluating its guards, assignments and parameters on the lifeline's interpretation
+    Fresh values '#' are represented by symbols which can only be resolved
+    by the arguments of the trace action against which the action is executed
+*/
+pub fn concrete_execution(gen_ctx : &GeneralContext,
+                          exe_ctx : &mut ExecutionContext,
+                          model_action : &ObservableAction,
+                          trace_action : Option<&TraceAction>) -> Result<ConcreteExecutionResult,HibouCoreError> {
+    let lf_id = model_action.lf_act.lf_id;
+    let mut lf_interpretation : BTreeMap<usize,TD_Generic>;
+    match exe_ctx.get_lf_interpretation(lf_id) {
+        None => {
+            lf_interpretation = BTreeMap::new();
+        },
+        Some( got_lfint ) => {
+            lf_interpretation = got_lfint.clone();
+        }
+    }
+    // ***
+    /*
+        Guards of the preamble are only evaluated once the parameters are matched against the trace
+        given that fresh values may be resolved by doing so
+    */
+    let mut deferred_guards : Vec<(TD_Bool,BTreeMap<usize,TD_Generic>)> = Vec::new();
+    for amble_item in &model_action.lf_act.preamble {
+        match amble_item {
+            ActionAmbleItem::Guard( td_bool ) => {
+                deferred_guards.push( (td_bool.clone(),lf_interpretation.clone()) );
+            },
+            ActionAmbleItem::Assignment( vr_id, value_or_new_fresh ) => {
+                let new_value = assigned_value(gen_ctx,exe_ctx,&lf_interpretation,None,*vr_id,value_or_new_fresh)?;
+                lf_interpretation.insert(*vr_id,new_value);
+            },
+            ActionAmbleItem::Reset( vr_id ) => {
+                lf_interpretation.insert(*vr_id,TD_Generic::Float(TD_Float::Value(0.0)));
+            }
+        }
+    }
+    // ***
+    let mut effective_parameters : Vec<TD_Generic> = Vec::new();
+    let mut pr_id : usize = 0;
+    for param in &model_action.params {
+        let trace_argument : Option<&TD_Generic>;
+        match trace_action {
+            None => {
+                trace_argument = None;
+            },
+            Some( tract ) => {
+                trace_argument = tract.arguments.get(pr_id);
+            }
+        }
+        // ***
+        let effective : TD_Generic;
+        match param {
+            ValueOrNewFresh::NewFresh => {
+                match trace_argument {
+                    None => {
+                        return Err( unresolved_parameter(gen_ctx,model_action,pr_id,"no trace argument to take it from") );
+                    },
+                    Some( arg ) => {
+                        effective = arg.clone();
+                    }
+                }
+            },
+            ValueOrNewFresh::Value( td_gen ) => {
+                let evaluated : Result<TD_Generic,HibouCoreError>;
+                {
+                    let valuation = Valuation::new(&lf_interpretation,exe_ctx.get_symbol_values());
+                    evaluated = evaluate_generic(td_gen,&valuation);
+                }
+                match evaluated {
+                    Ok( got ) => {
+                        effective = got;
+                    },
+                    Err( HibouCoreError::UnboundSymbol(sy_id) ) => {
+                        match (trace_argument,forwarded_symbol(&lf_interpretation,td_gen)) {
+                            (Some(arg),Some(fwd_sy_id)) if fwd_sy_id == sy_id => {
+                                exe_ctx.bind_symbol(sy_id,arg.clone());
+                                effective = arg.clone();
+                            },
+                            (None,_) => {
+                                return Err( unresolved_parameter(gen_ctx,model_action,pr_id,"no trace argument to take it from") );
+                            },
+                            _ => {
+                                return Err( unresolved_parameter(gen_ctx,model_action,pr_id,"it is not directly forwarded by the parameter") );
+                            }
+                        }
+                    },
+                    Err(e) => {
+                        return Err(e);
+                    }
+                }
+            }
+        }
+        // ***
+        match trace_argument {
+            None => {},
+            Some( arg ) => {
+                if !literals_match(&effective,arg) {
+                    return Ok( ConcreteExecutionResult::UnSat );
+                }
+            }
+        }
+        effective_parameters.push(effective);
+        pr_id = pr_id + 1;
+    }
+    // ***
+    for (td_bool,guard_interpretation) in &deferred_guards {
+        let valuation = Valuation::new(guard_interpretation,exe_ctx.get_symbol_values());
+        if !evaluate_guard(gen_ctx,model_action,td_bool,&valuation)? {
+            return Ok( ConcreteExecutionResult::UnSat );
+        }
+    }
+    // ***
+    for amble_item in &model_action.lf_act.postamble {
+        match amble_item {
+            ActionAmbleItem::Guard( td_bool ) => {
+                let valuation = Valuation::with_parameters(&lf_interpretation,exe_ctx.get_symbol_values(),model_action.ms_id,&effective_parameters);
+                if !evaluate_guard(gen_ctx,model_action,td_bool,&valuation)? {
+                    return Ok( ConcreteExecutionResult::UnSat );
+                }
+            },
+            ActionAmbleItem::Assignment( vr_id, value_or_new_fresh ) => {
+                let new_value = assigned_value(gen_ctx,exe_ctx,&lf_interpretation,Some((model_action.ms_id,&effective_parameters)),*vr_id,value_or_new_fresh)?;
+                lf_interpretation.insert(*vr_id,new_value);
+            },
+            ActionAmbleItem::Reset( vr_id ) => {
+                lf_interpretation.insert(*vr_id,TD_Generic::Float(TD_Float::Value(0.0)));
+            }
+        }
+    }
+    // ***
+    exe_ctx.set_lf_interpretation(lf_id,lf_interpretation);
+    return Ok( ConcreteExecutionResult::Sat(effective_parameters) );
+}
+
+/*
+    Value given to a variable by an assignment
+    A fresh value '#' becomes a new unbound symbol
+    Otherwise the value must be concretely known unless it only forwards an unbound symbol
+*/
+fn assigned_value(gen_ctx : &GeneralContext,
+                  exe_ctx : &mut ExecutionContext,
+                  lf_interpretation : &BTreeMap<usize,TD_Generic>,
+                  parameters : Option<(usize,&Vec<TD_Generic>)>, // message id and effective parameters
+                  vr_id : usize,
+                  value_or_new_fresh : &ValueOrNewFresh) -> Result<TD_Generic,HibouCoreError> {
+    match value_or_new_fresh {
+        ValueOrNewFresh::NewFresh => {
+            let vr_type = exe_ctx.get_vr_type(gen_ctx,vr_id)?;
+            let sy_id = exe_ctx.add_fresh_symbol(&vr_type);
+            return Ok( symbol_reference(sy_id,&vr_type) );
+        },
+        ValueOrNewFresh::Value( td_gen ) => {
+            let evaluated : Result<TD_Generic,HibouCoreError>;
+            {
+                let valuation : Valuation;
+                match parameters {
+                    None => {
+                        valuation = Valuation::new(lf_interpretation,exe_ctx.get_symbol_values());
+                    },
+                    Some( (ms_id,params) ) => {
+                        valuation = Valuation::with_parameters(lf_interpretation,exe_ctx.get_symbol_values(),ms_id,params);
+                    }
+                }
+                evaluated = evaluate_generic(td_gen,&valuation);
+            }
+            match evaluated {
+                Ok( got ) => {
+                    return Ok(got);
+                },
+                Err( HibouCoreError::UnboundSymbol(sy_id) ) => {
+                    match forwarded_symbol(lf_interpretation,td_gen) {
+                        Some( fwd_sy_id ) if fwd_sy_id == sy_id => {
+                            // the symbol may be resolved later on
+                            return Ok( symbol_reference(sy_id,&td_gen.get_td_type()) );
+                        },
+                        _ => {
+                            let vr_name = exe_ctx.get_vr_name(gen_ctx,vr_id)?;
+                            return Err( HibouCoreError::UnresolvedFreshValue(format!("in the value assigned to variable '{}'",vr_name)) );
+                        }
+                    }
+                },
+                Err(e) => {
+                    return Err(e);
+                }
+            }
+        }
+    }
+}
+
+fn evaluate_guard(gen_ctx : &GeneralContext,
+                  model_action : &ObservableAction,
+                  td_bool : &TD_Bool,
+                  valuation : &Valuation) -> Result<bool,HibouCoreError> {
+    match evaluate_bool(td_bool,valuation) {
+        Ok( got ) => {
+            return Ok(got);
+        },
+        Err( HibouCoreError::UnboundSymbol(_) ) => {
+            let lf_name = gen_ctx.get_lf_name(model_action.lf_act.lf_id)?;
+            let ms_name = gen_ctx.get_ms_name(model_action.ms_id)?;
+            return Err( HibouCoreError::UnresolvedFreshValue(format!("in a guard of action '{}' on message '{}'",lf_name,ms_name)) );
+        },
+        Err(e) => {
+            return Err(e);
+        }
+    }
+}
+
+fn unresolved_parameter(gen_ctx : &GeneralContext, model_action : &ObservableAction, pr_id : usize, reason : &str) -> HibouCoreError {
+    let lf_name = gen_ctx.get_lf_name(model_action.lf_act.lf_id).unwrap();
+    let ms_name = gen_ctx.get_ms_name(model_action.ms_id).unwrap();
+    return HibouCoreError::UnresolvedFreshValue(format!("in parameter {} of message '{}' on lifeline '{}' ({})",pr_id,ms_name,lf_name,reason));
+}
+
+/*
+    If the term is a mere reference (possibly through variables) to a symbol, returns that symbol
+*/
+fn forwarded_symbol(lf_interpretation : &BTreeMap<usize,TD_Generic>, td_gen : &TD_Generic) -> Option<usize> {
+    let var_ref : &VariableReference;
+    match td_gen {
+        TD_Generic::Bool( TD_Bool::Reference(got_ref) ) => {
+            var_ref = got_ref;
+        },
+        TD_Generic::Integer( TD_Integer::Reference(got_ref) ) => {
+            var_ref = got_ref;
+        },
+        TD_Generic::Float( TD_Float::Reference(got_ref) ) => {
+            var_ref = got_ref;
+        },
+        TD_Generic::String( TD_String::Reference(got_ref) ) => {
+            var_ref = got_ref;
+        },
+        _ => {
+            return None;
+        }
+    }
+    match var_ref {
+        VariableReference::SYMBOL( sy_id ) => {
+            return Some(*sy_id);
+        },
+        VariableReference::VARIABLE( vr_id ) => {
+            match lf_interpretation.get(vr_id) {
+                None => {
+                    return None;
+                },
+                Some( vr_value ) => {
+                    return forwarded_symbol(lf_interpretation,vr_value);
+                }
+            }
+        },
+        VariableReference::MSG_PARAMETER(_,_) => {
+            return None;
+        }
+    }
+}
+
+fn symbol_reference(sy_id : usize, sy_type : &TD_DataType) -> TD_Generic {
+    let sy_ref = VariableReference::SYMBOL(sy_id);
+    match sy_type {
+        TD_DataType::Bool => {
+            return TD_Generic::Bool( TD_Bool::Reference(sy_ref) );
+        },
+        TD_DataType::Integer => {
+            return TD_Generic::Integer( TD_Integer::Reference(sy_ref) );
+        },
+        TD_DataType::Float => {
+            return TD_Generic::Float( TD_Float::Reference(sy_ref) );
+        },
+        TD_DataType::String => {
+            return TD_Generic::String( TD_String::Reference(sy_ref) );
+        }
+    }
+}
+
+fn literals_match(first : &TD_Generic, second : &TD_Generic) -> bool {
+    match (first,second) {
+        (TD_Generic::Bool(first_bool),TD_Generic::Bool(second_bool)) => {
+            return first_bool == second_bool;
+        },
+        _ => {
+            match compare_literals(first,second) {
+                Ok( Ordering::Equal ) => {
+                    return true;
+                },
+                _ => {
+                    return false;
+                }
+            }
+        }
+    }
+}
@@ -32,30 +32,31 @@ pub fn deploy_original_action_followup(exe_ctx : &ExecutionContext,
         },
         ObservableActionKind::Emission( targets ) => {
             let params = effective_parameters.iter().map(|x| ValueOrNewFresh::Value(x.clone()) ).collect();;
-            to_substitute = deploy_receptions(exe_ctx,0,targets, &model_action.ms_id, &params, (model_action.original_position).as_ref().unwrap());
+            // the deployed receptions keep the tags of the emission
+            to_substitute = deploy_receptions(exe_ctx,0,targets, &model_action.ms_id, &params, &model_action.com_kind, (model_action.original_position).as_ref().unwrap()).add_tags(&model_action.tags);
         }
     }
     return interaction.substitute(to_substitute,position);
 }
 
 
-fn deploy_receptions(exe_ctx : &ExecutionContext, index : usize, targets : &Vec<LifelineAction>, ms_id : &usize, params : &Vec<ValueOrNewFresh>, parent_original : &Vec<u32>) -> Interaction {
+fn deploy_receptions(exe_ctx : &ExecutionContext, index : usize, targets : &Vec<LifelineAction>, ms_id : &usize, params : &Vec<ValueOrNewFresh>, com_kind : &CommunicationKind, parent_original : &Vec<u32>) -> Interaction {
     let tar_len = targets.len();
     if index >= tar_len {
         return Interaction::Empty;
     } else if index == tar_len -1 {
         let lf_act = targets.get(index).unwrap();
-        return deploy_lf_act(exe_ctx, lf_act, ms_id, params, parent_original,index as u32);
+        return deploy_lf_act(exe_ctx, lf_act, ms_id, params, com_kind, parent_original,index as u32);
     } else {
         let head_lf_act = targets.get(index).unwrap();
-        let head_int = deploy_lf_act(exe_ctx, head_lf_act, ms_id, params,parent_original,index as u32);
+        let head_int = deploy_lf_act(exe_ctx, head_lf_act, ms_id, params, com_kind, parent_original,index as u32);
         match &head_int {
             &Interaction::Empty => {
-                return deploy_receptions(exe_ctx,index+1,targets, ms_id, params,parent_original);
+                return deploy_receptions(exe_ctx,index+1,targets, ms_id, params, com_kind, parent_original);
             },
             _ => {
                 return Interaction::Par(Box::new(head_int),
-                                        Box::new(deploy_receptions(exe_ctx,index+1,targets, ms_id, params,parent_original)));
+                                        Box::new(deploy_receptions(exe_ctx,index+1,targets, ms_id, params, com_kind, parent_original)));
             }
         }
     }
@@ -65,6 +66,7 @@ fn deploy_lf_act(exe_ctx : &ExecutionContext,
                  lf_act : &LifelineAction,
                  ms_id : &usize,
                  params : &Vec<ValueOrNewFresh>,
+                 com_kind : &CommunicationKind,
                  parent_original : &Vec<u32>,
                  index : u32) -> Interaction {
     let mut original = parent_original.clone();
@@ -74,6 +76,8 @@ fn deploy_lf_act(exe_ctx : &ExecutionContext,
         act_kind:ObservableActionKind::Reception,
         ms_id:*ms_id,
         params:params.clone(),
-        original_position:Some(original.clone()) });
+        com_kind:com_kind.clone(),
+        original_position:Some(original.clone()),
+        tags:Vec::new() });
 }
 
@@ -28,7 +28,7 @@ use crate::core::syntax::data::builtin::bool::TD_Bool;
 use crate::core::syntax::data::generic::TD_Generic;
 
 use crate::core::trace::*;
-use crate::core::semantics::frontier::make_frontier;
+use crate::core::semantics::frontier::make_frontier_in_context;
 use crate::core::semantics::shape_execute::shape_execute;
 
 use crate::process::verdicts::*;
@@ -49,86 +49,182 @@ use crate::diversity::symbex_client::SymbexClient;
 use crate::diversity::*;
 
 use crate::process::queue::ProcessQueue;
+use crate::process::checkpoint::{CheckpointOptions,ProcessCheckpoint};
+use crate::core::error::HibouCoreError;
 
 pub async fn explore(interaction : Interaction,
                      gen_ctx : GeneralContext,
                      exe_ctx : ExecutionContext,
                      temporality : HibouProcessTemporality,
+                     engine : HibouProcessEngine,
                      pre_filters : Vec<HibouPreFilter>,
                      strategy : HibouSearchStrategy,
                      frontier_priorities : ProcessPriorities,
-                     loggers : Vec<Box<dyn ProcessLogger>>) {
+                     loggers : Vec<Box<dyn ProcessLogger>>,
+                     checkpoint_options : Option<CheckpointOptions>,
+                     resume_from : Option<ProcessCheckpoint>) -> Result<(),HibouCoreError> {
     // ***
     let mut first_context = exe_ctx;
-    let xlia_model_string = generate_xlia_model(&gen_ctx,&first_context,&interaction, &temporality);
-    let model_file_path = "xlia_model.xlia".to_string();
-    let mut file = File::create(&model_file_path).unwrap();
-    file.write( xlia_model_string.as_bytes() );
-    println!("generated xlia model :\n{}",xlia_model_string);
-    // ***
-    let (mut client,mut initial_div_ec_id) = symbex_init_model(&gen_ctx,&mut first_context,xlia_model_string).await;
-    // ***
-    initial_div_ec_id = symbex_fire_lifeline_initializations(&mut client, &gen_ctx,&mut first_context,initial_div_ec_id).await;
+    let mut client : Option<SymbexClient<tonic::transport::Channel>>;
+    let initial_div_ec_id : u32;
+    match &engine {
+        HibouProcessEngine::Symbolic => {
+            let xlia_model_string = generate_xlia_model(&gen_ctx,&first_context,&interaction, &temporality);
+            let model_file_path = "xlia_model.xlia".to_string();
+            let mut file = File::create(&model_file_path).unwrap();
+            file.write( xlia_model_string.as_bytes() );
+            println!("generated xlia model :\n{}",xlia_model_string);
+            // ***
+            let (mut got_client,got_div_ec_id) = symbex_init_model(&gen_ctx,&mut first_context,xlia_model_string).await;
+            // ***
+            initial_div_ec_id = symbex_fire_lifeline_initializations(&mut got_client, &gen_ctx,&mut first_context,got_div_ec_id).await;
+            client = Some(got_client);
+        },
+        HibouProcessEngine::Concrete => {
+            // no DIVERSITY execution context is ever used
+            initial_div_ec_id = 0;
+            client = None;
+        }
+    }
     // ***
     let mut manager = HibouProcessManager::new(gen_ctx,
                                                strategy,
                                                temporality,
+                                               engine,
                                                pre_filters,
                                                HashMap::new(),
                                                ProcessQueue::new(),
                                                frontier_priorities,
                                                loggers);
     // ***
-    manager.init_loggers(&first_context,&interaction,&None);
-    // ***
-    let mut next_state_id : u32 = 1;
-    let mut node_counter : u32 = 0;
-    enqueue_next_node_in_exploration(&mut manager,
-                                     next_state_id,
-                                     initial_div_ec_id,
-                                     first_context,
-                                     interaction,0,0);
-    next_state_id = next_state_id + 1;
-    node_counter = node_counter +1;
+    let mut next_state_id : u32;
+    let mut node_counter : u32;
+    match resume_from {
+        None => {
+            manager.init_loggers(&first_context,&interaction,&None);
+            // ***
+            next_state_id = 1;
+            node_counter = 0;
+            enqueue_next_node_in_exploration(&mut manager,
+                                             next_state_id,
+                                             initial_div_ec_id,
+                                             first_context.clone(),
+                                             interaction.clone(),
+                                             Vec::new(),0,0);
+            next_state_id = next_state_id + 1;
+            node_counter = node_counter +1;
+        },
+        Some( checkpoint ) => {
+            match manager.resume_loggers(&first_context,&interaction,&None,&checkpoint.loggers) {
+                Err(e) => {
+                    return Err(e);
+                },
+                Ok(_) => {}
+            }
+            manager.restore_from_checkpoint(&mut client,
+                                            &interaction,
+                                            &first_context,
+                                            initial_div_ec_id,
+                                            &None,
+                                            &checkpoint).await?;
+            next_state_id = checkpoint.next_state_id;
+            node_counter = checkpoint.node_counter;
+        }
+    }
     // ***
+    let mut processed_since_checkpoint : u32 = 0;
     // ***
-    while let Some(next_to_process) = manager.extract_from_queue() {
-        let new_state_id = next_state_id;
-        next_state_id = next_state_id + 1;
-        // ***
-        let mut parent_state = manager.get_memorized_state(next_to_process.state_id).unwrap().clone();
-        // ***
-        match manager.process_next(&mut client,
-                                   &parent_state,
-                                   &next_to_process,
-                                   new_state_id,
-                                   node_counter).await {
-            None => {},
-            Some( (new_interaction,new_exe_ctx,new_div_ec_id,new_multi_trace,new_depth,new_loop_depth) ) => {
-                node_counter = node_counter + 1;
-                enqueue_next_node_in_exploration(&mut manager,
-                                                 new_state_id,
-                                                 new_div_ec_id,
-                                                 new_exe_ctx,
-                                                 new_interaction,
-                                                 new_depth,
-                                                 new_loop_depth);
+    loop {
+        while let Some(next_to_process) = manager.extract_from_queue() {
+            let new_state_id = next_state_id;
+            next_state_id = next_state_id + 1;
+            // ***
+            let mut parent_state = manager.get_memorized_state(next_to_process.state_id).unwrap().clone();
+            // ***
+            match manager.process_next(&mut client,
+                                       &parent_state,
+                                       &next_to_process,
+                                       new_state_id,
+                                       node_counter).await? {
+                None => {},
+                Some( (new_interaction,new_exe_ctx,new_div_ec_id,new_multi_trace,new_depth,new_loop_depth) ) => {
+                    node_counter = node_counter + 1;
+                    manager.abandon_siblings_after_step(next_to_process.state_id,&mut parent_state);
+                    let mut new_path = parent_state.path.clone();
+                    match &next_to_process.kind {
+                        NextToProcessKind::Execute( ref position ) => {
+                            new_path.push( position.clone() );
+                        }
+                    }
+                    enqueue_next_node_in_exploration(&mut manager,
+                                                     new_state_id,
+                                                     new_div_ec_id,
+                                                     new_exe_ctx,
+                                                     new_interaction,
+                                                     new_path,
+                                                     new_depth,
+                                                     new_loop_depth);
+                }
             }
+            // ***
+            parent_state.remaining_ids_to_process.remove(&next_to_process.id_as_child);
+            if parent_state.remaining_ids_to_process.len() == 0 {
+                manager.forget_state(next_to_process.state_id);
+            } else {
+                manager.remember_state(next_to_process.state_id,parent_state);
+            }
+            // ***
+            match &checkpoint_options {
+                None => {},
+                Some( ckpt_opts ) => {
+                    processed_since_checkpoint = processed_since_checkpoint + 1;
+                    if processed_since_checkpoint >= ckpt_opts.period {
+                        match manager.make_checkpoint(next_state_id,node_counter,None).write_to_file(&ckpt_opts.file_path) {
+                            Err(e) => {
+                                // the process goes on and the previous checkpoint, if any, can still be used to resume it
+                                println!("could not write checkpoint : {}", e);
+                            },
+                            Ok(_) => {}
+                        }
+                        processed_since_checkpoint = 0;
+                    }
+                }
+            }
+            // ***
         }
         // ***
-        parent_state.remaining_ids_to_process.remove(&next_to_process.id_as_child);
-        if parent_state.remaining_ids_to_process.len() == 0 {
-            manager.forget_state(next_to_process.state_id);
+        if manager.start_next_iteration() {
+            // the initial state always has the id 1
+            enqueue_next_node_in_exploration(&mut manager,
+                                             1,
+                                             initial_div_ec_id,
+                                             first_context.clone(),
+                                             interaction.clone(),
+                                             Vec::new(),0,0);
         } else {
-            manager.remember_state(next_to_process.state_id,parent_state);
+            break;
         }
-        // ***
     }
     // ***
-    symbex_post_process(&mut client).await;
+    match client.as_mut() {
+        None => {},
+        Some( got_client ) => {
+            symbex_post_process(got_client).await;
+        }
+    }
     // ***
     manager.term_loggers(None);
     // ***
+    match &checkpoint_options {
+        None => {},
+        Some( ckpt_opts ) => {
+            // the process terminated normally so there is nothing to resume
+            // the result is ignored because no checkpoint is written if the process ends before a full period
+            let _ = fs::remove_file(&ckpt_opts.file_path);
+        }
+    }
+    // ***
+    return Ok(());
 }
 
 
@@ -138,13 +234,20 @@ fn enqueue_next_node_in_exploration(manager      : &mut HibouProcessManager,
                                      diversity_ec_id : u32,
                                      exe_ctx : ExecutionContext,
                                      interaction : Interaction,
+                                     path        : Vec<Position>,
                                      depth       : u32,
                                      loop_depth  : u32) {
+    if interaction.exhibits_negative() {
+        // the forbidden behaviour is reported and not explored any further
+        manager.verdict_loggers(&CoverageVerdict::Neg,state_id);
+        manager.forget_state(state_id);
+        return;
+    }
     // ***
     let mut next_child_id : u32 = 0;
     // ***
     let mut to_enqueue : Vec<(u32,NextToProcessKind)> = Vec::new();
-    for front_pos in make_frontier(&interaction) {
+    for front_pos in make_frontier_in_context(&interaction,&exe_ctx) {
         next_child_id = next_child_id +1;
         let child_kind = NextToProcessKind::Execute(front_pos);
         to_enqueue.push( (next_child_id,child_kind) );
@@ -157,9 +260,12 @@ fn enqueue_next_node_in_exploration(manager      : &mut HibouProcessManager,
                                              diversity_ec_id,
                                              None,
                                              rem_child_ids,
+                                             path,
                                              loop_depth,depth);
         manager.remember_state( state_id, memo_state );
         manager.enqueue_executions( state_id, to_enqueue );
+    } else {
+        manager.forget_state(state_id);
     }
 }
 
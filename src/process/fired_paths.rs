@@ -0,0 +1,129 @@
+/********************************************************************************
+ * Copyright (c) 2020 Erwan Mahe (github.com/erwanM974)
+ *
+ * This program and the accompanying materials are made available under the
+ * terms of the Eclipse Public License 2.0 which is available at
+ * http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+ * which is available at https://www.apache.org/licenses/LICENSE-2.0.
+ *
+ * SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+ ********************************************************************************/
+
+use std::collections::HashMap;
+
+
+struct FiredStep<T> {
+    parent_state_id : u32,
+    fired : T,
+    forgotten : bool,   // whether nothing remains to be processed from the state reached by the step
+    live_children : u32 // number of steps fired from that state which are still kept
+}
+
+/*
+    For the loggers which need the path leading to a state,
+    keeps what they record of the action fired to reach each state, together with the state it was fired from.
+    A step is dropped once the state it reaches has been forgotten and so have all the states reached from it
+    so that only the paths leading to the states which remain to be processed are kept
+*/
+pub struct FiredPaths<T> {
+    steps : HashMap<u32,FiredStep<T>>
+}
+
+impl<T> FiredPaths<T> {
+
+    pub fn new() -> FiredPaths<T> {
+        return FiredPaths{steps:HashMap::new()};
+    }
+
+    /*
+        Rebuilds the steps saved as (state_id,parent_state_id,forgotten,fired)
+    */
+    pub fn from_saved(saved_steps : Vec<(u32,u32,bool,T)>) -> FiredPaths<T> {
+        let mut steps : HashMap<u32,FiredStep<T>> = HashMap::new();
+        for (state_id,parent_state_id,forgotten,fired) in saved_steps {
+            steps.insert( state_id, FiredStep{parent_state_id,fired,forgotten,live_children:0} );
+        }
+        let parent_ids : Vec<u32> = steps.values().map(|step| step.parent_state_id).collect();
+        for parent_state_id in parent_ids {
+            match steps.get_mut(&parent_state_id) {
+                None => {},
+                Some( parent_step ) => {
+                    parent_step.live_children = parent_step.live_children + 1;
+                }
+            }
+        }
+        return FiredPaths{steps};
+    }
+
+    pub fn add_step(&mut self, parent_state_id : u32, new_state_id : u32, fired : T) {
+        match self.steps.get_mut(&parent_state_id) {
+            None => {},
+            Some( parent_step ) => {
+                parent_step.live_children = parent_step.live_children + 1;
+            }
+        }
+        self.steps.insert( new_state_id, FiredStep{parent_state_id,fired,forgotten:false,live_children:0} );
+    }
+
+    pub fn forget_state(&mut self, state_id : u32) {
+        match self.steps.get_mut(&state_id) {
+            None => {
+                // the initial state is not reached by any step
+                return;
+            },
+            Some( step ) => {
+                step.forgotten = true;
+            }
+        }
+        // dropping a step may finish the subtree of the state it was fired from
+        let mut current_state_id = state_id;
+        loop {
+            let parent_state_id : u32;
+            match self.steps.get(&current_state_id) {
+                None => {
+                    return;
+                },
+                Some( step ) => {
+                    if !step.forgotten || step.live_children > 0 {
+                        return;
+                    }
+                    parent_state_id = step.parent_state_id;
+                }
+            }
+            self.steps.remove(&current_state_id);
+            match self.steps.get_mut(&parent_state_id) {
+                None => {
+                    return;
+                },
+                Some( parent_step ) => {
+                    parent_step.live_children = parent_step.live_children - 1;
+                }
+            }
+            current_state_id = parent_state_id;
+        }
+    }
+
+    /*
+        Returns what has been recorded of the actions fired to reach the given state, from the first fired to the last
+    */
+    pub fn get_path_to(&self, state_id : u32) -> Vec<&T> {
+        let mut path : Vec<&T> = Vec::new();
+        let mut current_state_id = state_id;
+        while let Some( step ) = self.steps.get(&current_state_id) {
+            path.push( &step.fired );
+            current_state_id = step.parent_state_id;
+        }
+        path.reverse();
+        return path;
+    }
+
+    /*
+        Returns the steps as (state_id,parent_state_id,forgotten,fired) so that they can be saved
+    */
+    pub fn get_steps(&self) -> Vec<(u32,u32,bool,&T)> {
+        let mut steps : Vec<(u32,u32,bool,&T)> = self.steps.iter()
+            .map(|(state_id,step)| (*state_id,step.parent_state_id,step.forgotten,&step.fired)).collect();
+        steps.sort_by_key(|(state_id,_,_,_)| *state_id);
+        return steps;
+    }
+}
@@ -32,6 +32,7 @@ pub struct MemorizedState {
     pub diversity_ec_id : u32,
     pub multi_trace : Option<AnalysableMultiTrace>,
     pub remaining_ids_to_process : HashSet<u32>,
+    pub path : Vec<Position>, // positions executed since initial interaction
     pub loop_depth : u32, // number of loop instanciations since intial interaction
     pub depth : u32       // number of execution steps since initial interaction
 }
@@ -42,9 +43,10 @@ impl MemorizedState {
                diversity_ec_id : u32,
                multi_trace : Option<AnalysableMultiTrace>,
                remaining_ids_to_process : HashSet<u32>,
+               path : Vec<Position>,
                loop_depth : u32,
                depth : u32) -> MemorizedState {
-        return MemorizedState{interaction,exe_ctx,diversity_ec_id,multi_trace,remaining_ids_to_process,loop_depth,depth};
+        return MemorizedState{interaction,exe_ctx,diversity_ec_id,multi_trace,remaining_ids_to_process,path,loop_depth,depth};
     }
 }
 
@@ -73,7 +75,11 @@ impl NextToProcess {
 pub enum HibouPreFilter {
     MaxLoopInstanciation(u32),
     MaxProcessDepth(u32),
-    MaxNodeNumber(u32)
+    MaxNodeNumber(u32),
+    MaxTime(u32),             // in seconds since the beginning of the process
+    MaxSolverCalls(u32),
+    MaxSymbols(u32),
+    MaxPathConditionSize(u32)
 }
 
 impl std::string::ToString for HibouPreFilter {
@@ -87,6 +93,18 @@ impl std::string::ToString for HibouPreFilter {
             },
             HibouPreFilter::MaxNodeNumber(num) => {
                 return format!("MaxNum={}",num);
+            },
+            HibouPreFilter::MaxTime(num) => {
+                return format!("MaxTime={}s",num);
+            },
+            HibouPreFilter::MaxSolverCalls(num) => {
+                return format!("MaxSolverCalls={}",num);
+            },
+            HibouPreFilter::MaxSymbols(num) => {
+                return format!("MaxSymbols={}",num);
+            },
+            HibouPreFilter::MaxPathConditionSize(num) => {
+                return format!("MaxPCSize={}",num);
             }
         }
     }
@@ -95,7 +113,12 @@ impl std::string::ToString for HibouPreFilter {
 pub enum FilterEliminationKind {
     MaxLoopInstanciation,
     MaxProcessDepth,
-    MaxNodeNumber
+    MaxNodeNumber,
+    MaxTime,
+    MaxSolverCalls,
+    MaxSymbols,
+    MaxPathConditionSize,
+    ConcreteMismatch // not a user-defined filter ; the action concretely cannot match the trace
 }
 
 impl std::string::ToString for FilterEliminationKind {
@@ -109,6 +132,21 @@ impl std::string::ToString for FilterEliminationKind {
             },
             FilterEliminationKind::MaxNodeNumber => {
                 return "MaxNum".to_string();
+            },
+            FilterEliminationKind::MaxTime => {
+                return "MaxTime".to_string();
+            },
+            FilterEliminationKind::MaxSolverCalls => {
+                return "MaxSolverCalls".to_string();
+            },
+            FilterEliminationKind::MaxSymbols => {
+                return "MaxSymbols".to_string();
+            },
+            FilterEliminationKind::MaxPathConditionSize => {
+                return "MaxPCSize".to_string();
+            },
+            FilterEliminationKind::ConcreteMismatch => {
+                return "ConcreteMismatch".to_string();
             }
         }
     }
@@ -117,7 +155,10 @@ impl std::string::ToString for FilterEliminationKind {
 
 pub enum HibouSearchStrategy {
     BFS,
-    DFS
+    DFS,
+    BestFS,                  // greedy : nodes which consumed the most of the multi-trace first
+    IterativeDeepening(u32), // DFS with a maximum depth increased by the given step between iterations
+    RandomWalk(u64,u32)      // seed and number of walks
 }
 
 impl std::string::ToString for HibouSearchStrategy {
@@ -128,6 +169,40 @@ impl std::string::ToString for HibouSearchStrategy {
             },
             HibouSearchStrategy::DFS => {
                 return "Depth First Search".to_string();
+            },
+            HibouSearchStrategy::BestFS => {
+                return "Greedy Best First Search".to_string();
+            },
+            HibouSearchStrategy::IterativeDeepening(step) => {
+                return format!("Iterative Deepening Depth First Search (step={})",step);
+            },
+            HibouSearchStrategy::RandomWalk(seed,walks) => {
+                return format!("Random Walk (seed={};walks={})",seed,walks);
+            }
+        }
+    }
+}
+
+/*
+    What a search strategy needs to remember in between iterations
+    (e.g. in between two walks or two deepening steps)
+*/
+#[derive(Clone, PartialEq, Debug)]
+pub struct SearchProgress {
+    pub depth_limit : Option<u32>, // current maximum depth for iterative deepening
+    pub depth_limit_hit : bool,    // whether or not some node was eliminated by that maximum depth during the current iteration
+    pub rng_draws : u64,           // number of random numbers drawn since the seed
+    pub completed_walks : u32
+}
+
+impl SearchProgress {
+    pub fn new(strategy : &HibouSearchStrategy) -> SearchProgress {
+        match strategy {
+            HibouSearchStrategy::IterativeDeepening(step) => {
+                return SearchProgress{depth_limit:Some(*step),depth_limit_hit:false,rng_draws:0,completed_walks:0};
+            },
+            _ => {
+                return SearchProgress{depth_limit:None,depth_limit_hit:false,rng_draws:0,completed_walks:0};
             }
         }
     }
@@ -153,26 +228,20 @@ impl std::string::ToString for HibouProcessTemporality {
 }
 
 
+pub enum HibouProcessEngine {
+    Symbolic, // relies on DIVERSITY
+    Concrete  // concrete evaluation within HIBOU
+}
 
-
-
-
-
-
-
-
-
-
-
-
-
-
-
-
-
-
-
-
-
-
-
+impl std::string::ToString for HibouProcessEngine {
+    fn to_string(&self) -> String {
+        match self {
+            HibouProcessEngine::Symbolic => {
+                return "symbolic".to_string();
+            },
+            HibouProcessEngine::Concrete => {
+                return "concrete".to_string();
+            }
+        }
+    }
+}
@@ -0,0 +1,490 @@
+/********************************************************************************
+ * Copyright (c) 2020 Erwan Mahe (github.com/erwanM974)
+ *
+ * This program and the accompanying materials are made available under the
+ * terms of the Eclipse Public License 2.0 which is available at
+ * http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+ * which is available at https://www.apache.org/licenses/LICENSE-2.0.
+ *
+ * SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+ ********************************************************************************/
+
+use std::collections::{HashSet,BTreeMap};
+
+use crate::core::context::general::GeneralContext;
+use crate::core::context::execution::ExecutionContext;
+use crate::core::syntax::interaction::{Interaction,ScheduleOperatorKind};
+use crate::core::syntax::action::*;
+
+use crate::process::pathologies::find_pathologies;
+
+use crate::rendering::textual::convention::*;
+
+
+pub enum LintWarningKind {
+    UnusedMessage,
+    UnusedVariable,
+    UnusedLifeline,
+    ReadBeforeAssignment,
+    UselessScope,
+    IdenticalAltBranches,
+    ParametersNumberMismatch,
+    EmptyLoop,
+    NonLocalChoice,
+    Race
+}
+
+impl std::string::ToString for LintWarningKind {
+    fn to_string(&self) -> String {
+        match self {
+            LintWarningKind::UnusedMessage => {
+                return "unused-message".to_string();
+            },
+            LintWarningKind::UnusedVariable => {
+                return "unused-variable".to_string();
+            },
+            LintWarningKind::UnusedLifeline => {
+                return "unused-lifeline".to_string();
+            },
+            LintWarningKind::ReadBeforeAssignment => {
+                return "read-before-assignment".to_string();
+            },
+            LintWarningKind::UselessScope => {
+                return "useless-scope".to_string();
+            },
+            LintWarningKind::IdenticalAltBranches => {
+                return "identical-alt-branches".to_string();
+            },
+            LintWarningKind::ParametersNumberMismatch => {
+                return "parameters-number-mismatch".to_string();
+            },
+            LintWarningKind::EmptyLoop => {
+                return "empty-loop".to_string();
+            },
+            LintWarningKind::NonLocalChoice => {
+                return "non-local-choice".to_string();
+            },
+            LintWarningKind::Race => {
+                return "race".to_string();
+            }
+        }
+    }
+}
+
+pub struct LintWarning {
+    pub kind : LintWarningKind,
+    pub message : String
+}
+
+impl std::string::ToString for LintWarning {
+    fn to_string(&self) -> String {
+        return format!("[{}] {}", self.kind.to_string(), self.message);
+    }
+}
+
+// for each lifeline, the variables which may have been assigned on it
+type AssignedVariables = BTreeMap<usize,HashSet<usize>>;
+
+/*
+    Static checks on a parsed model which point at likely modelling mistakes
+    None of them prevents the model from being explored or analysed
+*/
+pub fn lint_model(interaction : &Interaction,
+                  gen_ctx : &GeneralContext,
+                  exe_ctx : &ExecutionContext) -> Vec<LintWarning> {
+    let mut warnings : Vec<LintWarning> = Vec::new();
+    let mut actions : Vec<&ObservableAction> = Vec::new();
+    collect_actions(interaction, &mut actions);
+    // ***
+    lint_unused_declarations(&actions, gen_ctx, &mut warnings);
+    // ***
+    let mut initialized : AssignedVariables = BTreeMap::new();
+    for lf_id in 0..gen_ctx.get_lf_num() {
+        let mut lf_initialized : HashSet<usize> = HashSet::new();
+        match exe_ctx.get_lf_interpretation(lf_id) {
+            None => {},
+            Some( lf_interpretation ) => {
+                lf_initialized.extend( lf_interpretation.keys().cloned() );
+            }
+        }
+        initialized.insert(lf_id, lf_initialized);
+    }
+    let mut reported_reads : HashSet<(usize,usize)> = HashSet::new();
+    lint_guard_reads(interaction, gen_ctx, &initialized, &mut reported_reads, &mut warnings);
+    // ***
+    lint_structure(interaction, gen_ctx, &mut warnings);
+    warnings.append( &mut find_pathologies(interaction, gen_ctx) );
+    // ***
+    for action in &actions {
+        let expected_num = get_expected_parameters_number(action, gen_ctx);
+        if action.params.len() != expected_num {
+            warnings.push( LintWarning{kind:LintWarningKind::ParametersNumberMismatch,
+                message:format!("action '{}' carries {} parameter(s) while message '{}' is declared with {} ; it cannot match any trace action",
+                                action_to_short_text(action,gen_ctx), action.params.len(), gen_ctx.get_ms_name(action.ms_id).unwrap(), expected_num)} );
+        }
+    }
+    return warnings;
+}
+
+// replies carry the return value of the call, if the message declares one, instead of its parameters
+fn get_expected_parameters_number(action : &ObservableAction, gen_ctx : &GeneralContext) -> usize {
+    match action.com_kind {
+        CommunicationKind::Reply => {
+            match gen_ctx.get_ms_return_type(action.ms_id) {
+                None => {
+                    return 0;
+                },
+                Some(_) => {
+                    return 1;
+                }
+            }
+        },
+        _ => {
+            return gen_ctx.get_ms_spec(action.ms_id).unwrap().len();
+        }
+    }
+}
+
+fn lint_unused_declarations(actions : &Vec<&ObservableAction>, gen_ctx : &GeneralContext, warnings : &mut Vec<LintWarning>) {
+    let mut used_messages : HashSet<usize> = HashSet::new();
+    let mut used_lifelines : HashSet<usize> = HashSet::new();
+    let mut used_variables : HashSet<usize> = HashSet::new();
+    for action in actions {
+        used_messages.insert( action.ms_id );
+        used_lifelines.extend( action.occupation_after() );
+        for param in &action.params {
+            match param {
+                ValueOrNewFresh::NewFresh => {},
+                ValueOrNewFresh::Value( td_gen ) => {
+                    used_variables.extend( td_gen.get_occuring_variables() );
+                }
+            }
+        }
+        for lf_act in get_lifeline_actions(action) {
+            for amble_item in lf_act.preamble.iter().chain(lf_act.postamble.iter()) {
+                used_variables.extend( get_amble_item_variables(amble_item) );
+            }
+        }
+    }
+    // ***
+    for ms_id in 0..gen_ctx.get_ms_num() {
+        if !used_messages.contains(&ms_id) {
+            warnings.push( LintWarning{kind:LintWarningKind::UnusedMessage,
+                message:format!("message '{}' is declared but never used", gen_ctx.get_ms_name(ms_id).unwrap())} );
+        }
+    }
+    for vr_id in 0..gen_ctx.get_vr_num() {
+        if !used_variables.contains(&vr_id) {
+            warnings.push( LintWarning{kind:LintWarningKind::UnusedVariable,
+                message:format!("variable '{}' is declared but never used", gen_ctx.get_vr_name(vr_id).unwrap())} );
+        }
+    }
+    for lf_id in 0..gen_ctx.get_lf_num() {
+        if !used_lifelines.contains(&lf_id) {
+            warnings.push( LintWarning{kind:LintWarningKind::UnusedLifeline,
+                message:format!("lifeline '{}' is declared but never used", gen_ctx.get_lf_name(lf_id).unwrap())} );
+        }
+    }
+}
+
+/*
+    Walks the interaction in the order in which its actions may occur, keeping track of the variables which may have been assigned on each lifeline
+    Guards reading a variable which cannot have been assigned yet on their lifeline are reported once per lifeline and variable
+*/
+fn lint_guard_reads(interaction : &Interaction,
+                    gen_ctx : &GeneralContext,
+                    assigned : &AssignedVariables,
+                    reported : &mut HashSet<(usize,usize)>,
+                    warnings : &mut Vec<LintWarning>) -> AssignedVariables {
+    match interaction {
+        Interaction::Empty => {
+            return assigned.clone();
+        },
+        Interaction::Action( action ) => {
+            let mut new_assigned = assigned.clone();
+            for lf_act in get_lifeline_actions(action) {
+                let lf_assigned = new_assigned.entry(lf_act.lf_id).or_insert( HashSet::new() );
+                for amble_item in lf_act.preamble.iter().chain(lf_act.postamble.iter()) {
+                    match amble_item {
+                        ActionAmbleItem::Guard( td_bool ) => {
+                            for vr_id in td_bool.get_occuring_variables() {
+                                // clocks start at zero
+                                if !gen_ctx.is_clock(vr_id) && !lf_assigned.contains(&vr_id) && !reported.contains(&(lf_act.lf_id,vr_id)) {
+                                    reported.insert( (lf_act.lf_id,vr_id) );
+                                    warnings.push( LintWarning{kind:LintWarningKind::ReadBeforeAssignment,
+                                        message:format!("variable '{}' is read in a guard of action '{}' before any assignment or '@init' on lifeline '{}'",
+                                                        gen_ctx.get_vr_name(vr_id).unwrap(), action_to_short_text(action,gen_ctx), gen_ctx.get_lf_name(lf_act.lf_id).unwrap())} );
+                                }
+                            }
+                        },
+                        ActionAmbleItem::Assignment( vr_id, _ ) | ActionAmbleItem::Reset( vr_id ) => {
+                            lf_assigned.insert( *vr_id );
+                        }
+                    }
+                }
+            }
+            return new_assigned;
+        },
+        Interaction::Strict(i1,i2) | Interaction::Seq(i1,i2) => {
+            let after_i1 = lint_guard_reads(i1, gen_ctx, assigned, reported, warnings);
+            return lint_guard_reads(i2, gen_ctx, &after_i1, reported, warnings);
+        },
+        Interaction::Alt(i1,i2) => {
+            let after_i1 = lint_guard_reads(i1, gen_ctx, assigned, reported, warnings);
+            let after_i2 = lint_guard_reads(i2, gen_ctx, assigned, reported, warnings);
+            return union_assigned(&after_i1, &after_i2);
+        },
+        Interaction::Par(i1,i2) => {
+            // actions of either side may be interleaved before those of the other
+            let mut in_i1 : AssignedVariables = BTreeMap::new();
+            collect_assignments(i1, &mut in_i1);
+            let mut in_i2 : AssignedVariables = BTreeMap::new();
+            collect_assignments(i2, &mut in_i2);
+            let after_i1 = lint_guard_reads(i1, gen_ctx, &union_assigned(assigned,&in_i2), reported, warnings);
+            let after_i2 = lint_guard_reads(i2, gen_ctx, &union_assigned(assigned,&in_i1), reported, warnings);
+            return union_assigned(&after_i1, &after_i2);
+        },
+        Interaction::Loop(_,_,i1) | Interaction::Opt(i1) | Interaction::Break(_,i1) => {
+            let after_i1 = lint_guard_reads(i1, gen_ctx, assigned, reported, warnings);
+            return union_assigned(assigned, &after_i1);
+        },
+        Interaction::Neg(_,i1) => {
+            // no accepted behaviour goes through a neg fragment
+            lint_guard_reads(i1, gen_ctx, assigned, reported, warnings);
+            return assigned.clone();
+        },
+        Interaction::Scope(_,i1) | Interaction::Call(_,_,i1) | Interaction::Assert(_,i1) | Interaction::Create(_,i1)
+                | Interaction::Ref(_,i1) => {
+            return lint_guard_reads(i1, gen_ctx, assigned, reported, warnings);
+        }
+    }
+}
+
+fn lint_structure(interaction : &Interaction, gen_ctx : &GeneralContext, warnings : &mut Vec<LintWarning>) {
+    match interaction {
+        Interaction::Empty | Interaction::Action(_) => {},
+        Interaction::Strict(i1,i2) | Interaction::Seq(i1,i2) | Interaction::Par(i1,i2) => {
+            lint_structure(i1, gen_ctx, warnings);
+            lint_structure(i2, gen_ctx, warnings);
+        },
+        Interaction::Alt(_,_) => {
+            let mut branches : Vec<&Interaction> = Vec::new();
+            collect_alt_branches(interaction, &mut branches);
+            let stripped : Vec<Interaction> = branches.iter().map(|br| strip_original_positions(br)).collect();
+            for i in 0..stripped.len() {
+                for j in (i+1)..stripped.len() {
+                    if stripped[i] == stripped[j] {
+                        warnings.push( LintWarning{kind:LintWarningKind::IdenticalAltBranches,
+                            message:format!("branches {} and {} of the '@alt' starting with {} are structurally identical",
+                                            i+1, j+1, describe_first_action(interaction,gen_ctx))} );
+                    }
+                }
+            }
+            for branch in branches {
+                lint_structure(branch, gen_ctx, warnings);
+            }
+        },
+        Interaction::Loop(lkind,_,i1) => {
+            let mut body_actions : Vec<&ObservableAction> = Vec::new();
+            collect_actions(i1, &mut body_actions);
+            if body_actions.len() == 0 {
+                let loop_name = match lkind {
+                    ScheduleOperatorKind::Strict => {"@loopX"},
+                    ScheduleOperatorKind::Seq => {"@loopH"},
+                    ScheduleOperatorKind::Par => {"@loopP"}
+                };
+                warnings.push( LintWarning{kind:LintWarningKind::EmptyLoop,
+                    message:format!("a '{}' has an empty body", loop_name)} );
+            }
+            lint_structure(i1, gen_ctx, warnings);
+        },
+        Interaction::Opt(i1) | Interaction::Call(_,_,i1) | Interaction::Neg(_,i1) | Interaction::Assert(_,i1) | Interaction::Break(_,i1)
+                | Interaction::Create(_,i1) | Interaction::Ref(_,i1) => {
+            lint_structure(i1, gen_ctx, warnings);
+        },
+        Interaction::Scope(vr_ids,i1) => {
+            let mut assigned_inside : AssignedVariables = BTreeMap::new();
+            collect_assignments(i1, &mut assigned_inside);
+            for vr_id in vr_ids {
+                if !assigned_inside.values().any(|lf_assigned| lf_assigned.contains(vr_id)) {
+                    warnings.push( LintWarning{kind:LintWarningKind::UselessScope,
+                        message:format!("variable '{}' is scoped in the '@scope' starting with {} but never assigned inside",
+                                        gen_ctx.get_vr_name(*vr_id).unwrap(), describe_first_action(interaction,gen_ctx))} );
+                }
+            }
+            lint_structure(i1, gen_ctx, warnings);
+        }
+    }
+}
+
+pub fn collect_actions<'a>(interaction : &'a Interaction, actions : &mut Vec<&'a ObservableAction>) {
+    match interaction {
+        Interaction::Empty => {},
+        Interaction::Action( action ) => {
+            actions.push( action );
+        },
+        Interaction::Strict(i1,i2) | Interaction::Seq(i1,i2) | Interaction::Alt(i1,i2) | Interaction::Par(i1,i2) => {
+            collect_actions(i1, actions);
+            collect_actions(i2, actions);
+        },
+        Interaction::Loop(_,_,i1) | Interaction::Opt(i1) | Interaction::Scope(_,i1) | Interaction::Call(_,_,i1)
+                | Interaction::Neg(_,i1) | Interaction::Assert(_,i1) | Interaction::Break(_,i1) | Interaction::Create(_,i1)
+                | Interaction::Ref(_,i1) => {
+            collect_actions(i1, actions);
+        }
+    }
+}
+
+fn collect_alt_branches<'a>(interaction : &'a Interaction, branches : &mut Vec<&'a Interaction>) {
+    match interaction {
+        Interaction::Alt(i1,i2) => {
+            collect_alt_branches(i1, branches);
+            collect_alt_branches(i2, branches);
+        },
+        _ => {
+            branches.push( interaction );
+        }
+    }
+}
+
+fn collect_assignments(interaction : &Interaction, assigned : &mut AssignedVariables) {
+    let mut actions : Vec<&ObservableAction> = Vec::new();
+    collect_actions(interaction, &mut actions);
+    for action in actions {
+        for lf_act in get_lifeline_actions(action) {
+            let lf_assigned = assigned.entry(lf_act.lf_id).or_insert( HashSet::new() );
+            for amble_item in lf_act.preamble.iter().chain(lf_act.postamble.iter()) {
+                match amble_item {
+                    ActionAmbleItem::Assignment( vr_id, _ ) | ActionAmbleItem::Reset( vr_id ) => {
+                        lf_assigned.insert( *vr_id );
+                    },
+                    ActionAmbleItem::Guard(_) => {}
+                }
+            }
+        }
+    }
+}
+
+fn union_assigned(first : &AssignedVariables, second : &AssignedVariables) -> AssignedVariables {
+    let mut union = first.clone();
+    for (lf_id,lf_assigned) in second {
+        union.entry(*lf_id).or_insert( HashSet::new() ).extend( lf_assigned.iter().cloned() );
+    }
+    return union;
+}
+
+fn get_lifeline_actions(action : &ObservableAction) -> Vec<&LifelineAction> {
+    let mut lf_acts : Vec<&LifelineAction> = vec![&action.lf_act];
+    match &action.act_kind {
+        ObservableActionKind::Reception => {},
+        ObservableActionKind::Emission( targets ) => {
+            for target in targets {
+                lf_acts.push( target );
+            }
+        }
+    }
+    return lf_acts;
+}
+
+fn get_amble_item_variables(amble_item : &ActionAmbleItem) -> HashSet<usize> {
+    match amble_item {
+        ActionAmbleItem::Guard( td_bool ) => {
+            return td_bool.get_occuring_variables();
+        },
+        ActionAmbleItem::Assignment( vr_id, ValueOrNewFresh::NewFresh ) | ActionAmbleItem::Reset( vr_id ) => {
+            let mut vars : HashSet<usize> = HashSet::new();
+            vars.insert( *vr_id );
+            return vars;
+        },
+        ActionAmbleItem::Assignment( vr_id, ValueOrNewFresh::Value(td_gen) ) => {
+            let mut vars = td_gen.get_occuring_variables();
+            vars.insert( *vr_id );
+            return vars;
+        }
+    }
+}
+
+// positions are given to actions after parsing and differ between otherwise identical branches
+fn strip_original_positions(interaction : &Interaction) -> Interaction {
+    match interaction {
+        Interaction::Empty => {
+            return Interaction::Empty;
+        },
+        Interaction::Action( action ) => {
+            let mut stripped = action.clone();
+            stripped.original_position = None;
+            return Interaction::Action(stripped);
+        },
+        Interaction::Strict(i1,i2) => {
+            return Interaction::Strict( Box::new(strip_original_positions(i1)), Box::new(strip_original_positions(i2)) );
+        },
+        Interaction::Seq(i1,i2) => {
+            return Interaction::Seq( Box::new(strip_original_positions(i1)), Box::new(strip_original_positions(i2)) );
+        },
+        Interaction::Alt(i1,i2) => {
+            return Interaction::Alt( Box::new(strip_original_positions(i1)), Box::new(strip_original_positions(i2)) );
+        },
+        Interaction::Par(i1,i2) => {
+            return Interaction::Par( Box::new(strip_original_positions(i1)), Box::new(strip_original_positions(i2)) );
+        },
+        Interaction::Loop(lkind,bounds,i1) => {
+            return Interaction::Loop( lkind.clone(), bounds.clone(), Box::new(strip_original_positions(i1)) );
+        },
+        Interaction::Opt(i1) => {
+            return Interaction::Opt( Box::new(strip_original_positions(i1)) );
+        },
+        Interaction::Break(_,i1) => {
+            // the scope of a break is itself an original position
+            return Interaction::Break( Vec::new(), Box::new(strip_original_positions(i1)) );
+        },
+        Interaction::Neg(entered,i1) => {
+            return Interaction::Neg( *entered, Box::new(strip_original_positions(i1)) );
+        },
+        Interaction::Assert(entered,i1) => {
+            return Interaction::Assert( *entered, Box::new(strip_original_positions(i1)) );
+        },
+        Interaction::Create(lf_ids,i1) => {
+            return Interaction::Create( lf_ids.clone(), Box::new(strip_original_positions(i1)) );
+        },
+        Interaction::Ref(ref_id,i1) => {
+            return Interaction::Ref( *ref_id, Box::new(strip_original_positions(i1)) );
+        },
+        Interaction::Scope(vr_ids,i1) => {
+            return Interaction::Scope( vr_ids.clone(), Box::new(strip_original_positions(i1)) );
+        },
+        Interaction::Call(caller,called,i1) => {
+            return Interaction::Call( *caller, *called, Box::new(strip_original_positions(i1)) );
+        }
+    }
+}
+
+fn describe_first_action(interaction : &Interaction, gen_ctx : &GeneralContext) -> String {
+    let mut actions : Vec<&ObservableAction> = Vec::new();
+    collect_actions(interaction, &mut actions);
+    match actions.get(0) {
+        None => {
+            return "no action".to_string();
+        },
+        Some( action ) => {
+            return format!("action '{}'", action_to_short_text(action,gen_ctx));
+        }
+    }
+}
+
+pub fn action_to_short_text(action : &ObservableAction, gen_ctx : &GeneralContext) -> String {
+    let lf_name = gen_ctx.get_lf_name(action.lf_act.lf_id).unwrap();
+    let ms_name = gen_ctx.get_ms_name(action.ms_id).unwrap();
+    let com_mark = match action.com_kind {
+        CommunicationKind::Asynchronous => {""},
+        CommunicationKind::Call => {SYNTAX_CALL_MARK},
+        CommunicationKind::Reply => {SYNTAX_REPLY_MARK}
+    };
+    match action.act_kind {
+        ObservableActionKind::Reception => {
+            return format!("{}{}{}{}", lf_name, SYNTAX_RECEPTION, com_mark, ms_name);
+        },
+        ObservableActionKind::Emission(_) => {
+            return format!("{}{}{}{}", lf_name, SYNTAX_EMISSION, com_mark, ms_name);
+        }
+    }
+}
@@ -37,6 +37,23 @@ pub trait ProcessLogger {
                 exe_ctx : &ExecutionContext,
                 remaining_multi_trace : &Option<AnalysableMultiTrace>);
 
+    /*
+        Called instead of 'log_init' when the process is resumed from a checkpoint
+        with the state the logger saved in that checkpoint
+    */
+    fn log_resume(&mut self,
+                  interaction : &Interaction,
+                  gen_ctx : &GeneralContext,
+                  exe_ctx : &ExecutionContext,
+                  remaining_multi_trace : &Option<AnalysableMultiTrace>,
+                  saved_state : &Vec<String>) -> Result<(),String>;
+
+    /*
+        Returns the state to save in a checkpoint so that the logger can be resumed
+        Each item is one line of text
+    */
+    fn log_checkpoint(&mut self) -> Vec<String>;
+
     fn log_term(&mut self,
                 options_as_str : &Vec<String>);
 
@@ -55,6 +72,13 @@ pub trait ProcessLogger {
                    parent_state_id : u32,
                    verdict : &CoverageVerdict);
 
+    /*
+        Called once nothing remains to be processed from the given state
+        i.e. once all its children have been processed, or right away if it has none
+    */
+    fn log_forget(&mut self,
+                  state_id : u32);
+
     fn log_filtered(&mut self,
                     gen_ctx : &GeneralContext,
                     exe_ctx:&ExecutionContext,
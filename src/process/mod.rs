@@ -18,6 +18,14 @@ pub mod process_manager;
 pub mod deploy_receptions;
 pub mod verdicts;
 pub mod queue;
+pub mod checkpoint;
+pub mod precheck;
+pub mod concrete_execution;
+pub mod simulation;
+pub mod lint;
+pub mod pathologies;
+pub mod model_coverage;
+pub mod fired_paths;
 
 pub mod exploration;
 pub mod analysis;
@@ -0,0 +1,316 @@
+/********************************************************************************
+ * Copyright (c) 2020 Erwan Mahe (github.com/erwanM974)
+ *
+ * This program and the accompanying materials are made available under the
+ * terms of the Eclipse Public License 2.0 which is available at
+ * http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+ * which is available at https://www.apache.org/licenses/LICENSE-2.0.
+ *
+ * SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+ ********************************************************************************/
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::collections::{HashSet,HashMap};
+
+use crate::core::context::general::GeneralContext;
+use crate::core::context::execution::ExecutionContext;
+use crate::core::syntax::position::*;
+use crate::core::syntax::interaction::Interaction;
+use crate::core::syntax::action::*;
+use crate::core::trace::{AnalysableMultiTrace,TraceAction};
+
+use crate::process::log::ProcessLogger;
+use crate::process::verdicts::CoverageVerdict;
+use crate::process::hibou_process::FilterEliminationKind;
+use crate::process::lint::{collect_actions,action_to_short_text};
+use crate::process::fired_paths::FiredPaths;
+
+use crate::rendering::textual::monochrome::position::position_to_text;
+
+
+pub enum ModelFragmentKind {
+    Action(String),
+    AltBranch,
+    EmptyAltBranch, // cannot be told apart from the other branches not being taken, hence not applicable
+    LoopBody,
+    OptBody,
+    BreakBody
+}
+
+impl std::string::ToString for ModelFragmentKind {
+    fn to_string(&self) -> String {
+        match self {
+            ModelFragmentKind::Action( act_text ) => {
+                return format!("action '{}'", act_text);
+            },
+            ModelFragmentKind::AltBranch => {
+                return "'@alt' branch".to_string();
+            },
+            ModelFragmentKind::EmptyAltBranch => {
+                return "empty '@alt' branch".to_string();
+            },
+            ModelFragmentKind::LoopBody => {
+                return "loop body".to_string();
+            },
+            ModelFragmentKind::OptBody => {
+                return "'@opt' body".to_string();
+            },
+            ModelFragmentKind::BreakBody => {
+                return "'@break' body".to_string();
+            }
+        }
+    }
+}
+
+impl ModelFragmentKind {
+    /*
+        An empty '@alt' branch has no action which could tell whether or not it is exercised
+    */
+    pub fn is_applicable(&self) -> bool {
+        match self {
+            ModelFragmentKind::EmptyAltBranch => {
+                return false;
+            },
+            _ => {
+                return true;
+            }
+        }
+    }
+}
+
+/*
+    Counts, for the actions, the '@alt' branches, the loop bodies, the '@opt' and the '@break' bodies of a model,
+    identified by their position in the interaction as written in the model,
+    the number of accepted traces which exercise them
+*/
+pub struct ModelCoverage {
+    fragments : Vec<(Vec<u32>,ModelFragmentKind)>,
+    hits : HashMap<Vec<u32>,u32>,
+    accepted_traces : u32
+}
+
+impl ModelCoverage {
+    pub fn new(interaction : &Interaction, gen_ctx : &GeneralContext) -> ModelCoverage {
+        let mut fragments : Vec<(Vec<u32>,ModelFragmentKind)> = Vec::new();
+        collect_fragments(interaction, gen_ctx, Vec::new(), &mut fragments);
+        let mut hits : HashMap<Vec<u32>,u32> = HashMap::new();
+        for (frag_pos,_) in &fragments {
+            hits.insert( frag_pos.clone(), 0 );
+        }
+        return ModelCoverage{fragments,hits,accepted_traces:0};
+    }
+
+    /*
+        A fragment is exercised if one of the fired actions originates from within it
+        Receptions deployed from an emission have the original position of the emission extended by one index
+    */
+    pub fn add_accepted_trace(&mut self, fired_positions : &HashSet<Vec<u32>>) {
+        self.accepted_traces = self.accepted_traces + 1;
+        // a single action may be both a fragment and the branch or body containing it, hence the iteration on distinct positions
+        for (frag_pos,hit) in self.hits.iter_mut() {
+            if fired_positions.iter().any(|fired_pos| fired_pos.starts_with(frag_pos)) {
+                *hit = *hit + 1;
+            }
+        }
+    }
+
+    pub fn get_covered_action_positions(&self) -> HashSet<Vec<u32>> {
+        let mut covered : HashSet<Vec<u32>> = HashSet::new();
+        for (frag_pos,frag_kind) in &self.fragments {
+            match frag_kind {
+                ModelFragmentKind::Action(_) => {
+                    if *self.hits.get(frag_pos).unwrap() > 0 {
+                        covered.insert( frag_pos.clone() );
+                    }
+                },
+                _ => {}
+            }
+        }
+        return covered;
+    }
+
+    pub fn report_lines(&self) -> Vec<String> {
+        let mut lines : Vec<String> = Vec::new();
+        let applicable : Vec<&(Vec<u32>,ModelFragmentKind)> = self.fragments.iter().filter(|(_,frag_kind)| frag_kind.is_applicable()).collect();
+        let covered_num = applicable.iter().filter(|(frag_pos,_)| *self.hits.get(frag_pos).unwrap() > 0).count();
+        lines.push( format!("{}/{} fragment(s) exercised by {} accepted trace(s)", covered_num, applicable.len(), self.accepted_traces) );
+        for (frag_pos,frag_kind) in &self.fragments {
+            let frag_pos_str = position_to_text( &Position::from_vec(&mut frag_pos.clone()) );
+            if frag_kind.is_applicable() {
+                lines.push( format!("{} at position {} : {} hit(s)", frag_kind.to_string(), frag_pos_str, self.hits.get(frag_pos).unwrap()) );
+            } else {
+                lines.push( format!("{} at position {} : not applicable", frag_kind.to_string(), frag_pos_str) );
+            }
+        }
+        return lines;
+    }
+}
+
+fn collect_fragments(interaction : &Interaction,
+                     gen_ctx : &GeneralContext,
+                     path : Vec<u32>,
+                     fragments : &mut Vec<(Vec<u32>,ModelFragmentKind)>) {
+    match interaction {
+        Interaction::Empty => {},
+        Interaction::Action( action ) => {
+            fragments.push( (path, ModelFragmentKind::Action(action_to_short_text(action,gen_ctx))) );
+        },
+        Interaction::Alt(_,_) => {
+            // nested '@alt' on the right are branches of the same choice
+            let mut current = interaction;
+            let mut current_path = path;
+            while let Interaction::Alt(i1,i2) = current {
+                let branch_path = extend_path(&current_path,1);
+                fragments.push( (branch_path.clone(), get_alt_branch_kind(i1)) );
+                collect_fragments(i1, gen_ctx, branch_path, fragments);
+                current = i2;
+                current_path = extend_path(&current_path,2);
+            }
+            fragments.push( (current_path.clone(), get_alt_branch_kind(current)) );
+            collect_fragments(current, gen_ctx, current_path, fragments);
+        },
+        Interaction::Strict(i1,i2) | Interaction::Seq(i1,i2) | Interaction::Par(i1,i2) => {
+            collect_fragments(i1, gen_ctx, extend_path(&path,1), fragments);
+            collect_fragments(i2, gen_ctx, extend_path(&path,2), fragments);
+        },
+        Interaction::Loop(_,_,i1) => {
+            let body_path = extend_path(&path,1);
+            fragments.push( (body_path.clone(), ModelFragmentKind::LoopBody) );
+            collect_fragments(i1, gen_ctx, body_path, fragments);
+        },
+        Interaction::Opt(i1) => {
+            let body_path = extend_path(&path,1);
+            fragments.push( (body_path.clone(), ModelFragmentKind::OptBody) );
+            collect_fragments(i1, gen_ctx, body_path, fragments);
+        },
+        Interaction::Break(_,i1) => {
+            let body_path = extend_path(&path,1);
+            fragments.push( (body_path.clone(), ModelFragmentKind::BreakBody) );
+            collect_fragments(i1, gen_ctx, body_path, fragments);
+        },
+        Interaction::Neg(_,_) => {
+            // forbidden behaviours are never exercised by accepted traces
+        },
+        Interaction::Scope(_,i1) | Interaction::Call(_,_,i1) | Interaction::Assert(_,i1) | Interaction::Create(_,i1)
+                | Interaction::Ref(_,i1) => {
+            collect_fragments(i1, gen_ctx, extend_path(&path,1), fragments);
+        }
+    }
+}
+
+fn get_alt_branch_kind(branch : &Interaction) -> ModelFragmentKind {
+    let mut actions : Vec<&ObservableAction> = Vec::new();
+    collect_actions(branch, &mut actions);
+    if actions.is_empty() {
+        return ModelFragmentKind::EmptyAltBranch;
+    } else {
+        return ModelFragmentKind::AltBranch;
+    }
+}
+
+fn extend_path(path : &Vec<u32>, direction : u32) -> Vec<u32> {
+    let mut extended = path.clone();
+    extended.push(direction);
+    return extended;
+}
+
+// ***
+
+/*
+    Collects the original positions of the actions fired along the paths of an analysis
+    which end in a 'Cov' or 'TooShort' local verdict i.e. which account for the analysed trace
+*/
+pub struct AcceptedPathsLogger {
+    fired_from : FiredPaths<Option<Vec<u32>>>,
+    fired_positions : Rc<RefCell<HashSet<Vec<u32>>>>
+}
+
+impl AcceptedPathsLogger {
+    pub fn new(fired_positions : Rc<RefCell<HashSet<Vec<u32>>>>) -> AcceptedPathsLogger {
+        return AcceptedPathsLogger{fired_from:FiredPaths::new(),fired_positions};
+    }
+}
+
+impl ProcessLogger for AcceptedPathsLogger {
+
+    fn log_init(&mut self,
+                _interaction : &Interaction,
+                _gen_ctx : &GeneralContext,
+                _exe_ctx : &ExecutionContext,
+                _remaining_multi_trace : &Option<AnalysableMultiTrace>) {
+        self.fired_from = FiredPaths::new();
+    }
+
+    fn log_resume(&mut self,
+                  _interaction : &Interaction,
+                  _gen_ctx : &GeneralContext,
+                  _exe_ctx : &ExecutionContext,
+                  _remaining_multi_trace : &Option<AnalysableMultiTrace>,
+                  _saved_state : &Vec<String>) -> Result<(),String> {
+        // the paths prior to the interruption are not persisted
+        return Ok(());
+    }
+
+    fn log_checkpoint(&mut self) -> Vec<String> {
+        return Vec::new();
+    }
+
+    fn log_term(&mut self,
+                _options_as_str : &Vec<String>) {}
+
+    fn log_execution(&mut self,
+                     _gen_ctx : &GeneralContext,
+                     parent_state_id : u32,
+                     new_state_id : u32,
+                     _action_position : &Position,
+                     _trace_action : Option<&TraceAction>,
+                     model_action : &ObservableAction,
+                     _new_interaction : &Interaction,
+                     _new_exe_ctx : &ExecutionContext,
+                     _remaining_multi_trace : &Option<AnalysableMultiTrace>) {
+        self.fired_from.add_step( parent_state_id, new_state_id, model_action.original_position.clone() );
+    }
+
+    fn log_verdict(&mut self,
+                   parent_state_id : u32,
+                   verdict : &CoverageVerdict) {
+        match verdict {
+            CoverageVerdict::Cov | CoverageVerdict::TooShort => {
+                let mut fired_positions = self.fired_positions.borrow_mut();
+                for orig_pos_opt in self.fired_from.get_path_to(parent_state_id) {
+                    match orig_pos_opt {
+                        None => {},
+                        Some( orig_pos ) => {
+                            fired_positions.insert( orig_pos.clone() );
+                        }
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+
+    fn log_forget(&mut self,
+                  state_id : u32) {
+        self.fired_from.forget_state(state_id);
+    }
+
+    fn log_filtered(&mut self,
+                    _gen_ctx : &GeneralContext,
+                    _exe_ctx:&ExecutionContext,
+                    _parent_state_id : u32,
+                    _new_state_id : u32,
+                    _action_position : &Position,
+                    _action : &ObservableAction,
+                    _elim_kind : &FilterEliminationKind) {}
+
+    fn log_unsat(&mut self,
+                 _gen_ctx : &GeneralContext,
+                 _exe_ctx:&ExecutionContext,
+                 _parent_state_id : u32,
+                 _new_state_id : u32,
+                 _action_position : &Position,
+                 _trace_action : Option<&TraceAction>,
+                 _model_action : &ObservableAction) {}
+}
@@ -0,0 +1,165 @@
+/********************************************************************************
+ * Copyright (c) 2020 Erwan Mahe (github.com/erwanM974)
+ *
+ * This program and the accompanying materials are made available under the
+ * terms of the Eclipse Public License 2.0 which is available at
+ * http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+ * which is available at https://www.apache.org/licenses/LICENSE-2.0.
+ *
+ * SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+ ********************************************************************************/
+
+use std::collections::{HashSet,BTreeSet};
+
+use crate::core::context::general::GeneralContext;
+use crate::core::syntax::interaction::Interaction;
+use crate::core::syntax::position::Position;
+use crate::core::syntax::action::*;
+use crate::core::semantics::frontier::make_frontier;
+
+use crate::rendering::textual::monochrome::position::position_to_text;
+
+use crate::process::lint::{LintWarning,LintWarningKind,collect_actions,action_to_short_text};
+
+/*
+    Reports the classical pathologies of sequence diagrams :
+        - non-local choices i.e. '@alt' whose branches may start with actions on different lifelines
+          no single lifeline can then decide which branch is taken
+        - races i.e. '@seq' specifying the order of two receptions on a lifeline while nothing orders the corresponding emissions
+    Positions are those of the interaction as written in the model
+*/
+pub fn find_pathologies(interaction : &Interaction, gen_ctx : &GeneralContext) -> Vec<LintWarning> {
+    let mut warnings : Vec<LintWarning> = Vec::new();
+    find_pathologies_at(interaction, gen_ctx, Vec::new(), &mut warnings);
+    return warnings;
+}
+
+fn find_pathologies_at(interaction : &Interaction,
+                       gen_ctx : &GeneralContext,
+                       path : Vec<u32>,
+                       warnings : &mut Vec<LintWarning>) {
+    match interaction {
+        Interaction::Empty | Interaction::Action(_) => {},
+        Interaction::Alt(_,_) => {
+            check_non_local_choice(interaction, gen_ctx, &path, warnings);
+            // nested '@alt' on the right are branches of the same choice
+            let mut current = interaction;
+            let mut current_path = path;
+            while let Interaction::Alt(i1,i2) = current {
+                find_pathologies_at(i1, gen_ctx, extend_path(&current_path,1), warnings);
+                current = i2;
+                current_path = extend_path(&current_path,2);
+            }
+            find_pathologies_at(current, gen_ctx, current_path, warnings);
+        },
+        Interaction::Seq(i1,i2) => {
+            check_races(i1, i2, gen_ctx, &path, warnings);
+            find_pathologies_at(i1, gen_ctx, extend_path(&path,1), warnings);
+            find_pathologies_at(i2, gen_ctx, extend_path(&path,2), warnings);
+        },
+        Interaction::Strict(i1,i2) | Interaction::Par(i1,i2) => {
+            find_pathologies_at(i1, gen_ctx, extend_path(&path,1), warnings);
+            find_pathologies_at(i2, gen_ctx, extend_path(&path,2), warnings);
+        },
+        Interaction::Loop(_,_,i1) | Interaction::Opt(i1) | Interaction::Scope(_,i1) | Interaction::Call(_,_,i1)
+                | Interaction::Neg(_,i1) | Interaction::Assert(_,i1) | Interaction::Break(_,i1) | Interaction::Create(_,i1)
+                | Interaction::Ref(_,i1) => {
+            find_pathologies_at(i1, gen_ctx, extend_path(&path,1), warnings);
+        }
+    }
+}
+
+fn check_non_local_choice(alt_interaction : &Interaction,
+                          gen_ctx : &GeneralContext,
+                          path : &Vec<u32>,
+                          warnings : &mut Vec<LintWarning>) {
+    let mut deciding_lifelines : BTreeSet<usize> = BTreeSet::new();
+    let mut first_actions : Vec<String> = Vec::new();
+    for front_pos in make_frontier(alt_interaction) {
+        let action = alt_interaction.get_sub_interaction(&front_pos).as_leaf();
+        deciding_lifelines.insert( action.occupation_before() );
+        first_actions.push( format!("'{}' at {}", action_to_short_text(action,gen_ctx), original_position_to_text(action)) );
+    }
+    if deciding_lifelines.len() > 1 {
+        let lf_names : Vec<String> = deciding_lifelines.iter().map(|lf_id| gen_ctx.get_lf_name(*lf_id).unwrap()).collect();
+        warnings.push( LintWarning{kind:LintWarningKind::NonLocalChoice,
+            message:format!("the '@alt' at {} is a non-local choice ; its branches start on lifelines {} with {}",
+                            path_to_text(path), lf_names.join(", "), first_actions.join(", "))} );
+    }
+}
+
+fn check_races(before : &Interaction,
+               after : &Interaction,
+               gen_ctx : &GeneralContext,
+               path : &Vec<u32>,
+               warnings : &mut Vec<LintWarning>) {
+    let mut before_actions : Vec<&ObservableAction> = Vec::new();
+    collect_actions(before, &mut before_actions);
+    let mut before_lifelines : HashSet<usize> = HashSet::new();
+    for action in &before_actions {
+        before_lifelines.extend( action.occupation_after() );
+    }
+    // ***
+    for front_pos in make_frontier(after) {
+        let after_action = after.get_sub_interaction(&front_pos).as_leaf();
+        let (after_emitter,after_receivers) = get_emitter_and_receivers(after_action);
+        // if the emitter takes part in what precedes, the emission is ordered after it
+        if let Some(emitter_lf_id) = after_emitter {
+            if before_lifelines.contains(&emitter_lf_id) {
+                continue;
+            }
+        }
+        for before_action in &before_actions {
+            let (before_emitter,before_receivers) = get_emitter_and_receivers(before_action);
+            // two messages from the environment are ordered by the environment itself
+            if before_emitter.is_none() && after_emitter.is_none() {
+                continue;
+            }
+            for lf_id in before_receivers.intersection(&after_receivers) {
+                warnings.push( LintWarning{kind:LintWarningKind::Race,
+                    message:format!("the '@seq' at {} orders the reception of '{}' on '{}' at {} after that of '{}' at {} but nothing orders their emissions",
+                                    path_to_text(path),
+                                    gen_ctx.get_ms_name(after_action.ms_id).unwrap(), gen_ctx.get_lf_name(*lf_id).unwrap(), original_position_to_text(after_action),
+                                    gen_ctx.get_ms_name(before_action.ms_id).unwrap(), original_position_to_text(before_action))} );
+            }
+        }
+    }
+}
+
+// the emitting lifeline, if the message does not come from the environment, and the receiving lifelines
+fn get_emitter_and_receivers(action : &ObservableAction) -> (Option<usize>,HashSet<usize>) {
+    let mut receivers : HashSet<usize> = HashSet::new();
+    match &action.act_kind {
+        ObservableActionKind::Reception => {
+            receivers.insert( action.occupation_before() );
+            return (None,receivers);
+        },
+        ObservableActionKind::Emission( targets ) => {
+            for target in targets {
+                receivers.insert( target.lf_id );
+            }
+            return (Some(action.occupation_before()),receivers);
+        }
+    }
+}
+
+fn extend_path(path : &Vec<u32>, direction : u32) -> Vec<u32> {
+    let mut extended = path.clone();
+    extended.push(direction);
+    return extended;
+}
+
+fn path_to_text(path : &Vec<u32>) -> String {
+    return format!("position {}", position_to_text( &Position::from_vec(&mut path.clone()) ));
+}
+
+fn original_position_to_text(action : &ObservableAction) -> String {
+    match &action.original_position {
+        None => {
+            return "unknown position".to_string();
+        },
+        Some( position ) => {
+            return path_to_text(position);
+        }
+    }
+}
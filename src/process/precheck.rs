@@ -0,0 +1,134 @@
+/********************************************************************************
+ * Copyright (c) 2020 Erwan Mahe (github.com/erwanM974)
+ *
+ * This program and the accompanying materials are made available under the
+ * terms of the Eclipse Public License 2.0 which is available at
+ * http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+ * which is available at https://www.apache.org/licenses/LICENSE-2.0.
+ *
+ * SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+ ********************************************************************************/
+
+use std::collections::btree_map::BTreeMap;
+use std::collections::HashSet;
+use std::cmp::Ordering;
+
+use crate::core::context::general::GeneralContext;
+use crate::core::context::execution::ExecutionContext;
+use crate::core::syntax::action::*;
+use crate::core::syntax::interaction::Interaction;
+use crate::core::syntax::position::Position;
+use crate::core::trace::TraceAction;
+use crate::core::syntax::data::generic::TD_Generic;
+use crate::core::syntax::data::evaluation::*;
+
+/*
+    Cheap check done by HIBOU before asking DIVERSITY to fire a model action against a trace action
+    Only literal values and variables that are concretely known on the lifeline are considered
+    Returns false only if the action certainly cannot match the trace action
+    i.e. if a guard of the preamble is concretely false or if a parameter concretely differs from the trace argument
+    The action is the one at the given position in the interaction, before the '@scope' enclosing it are opened
+    so that the variables of those scopes, which are fresh instances once opened, are not considered
+*/
+pub fn concrete_precheck(gen_ctx : &GeneralContext,
+                         exe_ctx : &ExecutionContext,
+                         interaction : &Interaction,
+                         position : &Position,
+                         trace_action : &TraceAction) -> bool {
+    let model_action = interaction.get_sub_interaction(position).as_leaf();
+    let mut scoped_variables : HashSet<usize> = HashSet::new();
+    collect_scoped_variables_on_path(interaction, position, &mut scoped_variables);
+    // symbols are only known to DIVERSITY
+    let no_symbols : BTreeMap<usize,TD_Generic> = BTreeMap::new();
+    let mut known_interpretation : BTreeMap<usize,TD_Generic> = BTreeMap::new();
+    match exe_ctx.get_lf_interpretation(model_action.lf_act.lf_id) {
+        None => {},
+        Some( got_lfint ) => {
+            for (vr_id,td_gen) in got_lfint {
+                // the value of a clock depends on the delay which is only known to DIVERSITY
+                if !gen_ctx.is_clock(*vr_id) && !scoped_variables.contains(vr_id) {
+                    known_interpretation.insert(*vr_id,td_gen.clone());
+                }
+            }
+        }
+    }
+    // variables assigned in the preamble are no longer known
+    for amble_item in &model_action.lf_act.preamble {
+        match amble_item {
+            ActionAmbleItem::Guard( td_bool ) => {
+                let valuation = Valuation::new(&known_interpretation,&no_symbols);
+                match evaluate_bool(td_bool,&valuation) {
+                    Ok( false ) => {
+                        return false;
+                    },
+                    _ => {}
+                }
+            },
+            ActionAmbleItem::Assignment( vr_id, _ ) => {
+                known_interpretation.remove(vr_id);
+            },
+            ActionAmbleItem::Reset( vr_id ) => {
+                known_interpretation.remove(vr_id);
+            }
+        }
+    }
+    // ***
+    let valuation = Valuation::new(&known_interpretation,&no_symbols);
+    for (param,argument) in model_action.params.iter().zip(trace_action.arguments.iter()) {
+        match param {
+            ValueOrNewFresh::NewFresh => {},
+            ValueOrNewFresh::Value( td_gen ) => {
+                match (evaluate_generic(td_gen,&valuation),evaluate_generic(argument,&valuation)) {
+                    (Ok(TD_Generic::Bool(param_bool)),Ok(TD_Generic::Bool(arg_bool))) => {
+                        if param_bool != arg_bool {
+                            return false;
+                        }
+                    },
+                    (Ok(param_lit),Ok(arg_lit)) => {
+                        match compare_literals(&param_lit,&arg_lit) {
+                            Ok( Ordering::Equal ) => {},
+                            Ok( _ ) => {
+                                return false;
+                            },
+                            Err(_) => {}
+                        }
+                    },
+                    _ => {}
+                }
+            }
+        }
+    }
+    return true;
+}
+
+fn collect_scoped_variables_on_path(interaction : &Interaction,
+                                    position : &Position,
+                                    scoped_variables : &mut HashSet<usize>) {
+    match position {
+        Position::Epsilon => {},
+        Position::Left(sub_pos) => {
+            match interaction {
+                &Interaction::Scope(ref scope, ref i1) => {
+                    scoped_variables.extend( scope.iter().cloned() );
+                    collect_scoped_variables_on_path(&*i1, &*sub_pos, scoped_variables);
+                },
+                &Interaction::Seq(ref i1, _) | &Interaction::Strict(ref i1, _) | &Interaction::Alt(ref i1, _)
+                | &Interaction::Par(ref i1, _) | &Interaction::Loop(_, _, ref i1) | &Interaction::Opt(ref i1)
+                | &Interaction::Neg(_, ref i1) | &Interaction::Assert(_, ref i1) | &Interaction::Break(_, ref i1)
+                | &Interaction::Create(_, ref i1) | &Interaction::Ref(_, ref i1) | &Interaction::Call(_, _, ref i1) => {
+                    collect_scoped_variables_on_path(&*i1, &*sub_pos, scoped_variables);
+                },
+                _ => {}
+            }
+        },
+        Position::Right(sub_pos) => {
+            match interaction {
+                &Interaction::Seq(_, ref i2) | &Interaction::Strict(_, ref i2) | &Interaction::Alt(_, ref i2)
+                | &Interaction::Par(_, ref i2) => {
+                    collect_scoped_variables_on_path(&*i2, &*sub_pos, scoped_variables);
+                },
+                _ => {}
+            }
+        }
+    }
+}
@@ -10,8 +10,9 @@
  ********************************************************************************/
 
 
-use std::collections::HashMap;
+use std::collections::{HashMap,BTreeMap};
 use std::cmp::Reverse;
+use std::time::{Duration,Instant};
 
 use crate::core::context::general::GeneralContext;
 use crate::core::context::execution::ExecutionContext;
@@ -21,31 +22,48 @@ use crate::core::syntax::action::*;
 use crate::core::syntax::position::*;
 use crate::core::trace::{AnalysableMultiTrace,MultiTraceCanal,TraceAction};
 use crate::process::log::ProcessLogger;
-use crate::core::semantics::frontier::make_frontier;
+use crate::core::semantics::frontier::{make_frontier,make_frontier_in_context};
 use crate::core::semantics::shape_execute::shape_execute;
 use crate::process::verdicts::*;
 use crate::process::hibou_process::*;
 use crate::core::trace::*;
 use crate::process::queue::ProcessQueue;
+use crate::process::checkpoint::{ProcessCheckpoint,CheckpointedState};
 
 use crate::process::symbex::model_symbex::*;
 use crate::process::symbex::trace_symbex::*;
 use crate::process::deploy_receptions::deploy_original_action_followup;
+use crate::process::precheck::concrete_precheck;
+use crate::process::concrete_execution::*;
+use crate::core::error::HibouCoreError;
 
 use crate::diversity::symbex_client::SymbexClient;
 use crate::diversity::*;
 
+use rand::{RngCore,SeedableRng};
+use rand_pcg::Pcg32;
+
 pub struct ProcessPriorities {
     pub emission : i32,
     pub reception : i32,
-    pub in_loop : i32
+    pub in_loop : i32,
+    pub env_emission : i32,               // emissions that have no target lifeline
+    pub broadcast : i32,                  // emissions that have several target lifelines
+    pub lifelines : BTreeMap<String,i32>, // key is the lifeline name
+    pub messages : BTreeMap<String,i32>,  // key is the message name
+    pub tags : BTreeMap<String,i32>       // key is the tag, applies to the actions annotated with it
 }
 
 impl ProcessPriorities {
     pub fn new(emission : i32,
                reception : i32,
                in_loop : i32) -> ProcessPriorities {
-        return ProcessPriorities{emission,reception,in_loop};
+        return ProcessPriorities{emission,reception,in_loop,
+            env_emission:0,
+            broadcast:0,
+            lifelines:BTreeMap::new(),
+            messages:BTreeMap::new(),
+            tags:BTreeMap::new()};
     }
 }
 
@@ -54,14 +72,35 @@ impl std::string::ToString for ProcessPriorities {
         let mut my_str = format!("emission={:},",self.emission);
         my_str.push_str( &format!("reception={:},",self.reception));
         my_str.push_str( &format!("in_loop={:}",self.in_loop));
+        if self.env_emission != 0 {
+            my_str.push_str( &format!(",env_emission={:}",self.env_emission));
+        }
+        if self.broadcast != 0 {
+            my_str.push_str( &format!(",broadcast={:}",self.broadcast));
+        }
+        for (lf_name,level) in &self.lifelines {
+            my_str.push_str( &format!(",lifeline({:})={:}",lf_name,level));
+        }
+        for (ms_name,level) in &self.messages {
+            my_str.push_str( &format!(",message({:})={:}",ms_name,level));
+        }
+        for (tag,level) in &self.tags {
+            my_str.push_str( &format!(",tag({:})={:}",tag,level));
+        }
         return my_str;
     }
 }
 
+enum FiringResult {
+    Sat(Interaction,ExecutionContext,u32,Option<AnalysableMultiTrace>,ObservableAction,TraceAction), // new_interaction,new_exe_ctx,new_ec_id,new_multi_trace,shaped_action,trace_action
+    UnSat(ObservableAction,Option<TraceAction>) // shaped_action,trace_action
+}
+
 pub struct HibouProcessManager {
     gen_ctx : GeneralContext,
     strategy : HibouSearchStrategy,
     temporality : HibouProcessTemporality,
+    engine : HibouProcessEngine,
     pre_filters : Vec<HibouPreFilter>,
     // ***
     memorized_states : HashMap<u32,MemorizedState>,
@@ -69,6 +108,13 @@ pub struct HibouProcessManager {
     // ***
     frontier_priorities : ProcessPriorities,
     // ***
+    search_progress : SearchProgress,
+    walk_rng : Option<Pcg32>,
+    // ***
+    start_time : Instant,
+    elapsed_before_resume : Duration, // time spent by the process before it was interrupted, if resumed
+    solver_calls : u32,
+    // ***
     loggers : Vec<Box<dyn ProcessLogger>>
 }
 
@@ -76,13 +122,24 @@ impl HibouProcessManager {
     pub fn new(gen_ctx : GeneralContext,
                strategy : HibouSearchStrategy,
                temporality : HibouProcessTemporality,
+               engine : HibouProcessEngine,
                pre_filters : Vec<HibouPreFilter>,
                memorized_states : HashMap<u32,MemorizedState>,
                process_queue : ProcessQueue,
                frontier_priorities : ProcessPriorities,
                loggers : Vec<Box<dyn ProcessLogger>>
     ) -> HibouProcessManager {
-        return HibouProcessManager{gen_ctx,strategy,temporality,pre_filters,memorized_states,process_queue,frontier_priorities,loggers};
+        let search_progress = SearchProgress::new(&strategy);
+        let walk_rng : Option<Pcg32>;
+        match &strategy {
+            HibouSearchStrategy::RandomWalk(seed,_) => {
+                walk_rng = Some( Pcg32::seed_from_u64(*seed) );
+            },
+            _ => {
+                walk_rng = None;
+            }
+        }
+        return HibouProcessManager{gen_ctx,strategy,temporality,engine,pre_filters,memorized_states,process_queue,frontier_priorities,search_progress,walk_rng,start_time:Instant::now(),elapsed_before_resume:Duration::from_secs(0),solver_calls:0,loggers};
     }
 
     pub fn get_options_as_strings(&self,goal_and_verdict:Option<(&GlobalVerdict,&GlobalVerdict)>) -> Vec<String> {
@@ -98,6 +155,7 @@ impl HibouProcessManager {
             }
         }
         options_str.push( format!("temporality={}", &self.temporality.to_string()) );
+        options_str.push( format!("engine={}", &self.engine.to_string()) );
         options_str.push( format!("strategy={}", &self.strategy.to_string()) );
         options_str.push( format!("frontier_priorities=[{}]", &self.frontier_priorities.to_string()) );
         {
@@ -133,6 +191,25 @@ impl HibouProcessManager {
         }
     }
 
+    pub fn resume_loggers(&mut self,
+                          exe_ctx : &ExecutionContext,
+                          interaction : &Interaction,
+                          remaining_multi_trace : &Option<AnalysableMultiTrace>,
+                          saved_states : &Vec<Vec<String>>) -> Result<(),HibouCoreError> {
+        let no_saved_state : Vec<String> = Vec::new();
+        for (logger_index,logger) in self.loggers.iter_mut().enumerate() {
+            // a logger which saved nothing has no line in the checkpoint
+            let saved_state = saved_states.get(logger_index).unwrap_or(&no_saved_state);
+            match (*logger).log_resume(interaction, &self.gen_ctx, exe_ctx, remaining_multi_trace, saved_state) {
+                Err(e) => {
+                    return Err( HibouCoreError::UnresumableLogger(logger_index,e) );
+                },
+                Ok(_) => {}
+            }
+        }
+        return Ok(());
+    }
+
     pub fn verdict_loggers(&mut self,
                            verdict : &CoverageVerdict,
                            parent_state_id : u32) {
@@ -206,12 +283,160 @@ impl HibouProcessManager {
 
     pub fn forget_state(&mut self, id:u32) {
         self.memorized_states.remove(&id);
+        for logger in self.loggers.iter_mut() {
+            logger.log_forget(id);
+        }
     }
 
     pub fn remember_state(&mut self, id:u32, state:MemorizedState) {
         self.memorized_states.insert( id, state );
     }
 
+    pub fn make_checkpoint(&mut self,
+                           next_state_id : u32,
+                           node_counter : u32,
+                           global_verdict : Option<GlobalVerdict>) -> ProcessCheckpoint {
+        let mut states : Vec<CheckpointedState> = Vec::new();
+        for (state_id,memo_state) in &self.memorized_states {
+            states.push( CheckpointedState{state_id:*state_id,
+                path:memo_state.path.clone(),
+                remaining_ids_to_process:memo_state.remaining_ids_to_process.clone(),
+                loop_depth:memo_state.loop_depth,
+                depth:memo_state.depth} );
+        }
+        states.sort_by_key(|st| st.state_id);
+        let queue = self.process_queue.get_items().clone();
+        let search_progress = self.search_progress.clone();
+        let mut loggers : Vec<Vec<String>> = Vec::new();
+        for logger in self.loggers.iter_mut() {
+            loggers.push( (*logger).log_checkpoint() );
+        }
+        let elapsed_millis = self.get_elapsed_time().as_millis() as u64;
+        return ProcessCheckpoint{next_state_id,node_counter,global_verdict,search_progress,elapsed_millis,solver_calls:self.solver_calls,states,queue,loggers};
+    }
+
+    /*
+        Reconstructs the memorized states and the queue from a checkpoint
+        DIVERSITY execution contexts are not persistent
+        so the path leading to each memorized state is fired anew from the initial one
+    */
+    pub async fn restore_from_checkpoint(&mut self,
+                                         client : &mut Option<SymbexClient<tonic::transport::Channel>>,
+                                         initial_interaction : &Interaction,
+                                         initial_exe_ctx : &ExecutionContext,
+                                         initial_diversity_ec_id : u32,
+                                         initial_multi_trace : &Option<AnalysableMultiTrace>,
+                                         checkpoint : &ProcessCheckpoint) -> Result<(),HibouCoreError> {
+        // the model or the trace may have changed since the checkpoint was made
+        for ckpt_state in &checkpoint.states {
+            let mut interaction = initial_interaction.clone();
+            let mut exe_ctx = initial_exe_ctx.clone();
+            let mut diversity_ec_id = initial_diversity_ec_id;
+            let mut multi_trace = initial_multi_trace.clone();
+            for position in &ckpt_state.path {
+                if !is_replayable(&interaction,&exe_ctx,&multi_trace,position) {
+                    return Err( HibouCoreError::UnreplayableCheckpoint(ckpt_state.state_id, format!("position {} cannot be executed", position.as_text())) );
+                }
+                match self.fire_position(client,&interaction,&exe_ctx,diversity_ec_id,&multi_trace,position).await {
+                    Err(e) => {
+                        return Err( HibouCoreError::UnreplayableCheckpoint(ckpt_state.state_id, format!("execution of position {} failed : {}", position.as_text(), e.to_string())) );
+                    },
+                    Ok( FiringResult::UnSat(_,_) ) => {
+                        return Err( HibouCoreError::UnreplayableCheckpoint(ckpt_state.state_id, format!("execution of position {} is not satisfiable", position.as_text())) );
+                    },
+                    Ok( FiringResult::Sat(new_interaction,new_exe_ctx,new_diversity_ec_id,new_multi_trace,_,_) ) => {
+                        interaction = new_interaction;
+                        exe_ctx = new_exe_ctx;
+                        diversity_ec_id = new_diversity_ec_id;
+                        multi_trace = new_multi_trace;
+                    }
+                }
+            }
+            let memo_state = MemorizedState::new(interaction,
+                                                 exe_ctx,
+                                                 diversity_ec_id,
+                                                 multi_trace,
+                                                 ckpt_state.remaining_ids_to_process.clone(),
+                                                 ckpt_state.path.clone(),
+                                                 ckpt_state.loop_depth,
+                                                 ckpt_state.depth);
+            self.remember_state(ckpt_state.state_id, memo_state);
+        }
+        for to_process in &checkpoint.queue {
+            self.process_queue.insert_item_right(to_process.clone());
+        }
+        self.restore_search_progress(&checkpoint.search_progress);
+        // the budgets of the process are those left at the time of the checkpoint
+        // and the firings done to replay the paths do not count against them
+        self.solver_calls = checkpoint.solver_calls;
+        self.elapsed_before_resume = Duration::from_millis(checkpoint.elapsed_millis);
+        self.start_time = Instant::now();
+        return Ok(());
+    }
+
+    fn get_elapsed_time(&self) -> Duration {
+        return self.elapsed_before_resume + self.start_time.elapsed();
+    }
+
+    fn restore_search_progress(&mut self, search_progress : &SearchProgress) {
+        self.search_progress = search_progress.clone();
+        // the random number generator is brought back to where it was by drawing as many numbers as before
+        match self.walk_rng.as_mut() {
+            None => {},
+            Some( rng ) => {
+                for _ in 0..search_progress.rng_draws {
+                    rng.next_u32();
+                }
+            }
+        }
+    }
+
+    /*
+        Called once the queue is empty
+        Returns true if the strategy requires the process to start anew from the initial state
+    */
+    pub fn start_next_iteration(&mut self) -> bool {
+        match &self.strategy {
+            HibouSearchStrategy::IterativeDeepening(step) => {
+                if self.search_progress.depth_limit_hit {
+                    self.search_progress.depth_limit = Some( self.search_progress.depth_limit.unwrap() + step );
+                    self.search_progress.depth_limit_hit = false;
+                    return true;
+                } else {
+                    return false;
+                }
+            },
+            HibouSearchStrategy::RandomWalk(_,walks) => {
+                self.search_progress.completed_walks = self.search_progress.completed_walks + 1;
+                return self.search_progress.completed_walks < *walks;
+            },
+            _ => {
+                return false;
+            }
+        }
+    }
+
+    /*
+        During a random walk, once a step has been taken from a state
+        the other steps that could have been taken from it are abandoned
+    */
+    pub fn abandon_siblings_after_step(&mut self, parent_state_id : u32, parent_state : &mut MemorizedState) {
+        match &self.strategy {
+            HibouSearchStrategy::RandomWalk(_,_) => {
+                for sibling in self.process_queue.remove_items_of_state(parent_state_id) {
+                    parent_state.remaining_ids_to_process.remove(&sibling.id_as_child);
+                }
+            },
+            _ => {}
+        }
+    }
+
+    fn draw_random_index(&mut self, bound : usize) -> usize {
+        self.search_progress.rng_draws = self.search_progress.rng_draws + 1;
+        let drawn = self.walk_rng.as_mut().unwrap().next_u32();
+        return (drawn as usize) % bound;
+    }
+
     pub fn extract_from_queue(&mut self) -> Option<NextToProcess> {
         return self.process_queue.get_next();
     }
@@ -225,12 +450,37 @@ impl HibouProcessManager {
                     // ***
                     let parent_state = self.get_memorized_state(state_id).unwrap();
                     let front_act = (parent_state.interaction).get_sub_interaction(&front_pos).as_leaf();
-                    match front_act.act_kind {
+                    match &front_act.act_kind {
                         ObservableActionKind::Reception => {
                             priority = priority + self.frontier_priorities.reception;
                         },
-                        ObservableActionKind::Emission(_) => {
+                        ObservableActionKind::Emission(ref targets) => {
                             priority = priority + self.frontier_priorities.emission;
+                            if targets.len() == 0 {
+                                priority = priority + self.frontier_priorities.env_emission;
+                            } else if targets.len() > 1 {
+                                priority = priority + self.frontier_priorities.broadcast;
+                            }
+                        }
+                    }
+                    match self.frontier_priorities.lifelines.get( &self.gen_ctx.get_lf_name(front_act.lf_act.lf_id).unwrap() ) {
+                        None => {},
+                        Some( level ) => {
+                            priority = priority + level;
+                        }
+                    }
+                    match self.frontier_priorities.messages.get( &self.gen_ctx.get_ms_name(front_act.ms_id).unwrap() ) {
+                        None => {},
+                        Some( level ) => {
+                            priority = priority + level;
+                        }
+                    }
+                    for tag_id in &front_act.tags {
+                        match self.frontier_priorities.tags.get( &self.gen_ctx.get_tag(*tag_id).unwrap() ) {
+                            None => {},
+                            Some( level ) => {
+                                priority = priority + level;
+                            }
                         }
                     }
                     let loop_depth = (parent_state.interaction).get_loop_depth_at_pos(&front_pos);
@@ -256,23 +506,26 @@ impl HibouProcessManager {
             let mut keys : Vec<i32> = to_enqueue_reorganize.keys().cloned().collect();
             keys.sort_by_key(|k| Reverse(*k));
             for k in keys {
-                match to_enqueue_reorganize.get_mut(&k) {
+                match to_enqueue_reorganize.remove(&k) {
                     None => {},
-                    Some( queue ) => {
-                        to_enqueue_reorganized.append( queue );
+                    Some( mut queue ) => {
+                        if self.walk_rng.is_some() {
+                            self.shuffle_same_priority(&mut queue);
+                        }
+                        to_enqueue_reorganized.append( &mut queue );
                     }
                 }
             }
         }
         // ***
         match &self.strategy {
-            &HibouSearchStrategy::DFS => {
-                to_enqueue_reorganized.reverse();
+            &HibouSearchStrategy::BFS => {
                 for (child_id,child_kind) in to_enqueue_reorganized {
                     self.enqueue_child_node(state_id,child_id,child_kind);
                 }
             },
-            &HibouSearchStrategy::BFS => {
+            _ => {
+                to_enqueue_reorganized.reverse();
                 for (child_id,child_kind) in to_enqueue_reorganized {
                     self.enqueue_child_node(state_id,child_id,child_kind);
                 }
@@ -280,155 +533,109 @@ impl HibouProcessManager {
         }
     }
 
+    /*
+        Shuffles the children of the same priority level
+        so that priorities still apply during random walks
+    */
+    fn shuffle_same_priority(&mut self, same_priority : &mut Vec<(u32,NextToProcessKind)>) {
+        let mut remaining = same_priority.len();
+        while remaining > 1 {
+            let drawn = self.draw_random_index(remaining);
+            remaining = remaining - 1;
+            same_priority.swap(drawn,remaining);
+        }
+    }
+
+    fn get_remaining_trace_length(&self, state_id : u32) -> usize {
+        match self.get_memorized_state(state_id) {
+            None => {
+                return 0;
+            },
+            Some( memo_state ) => {
+                match &memo_state.multi_trace {
+                    None => {
+                        return 0;
+                    },
+                    Some( multi_trace ) => {
+                        return multi_trace.length();
+                    }
+                }
+            }
+        }
+    }
+
     fn enqueue_child_node(&mut self,state_id: u32,child_id:u32,child_kind:NextToProcessKind) {
         let child = NextToProcess::new(state_id,child_id,child_kind);
         match &(self.strategy) {
-            &HibouSearchStrategy::DFS => {
-                self.process_queue.insert_item_left(child);
-            },
             &HibouSearchStrategy::BFS => {
                 self.process_queue.insert_item_right(child);
+            },
+            &HibouSearchStrategy::BestFS => {
+                // the queue is kept sorted by increasing length of remaining multi-trace
+                // nodes with the same length are processed in a depth first manner
+                let child_score = self.get_remaining_trace_length(state_id);
+                let mut index : usize = 0;
+                for queued in self.process_queue.get_items() {
+                    if self.get_remaining_trace_length(queued.state_id) >= child_score {
+                        break;
+                    }
+                    index = index + 1;
+                }
+                self.process_queue.insert_item_at(index,child);
+            },
+            _ => {
+                self.process_queue.insert_item_left(child);
             }
         }
     }
 
     pub async fn process_next(&mut self,
-                              client : &mut SymbexClient<tonic::transport::Channel>,
+                              client : &mut Option<SymbexClient<tonic::transport::Channel>>,
                         parent_state : &MemorizedState,
                         to_process   : &NextToProcess,
                         new_state_id : u32,
-                        node_counter : u32) -> Option<(Interaction,ExecutionContext,u32,Option<AnalysableMultiTrace>,u32,u32)> {
+                        node_counter : u32) -> Result<Option<(Interaction,ExecutionContext,u32,Option<AnalysableMultiTrace>,u32,u32)>,HibouCoreError> {
         match &(to_process.kind) {
             &NextToProcessKind::Execute( ref position ) => {
                 let new_depth = parent_state.depth + 1;
                 let new_loop_depth = parent_state.loop_depth + (parent_state.interaction).get_loop_depth_at_pos(position);
                 // ***
-                match self.apply_pre_filters(new_depth,new_loop_depth,node_counter) {
+                let mut elimination = self.apply_pre_filters(new_depth,new_loop_depth,node_counter,&parent_state.exe_ctx);
+                if elimination.is_none() && !self.passes_concrete_precheck(parent_state,position) {
+                    elimination = Some( FilterEliminationKind::ConcreteMismatch );
+                }
+                match elimination {
                     None => {
-                        let mut new_exe_ctx = parent_state.exe_ctx.clone();
-                        match shape_execute(&self.gen_ctx,&mut new_exe_ctx,&parent_state.interaction,position) {
-                            Err(e) => {
-                                panic!("{:?}",e);
+                        match self.fire_position(client,
+                                                 &parent_state.interaction,
+                                                 &parent_state.exe_ctx,
+                                                 parent_state.diversity_ec_id,
+                                                 &parent_state.multi_trace,
+                                                 position).await? {
+                            FiringResult::UnSat( shaped_action, trace_action_opt ) => {
+                                self.unsat_loggers(&position,
+                                                   &shaped_action,
+                                                   trace_action_opt.as_ref(),
+                                                   to_process.state_id,
+                                                   new_state_id);
+                                return Ok(None);
                             },
-                            Ok( (shaped_interaction,shaped_position,shaped_action,needs_scoping) ) => {
-                                match model_symbolic_execution(client,
-                                                               &self.gen_ctx,
-                                                               &mut new_exe_ctx,
-                                                               &shaped_action,
-                                                               parent_state.diversity_ec_id,
-                                                               needs_scoping,
-                                                               &self.temporality).await {
-                                    ModelSymbexResult::UnSat => {
-                                        self.unsat_loggers(&position,
-                                                           &shaped_action,
-                                                           None,
-                                                           to_process.state_id,
-                                                           new_state_id);
-                                        return None;
-                                    },
-                                    ModelSymbexResult::Sat( new_diversity_ec_id,
-                                                            model_firing_conditions,
-                                                            effective_parameters,
-                                                            opt_delay ) => {
-                                        // ***
-                                        match (parent_state.multi_trace).as_ref(){
-                                            None => {
-                                                let new_interaction = deploy_original_action_followup(&new_exe_ctx,
-                                                                                                  &shaped_interaction,
-                                                                                                  &shaped_position,
-                                                                                                  &shaped_action,
-                                                                                                  &effective_parameters);
-                                                // ***
-                                                let trace_act_kind : TraceActionKind;
-                                                match &shaped_action.act_kind {
-                                                    ObservableActionKind::Reception => {
-                                                        trace_act_kind = TraceActionKind::Reception;
-                                                    },
-                                                    ObservableActionKind::Emission(_) => {
-                                                        trace_act_kind = TraceActionKind::Emission;
-                                                    }
-                                                }
-                                                let trace_action = TraceAction{ delay:opt_delay,
-                                                    lf_id:shaped_action.lf_act.lf_id,
-                                                    ms_id:shaped_action.ms_id,
-                                                    act_kind:trace_act_kind,
-                                                    arguments:effective_parameters};
-                                                // ***
-                                                self.execution_loggers(&position,
-                                                                       &shaped_action,
-                                                                       Some(&trace_action),
-                                                                       &new_interaction,
-                                                                       &new_exe_ctx,
-                                                                       to_process.state_id,
-                                                                       new_state_id,
-                                                                       &None);
-                                                // ***
-                                                return Some( (new_interaction,new_exe_ctx,new_diversity_ec_id,None,new_depth,new_loop_depth) );
-                                            },
-                                            Some( ref multi_trace ) => {
-                                                let new_multi_trace : Option<AnalysableMultiTrace>;
-                                                let mut head_trace_action_opt : Option<TraceAction> = None;
-                                                {
-                                                    let mut new_canals : Vec<MultiTraceCanal> = Vec::new();
-                                                    for canal in &multi_trace.canals {
-                                                        if canal.lifelines.contains(&shaped_action.occupation_before()) {
-                                                            let mut new_trace = canal.trace.clone();
-                                                            head_trace_action_opt = Some(new_trace.remove(0));
-                                                            new_canals.push( MultiTraceCanal{lifelines:canal.lifelines.clone(),trace:new_trace} )
-                                                        } else {
-                                                            new_canals.push(canal.clone());
-                                                        }
-                                                    }
-                                                    new_multi_trace = Some( AnalysableMultiTrace::new(new_canals) );
-                                                }
-                                                let head_trace_action = head_trace_action_opt.unwrap();
-                                                // ***
-                                                match trace_symbolic_execution(client,
-                                                                               &self.gen_ctx,
-                                                                               &mut new_exe_ctx,
-                                                                               shaped_action.lf_act.lf_id,
-                                                                               shaped_action.ms_id,
-                                                                               &head_trace_action.arguments,
-                                                                               &head_trace_action.delay,
-                                                                               &self.temporality,
-                                                                               new_diversity_ec_id).await {
-                                                    TraceSymbexResult::UnSat(trace_firing_conditions) => {
-                                                        self.unsat_loggers(&position,
-                                                                           &shaped_action,
-                                                                           Some(&head_trace_action),
-                                                                           to_process.state_id,
-                                                                           new_state_id);
-                                                        return None;
-                                                    },
-                                                    TraceSymbexResult::Sat(post_trace_analysis_diversity_ec_id,trace_firing_condition) => {
-                                                        let post_trace_analysis_interaction = deploy_original_action_followup(&new_exe_ctx,
-                                                                                                                              &shaped_interaction,
-                                                                                                                              &shaped_position,
-                                                                                                                              &shaped_action,
-                                                                                                                              &head_trace_action.arguments);
-                                                        // ***
-                                                        self.execution_loggers(&position,
-                                                                               &shaped_action,
-                                                                               Some(&head_trace_action),
-                                                                               &post_trace_analysis_interaction,
-                                                                               &new_exe_ctx,
-                                                                               to_process.state_id,
-                                                                               new_state_id,
-                                                                               &new_multi_trace);
-                                                        // ***
-                                                        return Some( (post_trace_analysis_interaction,
-                                                                      new_exe_ctx,
-                                                                      post_trace_analysis_diversity_ec_id,
-                                                                      new_multi_trace,
-                                                                      new_depth,
-                                                                      new_loop_depth) );
-                                                    }
-                                                }
-                                                // ***
-                                            }
-                                        }
-                                    }
-                                }
+                            FiringResult::Sat( new_interaction,
+                                               new_exe_ctx,
+                                               new_diversity_ec_id,
+                                               new_multi_trace,
+                                               shaped_action,
+                                               trace_action ) => {
+                                self.execution_loggers(&position,
+                                                       &shaped_action,
+                                                       Some(&trace_action),
+                                                       &new_interaction,
+                                                       &new_exe_ctx,
+                                                       to_process.state_id,
+                                                       new_state_id,
+                                                       &new_multi_trace);
+                                // ***
+                                return Ok( Some( (new_interaction,new_exe_ctx,new_diversity_ec_id,new_multi_trace,new_depth,new_loop_depth) ) );
                             }
                         }
                     },
@@ -439,17 +646,244 @@ impl HibouProcessManager {
                                               to_process.state_id,
                                               new_state_id,
                                               &elim_kind);
-                        return None;
+                        return Ok(None);
                     }
                 }
             },
             _ => {
-                return None;
+                return Ok(None);
+            }
+        }
+    }
+
+    /*
+        During an analysis, checks that the action at the given position
+        does not concretely contradict the head of the corresponding trace component
+        so as to spare the solver calls
+    */
+    fn passes_concrete_precheck(&self, parent_state : &MemorizedState, position : &Position) -> bool {
+        match &parent_state.multi_trace {
+            None => {
+                return true;
+            },
+            Some( multi_trace ) => {
+                let model_action = (parent_state.interaction).get_sub_interaction(position).as_leaf();
+                for canal in &multi_trace.canals {
+                    if canal.lifelines.contains(&model_action.occupation_before()) {
+                        match canal.trace.get(0) {
+                            None => {
+                                return true;
+                            },
+                            Some( head_trace_action ) => {
+                                return concrete_precheck(&self.gen_ctx,&parent_state.exe_ctx,&parent_state.interaction,position,head_trace_action);
+                            }
+                        }
+                    }
+                }
+                return true;
+            }
+        }
+    }
+
+    /*
+        Executes the action at the given position
+        both in HIBOU and, with the symbolic engine, in DIVERSITY
+        without notifying the loggers
+    */
+    async fn fire_position(&mut self,
+                           client : &mut Option<SymbexClient<tonic::transport::Channel>>,
+                           interaction : &Interaction,
+                           exe_ctx : &ExecutionContext,
+                           diversity_ec_id : u32,
+                           multi_trace : &Option<AnalysableMultiTrace>,
+                           position : &Position) -> Result<FiringResult,HibouCoreError> {
+        match &self.engine {
+            HibouProcessEngine::Concrete => {
+                return self.fire_position_concretely(interaction,exe_ctx,diversity_ec_id,multi_trace,position);
+            },
+            HibouProcessEngine::Symbolic => {}
+        }
+        let client = client.as_mut().unwrap();
+        let mut new_exe_ctx = exe_ctx.clone();
+        match shape_execute(&self.gen_ctx,&mut new_exe_ctx,interaction,position) {
+            Err(e) => {
+                panic!("{:?}",e);
+            },
+            Ok( (shaped_interaction,shaped_position,shaped_action,needs_scoping) ) => {
+                self.solver_calls = self.solver_calls + 1;
+                match model_symbolic_execution(client,
+                                               &self.gen_ctx,
+                                               &mut new_exe_ctx,
+                                               &shaped_action,
+                                               diversity_ec_id,
+                                               needs_scoping,
+                                               &self.temporality).await {
+                    ModelSymbexResult::UnSat => {
+                        return Ok( FiringResult::UnSat(shaped_action,None) );
+                    },
+                    ModelSymbexResult::Sat( new_diversity_ec_id,
+                                            model_firing_conditions,
+                                            effective_parameters,
+                                            opt_delay ) => {
+                        // ***
+                        match multi_trace.as_ref() {
+                            None => {
+                                let new_interaction = deploy_original_action_followup(&new_exe_ctx,
+                                                                                      &shaped_interaction,
+                                                                                      &shaped_position,
+                                                                                      &shaped_action,
+                                                                                      &effective_parameters);
+                                // ***
+                                let trace_act_kind : TraceActionKind;
+                                match &shaped_action.act_kind {
+                                    ObservableActionKind::Reception => {
+                                        trace_act_kind = TraceActionKind::Reception;
+                                    },
+                                    ObservableActionKind::Emission(_) => {
+                                        trace_act_kind = TraceActionKind::Emission;
+                                    }
+                                }
+                                let trace_action = TraceAction{ delay:opt_delay,
+                                    lf_id:shaped_action.lf_act.lf_id,
+                                    ms_id:shaped_action.ms_id,
+                                    act_kind:trace_act_kind,
+                                    com_kind:shaped_action.com_kind.clone(),
+                                    arguments:effective_parameters};
+                                // ***
+                                return Ok( FiringResult::Sat(new_interaction,new_exe_ctx,new_diversity_ec_id,None,shaped_action,trace_action) );
+                            },
+                            Some( ref multi_trace ) => {
+                                let new_multi_trace : Option<AnalysableMultiTrace>;
+                                let mut head_trace_action_opt : Option<TraceAction> = None;
+                                {
+                                    let mut new_canals : Vec<MultiTraceCanal> = Vec::new();
+                                    for canal in &multi_trace.canals {
+                                        if canal.lifelines.contains(&shaped_action.occupation_before()) {
+                                            let mut new_trace = canal.trace.clone();
+                                            head_trace_action_opt = Some(new_trace.remove(0));
+                                            new_canals.push( MultiTraceCanal{lifelines:canal.lifelines.clone(),trace:new_trace} )
+                                        } else {
+                                            new_canals.push(canal.clone());
+                                        }
+                                    }
+                                    new_multi_trace = Some( AnalysableMultiTrace::new(new_canals) );
+                                }
+                                let head_trace_action = head_trace_action_opt.unwrap();
+                                // ***
+                                self.solver_calls = self.solver_calls + 1;
+                                match trace_symbolic_execution(client,
+                                                               &self.gen_ctx,
+                                                               &mut new_exe_ctx,
+                                                               shaped_action.lf_act.lf_id,
+                                                               shaped_action.ms_id,
+                                                               &shaped_action.com_kind,
+                                                               &head_trace_action.arguments,
+                                                               &head_trace_action.delay,
+                                                               &self.temporality,
+                                                               new_diversity_ec_id).await {
+                                    TraceSymbexResult::UnSat(trace_firing_conditions) => {
+                                        return Ok( FiringResult::UnSat(shaped_action,Some(head_trace_action)) );
+                                    },
+                                    TraceSymbexResult::Sat(post_trace_analysis_diversity_ec_id,trace_firing_condition) => {
+                                        let post_trace_analysis_interaction = deploy_original_action_followup(&new_exe_ctx,
+                                                                                                              &shaped_interaction,
+                                                                                                              &shaped_position,
+                                                                                                              &shaped_action,
+                                                                                                              &head_trace_action.arguments);
+                                        // ***
+                                        return Ok( FiringResult::Sat(post_trace_analysis_interaction,
+                                                                 new_exe_ctx,
+                                                                 post_trace_analysis_diversity_ec_id,
+                                                                 new_multi_trace,
+                                                                 shaped_action,
+                                                                 head_trace_action) );
+                                    }
+                                }
+                                // ***
+                            }
+                        }
+                    }
+                }
             }
         }
     }
 
-    fn apply_pre_filters(&self, depth : u32, loop_depth : u32, node_counter : u32) -> Option<FilterEliminationKind> {
+    /*
+        Executes the action at the given position only in HIBOU
+        guards, assignments and parameters being concretely evaluated
+        The DIVERSITY execution context id is left as is
+    */
+    fn fire_position_concretely(&self,
+                                interaction : &Interaction,
+                                exe_ctx : &ExecutionContext,
+                                diversity_ec_id : u32,
+                                multi_trace : &Option<AnalysableMultiTrace>,
+                                position : &Position) -> Result<FiringResult,HibouCoreError> {
+        let mut new_exe_ctx = exe_ctx.clone();
+        let (shaped_interaction,shaped_position,shaped_action,_) = shape_execute(&self.gen_ctx,&mut new_exe_ctx,interaction,position)?;
+        // ***
+        let mut new_multi_trace : Option<AnalysableMultiTrace> = None;
+        let mut head_trace_action_opt : Option<TraceAction> = None;
+        match multi_trace.as_ref() {
+            None => {},
+            Some( ref multi_trace ) => {
+                let mut new_canals : Vec<MultiTraceCanal> = Vec::new();
+                for canal in &multi_trace.canals {
+                    if canal.lifelines.contains(&shaped_action.occupation_before()) {
+                        let mut new_trace = canal.trace.clone();
+                        head_trace_action_opt = Some(new_trace.remove(0));
+                        new_canals.push( MultiTraceCanal{lifelines:canal.lifelines.clone(),trace:new_trace} )
+                    } else {
+                        new_canals.push(canal.clone());
+                    }
+                }
+                new_multi_trace = Some( AnalysableMultiTrace::new(new_canals) );
+            }
+        }
+        // ***
+        match concrete_execution(&self.gen_ctx,&mut new_exe_ctx,&shaped_action,head_trace_action_opt.as_ref())? {
+            ConcreteExecutionResult::UnSat => {
+                return Ok( FiringResult::UnSat(shaped_action,head_trace_action_opt) );
+            },
+            ConcreteExecutionResult::Sat( effective_parameters ) => {
+                let new_interaction = deploy_original_action_followup(&new_exe_ctx,
+                                                                      &shaped_interaction,
+                                                                      &shaped_position,
+                                                                      &shaped_action,
+                                                                      &effective_parameters);
+                let trace_action : TraceAction;
+                match head_trace_action_opt {
+                    Some( head_trace_action ) => {
+                        trace_action = head_trace_action;
+                    },
+                    None => {
+                        let trace_act_kind : TraceActionKind;
+                        match &shaped_action.act_kind {
+                            ObservableActionKind::Reception => {
+                                trace_act_kind = TraceActionKind::Reception;
+                            },
+                            ObservableActionKind::Emission(_) => {
+                                trace_act_kind = TraceActionKind::Emission;
+                            }
+                        }
+                        trace_action = TraceAction{ delay:None,
+                            lf_id:shaped_action.lf_act.lf_id,
+                            ms_id:shaped_action.ms_id,
+                            act_kind:trace_act_kind,
+                            com_kind:shaped_action.com_kind.clone(),
+                            arguments:effective_parameters};
+                    }
+                }
+                return Ok( FiringResult::Sat(new_interaction,new_exe_ctx,diversity_ec_id,new_multi_trace,shaped_action,trace_action) );
+            }
+        }
+    }
+
+    fn apply_pre_filters(&mut self,
+                         depth : u32,
+                         loop_depth : u32,
+                         node_counter : u32,
+                         parent_exe_ctx : &ExecutionContext) -> Option<FilterEliminationKind> {
         for pre_filter in &self.pre_filters {
             match pre_filter {
                 HibouPreFilter::MaxProcessDepth( max_depth ) => {
@@ -466,6 +900,35 @@ impl HibouProcessManager {
                     if node_counter >= *max_node_number {
                         return Some( FilterEliminationKind::MaxNodeNumber );
                     }
+                },
+                HibouPreFilter::MaxTime( max_seconds ) => {
+                    if self.get_elapsed_time().as_secs() >= (*max_seconds as u64) {
+                        return Some( FilterEliminationKind::MaxTime );
+                    }
+                },
+                HibouPreFilter::MaxSolverCalls( max_calls ) => {
+                    if self.solver_calls >= *max_calls {
+                        return Some( FilterEliminationKind::MaxSolverCalls );
+                    }
+                },
+                HibouPreFilter::MaxSymbols( max_symbols ) => {
+                    if parent_exe_ctx.get_sy_num() > (*max_symbols as usize) {
+                        return Some( FilterEliminationKind::MaxSymbols );
+                    }
+                },
+                HibouPreFilter::MaxPathConditionSize( max_size ) => {
+                    if parent_exe_ctx.get_path_condition().get_size() > (*max_size as usize) {
+                        return Some( FilterEliminationKind::MaxPathConditionSize );
+                    }
+                }
+            }
+        }
+        match self.search_progress.depth_limit {
+            None => {},
+            Some( depth_limit ) => {
+                if depth > depth_limit {
+                    self.search_progress.depth_limit_hit = true;
+                    return Some( FilterEliminationKind::MaxProcessDepth );
                 }
             }
         }
@@ -473,19 +936,58 @@ impl HibouProcessManager {
     }
 
     pub fn get_coverage_verdict(&self,interaction:&Interaction,multi_trace:&AnalysableMultiTrace) -> CoverageVerdict {
+        if interaction.exhibits_negative() {
+            return CoverageVerdict::Neg;
+        }
         if multi_trace.length() == 0 {
             if interaction.express_empty() {
                 return CoverageVerdict::Cov;
+            } else if interaction.is_within_neg() {
+                // the prefix of a forbidden behaviour is not the prefix of an accepted one
+                return CoverageVerdict::Out;
             } else {
                 return CoverageVerdict::TooShort;
             }
         } else {
             if multi_trace.is_any_component_empty() {
                 return CoverageVerdict::LackObs;
+            } else if interaction.is_within_assert() {
+                return CoverageVerdict::AssertOut;
             } else {
                 return CoverageVerdict::Out;
             }
         }
     }
 
-}
\ No newline at end of file
+}
+/*
+    Whether the action at the given position can be executed in the same manner as during the process
+    i.e. it is in the frontier and, when analysing, it matches the head of one of the trace components
+*/
+fn is_replayable(interaction : &Interaction,
+                 exe_ctx : &ExecutionContext,
+                 multi_trace : &Option<AnalysableMultiTrace>,
+                 position : &Position) -> bool {
+    if !make_frontier_in_context(interaction,exe_ctx).contains(position) {
+        return false;
+    }
+    match multi_trace {
+        None => {
+            return true;
+        },
+        Some( multi_trace ) => {
+            let front_act = interaction.get_sub_interaction(position).as_leaf();
+            for canal in &multi_trace.canals {
+                match canal.trace.get(0) {
+                    None => {},
+                    Some( head_act ) => {
+                        if head_act.is_signature_match(front_act) {
+                            return true;
+                        }
+                    }
+                }
+            }
+            return false;
+        }
+    }
+}
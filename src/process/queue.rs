@@ -29,6 +29,28 @@ impl ProcessQueue {
         self.queue.push(node);
     }
 
+    pub fn insert_item_at(&mut self,index:usize,node:NextToProcess) {
+        self.queue.insert(index,node);
+    }
+
+    pub fn remove_items_of_state(&mut self,state_id:u32) -> Vec<NextToProcess> {
+        let mut removed : Vec<NextToProcess> = Vec::new();
+        let mut kept : Vec<NextToProcess> = Vec::new();
+        for node in self.queue.drain(..) {
+            if node.state_id == state_id {
+                removed.push(node);
+            } else {
+                kept.push(node);
+            }
+        }
+        self.queue = kept;
+        return removed;
+    }
+
+    pub fn get_items(&self) -> &Vec<NextToProcess> {
+        return &self.queue;
+    }
+
     pub fn get_next(&mut self) -> Option<NextToProcess> {
         if self.queue.len() > 0 {
             return Some( self.queue.remove(0) );
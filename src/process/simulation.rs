@@ -0,0 +1,323 @@
+/********************************************************************************
+ * Copyright (c) 2020 Erwan Mahe (github.com/erwanM974)
+ *
+ * This program and the accompanying materials are made available under the
+ * terms of the Eclipse Public License 2.0 which is available at
+ * http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+ * which is available at https://www.apache.org/licenses/LICENSE-2.0.
+ *
+ * SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+ ********************************************************************************/
+
+use std::collections::btree_map::BTreeMap;
+use std::collections::HashMap;
+
+use crate::core::context::general::GeneralContext;
+use crate::core::context::execution::ExecutionContext;
+use crate::core::error::HibouCoreError;
+use crate::core::syntax::interaction::*;
+use crate::core::syntax::action::*;
+use crate::core::syntax::position::*;
+use crate::core::trace::TraceAction;
+use crate::core::semantics::frontier::make_frontier_in_context;
+use crate::core::semantics::shape_execute::shape_execute;
+use crate::core::syntax::data::td_type::TD_DataType;
+use crate::core::syntax::data::generic::TD_Generic;
+use crate::core::syntax::data::var_ref::VariableReference;
+use crate::core::syntax::data::builtin::bool::TD_Bool;
+use crate::core::syntax::data::builtin::integer::TD_Integer;
+use crate::core::syntax::data::builtin::float::TD_Float;
+use crate::core::syntax::data::builtin::string::TD_String;
+
+use crate::process::concrete_execution::*;
+use crate::process::deploy_receptions::deploy_original_action_followup;
+
+use rand::{Rng,SeedableRng};
+use rand_pcg::Pcg32;
+
+// number of times values are sampled for an action before trying another one
+static SAMPLING_ATTEMPTS : u32 = 32;
+// sampled delays, integers and floats lie in [0,bound] (traces do not accept negative numbers)
+static MAX_SAMPLED_DELAY : f64 = 10.0;
+static MAX_SAMPLED_INTEGER : i64 = 100;
+static MAX_SAMPLED_FLOAT : f64 = 100.0;
+static SAMPLED_STRINGS : [&'static str;4] = ["a","b","c","d"];
+
+pub struct SimulationOptions {
+    pub runs : u32,
+    pub seed : u64,
+    pub max_length : u32 // maximum number of actions in a run
+}
+
+impl SimulationOptions {
+    pub fn new(runs : u32,
+               seed : u64,
+               max_length : u32) -> SimulationOptions {
+        return SimulationOptions{runs,seed,max_length};
+    }
+}
+
+/*
+    Generates random concrete runs of the interaction
+    At each step an action is picked at random in the frontier
+    and values are sampled for its fresh values '#' and its delay until its guards are satisfied
+    A run ends when no action can be executed, when the maximum length is reached
+    or, at random, when the remaining interaction accepts the empty trace
+*/
+pub fn simulate(interaction : &Interaction,
+                gen_ctx : &GeneralContext,
+                exe_ctx : &ExecutionContext,
+                options : &SimulationOptions) -> Result<Vec<Vec<TraceAction>>,HibouCoreError> {
+    let mut rng = Pcg32::seed_from_u64(options.seed);
+    let mut runs : Vec<Vec<TraceAction>> = Vec::new();
+    for _ in 0..options.runs {
+        runs.push( simulate_run(interaction,gen_ctx,exe_ctx,options.max_length,&mut rng)? );
+    }
+    return Ok(runs);
+}
+
+fn simulate_run(initial_interaction : &Interaction,
+                gen_ctx : &GeneralContext,
+                initial_exe_ctx : &ExecutionContext,
+                max_length : u32,
+                rng : &mut Pcg32) -> Result<Vec<TraceAction>,HibouCoreError> {
+    let mut interaction = initial_interaction.clone();
+    let mut exe_ctx = initial_exe_ctx.clone();
+    reset_clocks(gen_ctx,&mut exe_ctx);
+    // ***
+    let mut now : f64 = 0.0;
+    let mut last_action_times : HashMap<usize,f64> = HashMap::new(); // key is the lifeline
+    let mut run : Vec<TraceAction> = Vec::new();
+    while (run.len() as u32) < max_length {
+        // simulated runs are accepted behaviours and thus never enter neg fragments
+        let mut frontier : Vec<Position> = make_frontier_in_context(&interaction,&exe_ctx).into_iter()
+            .filter(|pos| !interaction.is_position_within_neg(pos)).collect();
+        if interaction.express_empty() && rng.gen_range(0, frontier.len() + 1) == 0 {
+            break;
+        }
+        shuffle_frontier(&mut frontier,rng);
+        // ***
+        let mut fired : Option<(Interaction,ExecutionContext,TraceAction,f64)> = None;
+        for position in &frontier {
+            for _ in 0..SAMPLING_ATTEMPTS {
+                fired = try_fire_position(gen_ctx,&exe_ctx,&interaction,position,rng)?;
+                if fired.is_some() {
+                    break;
+                }
+            }
+            if fired.is_some() {
+                break;
+            }
+        }
+        // ***
+        match fired {
+            None => {
+                break;
+            },
+            Some( (new_interaction,new_exe_ctx,mut trace_action,elapsed) ) => {
+                now = now + elapsed;
+                let lf_last_time = *last_action_times.get(&trace_action.lf_id).unwrap_or(&0.0);
+                trace_action.delay = Some( TD_Float::Value(round_hundredths(now - lf_last_time)) );
+                last_action_times.insert(trace_action.lf_id,now);
+                run.push(trace_action);
+                interaction = new_interaction;
+                exe_ctx = new_exe_ctx;
+            }
+        }
+    }
+    return Ok(run);
+}
+
+/*
+    Tries to execute the action at the given position with freshly sampled values
+    Returns the time elapsed before the action alongside the result of its execution
+    or None if the sampled values do not satisfy its guards
+*/
+fn try_fire_position(gen_ctx : &GeneralContext,
+                     exe_ctx : &ExecutionContext,
+                     interaction : &Interaction,
+                     position : &Position,
+                     rng : &mut Pcg32) -> Result<Option<(Interaction,ExecutionContext,TraceAction,f64)>,HibouCoreError> {
+    let mut new_exe_ctx = exe_ctx.clone();
+    let elapsed = round_hundredths( rng.gen_range(0.0, MAX_SAMPLED_DELAY) );
+    advance_clocks(gen_ctx,&mut new_exe_ctx,elapsed);
+    bind_unbound_symbols(gen_ctx,&mut new_exe_ctx,rng);
+    // ***
+    let (shaped_interaction,shaped_position,shaped_action,_) = shape_execute(gen_ctx,&mut new_exe_ctx,interaction,position)?;
+    let sampled_action = sample_fresh_values(gen_ctx,&new_exe_ctx,&shaped_action,rng)?;
+    match concrete_execution(gen_ctx,&mut new_exe_ctx,&sampled_action,None)? {
+        ConcreteExecutionResult::UnSat => {
+            return Ok(None);
+        },
+        ConcreteExecutionResult::Sat( effective_parameters ) => {
+            let new_interaction = deploy_original_action_followup(&new_exe_ctx,
+                                                                  &shaped_interaction,
+                                                                  &shaped_position,
+                                                                  &sampled_action,
+                                                                  &effective_parameters);
+            let trace_action = TraceAction{ delay:None,
+                lf_id:sampled_action.lf_act.lf_id,
+                ms_id:sampled_action.ms_id,
+                act_kind:sampled_action.get_action_kind(),
+                com_kind:sampled_action.com_kind.clone(),
+                arguments:effective_parameters};
+            return Ok( Some( (new_interaction,new_exe_ctx,trace_action,elapsed) ) );
+        }
+    }
+}
+
+/*
+    Replaces the fresh values '#' of the parameters and of the assignments of the action with sampled literals
+*/
+fn sample_fresh_values(gen_ctx : &GeneralContext,
+                       exe_ctx : &ExecutionContext,
+                       action : &ObservableAction,
+                       rng : &mut Pcg32) -> Result<ObservableAction,HibouCoreError> {
+    let mut sampled_action = action.clone();
+    let mut pr_id : usize = 0;
+    for param in sampled_action.params.iter_mut() {
+        match param {
+            ValueOrNewFresh::NewFresh => {
+                let pr_type = gen_ctx.get_action_pr_type(action.ms_id,&action.com_kind,pr_id)?;
+                *param = ValueOrNewFresh::Value( sample_value(&pr_type,rng) );
+            },
+            ValueOrNewFresh::Value(_) => {}
+        }
+        pr_id = pr_id + 1;
+    }
+    for amble_item in sampled_action.lf_act.preamble.iter_mut().chain(sampled_action.lf_act.postamble.iter_mut()) {
+        match amble_item {
+            ActionAmbleItem::Assignment( vr_id, ValueOrNewFresh::NewFresh ) => {
+                let vr_type = exe_ctx.get_vr_type(gen_ctx,*vr_id)?;
+                *amble_item = ActionAmbleItem::Assignment( *vr_id, ValueOrNewFresh::Value( sample_value(&vr_type,rng) ) );
+            },
+            _ => {}
+        }
+    }
+    return Ok(sampled_action);
+}
+
+/*
+    Fresh values '#' given in the '@init' section are symbols which are given sampled values
+*/
+fn bind_unbound_symbols(gen_ctx : &GeneralContext, exe_ctx : &mut ExecutionContext, rng : &mut Pcg32) {
+    let mut to_bind : BTreeMap<usize,TD_DataType> = BTreeMap::new();
+    for lf_id in 0..gen_ctx.get_lf_num() {
+        match exe_ctx.get_lf_interpretation(lf_id) {
+            None => {},
+            Some( lf_interpretation ) => {
+                for td_gen in lf_interpretation.values() {
+                    match referenced_symbol(td_gen) {
+                        None => {},
+                        Some( sy_id ) => {
+                            if !exe_ctx.get_symbol_values().contains_key(&sy_id) {
+                                to_bind.insert(sy_id,td_gen.get_td_type());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    for (sy_id,sy_type) in to_bind {
+        exe_ctx.bind_symbol(sy_id, sample_value(&sy_type,rng));
+    }
+}
+
+fn referenced_symbol(td_gen : &TD_Generic) -> Option<usize> {
+    match td_gen {
+        TD_Generic::Bool( TD_Bool::Reference(VariableReference::SYMBOL(sy_id)) ) => {
+            return Some(*sy_id);
+        },
+        TD_Generic::Integer( TD_Integer::Reference(VariableReference::SYMBOL(sy_id)) ) => {
+            return Some(*sy_id);
+        },
+        TD_Generic::Float( TD_Float::Reference(VariableReference::SYMBOL(sy_id)) ) => {
+            return Some(*sy_id);
+        },
+        TD_Generic::String( TD_String::Reference(VariableReference::SYMBOL(sy_id)) ) => {
+            return Some(*sy_id);
+        },
+        _ => {
+            return None;
+        }
+    }
+}
+
+/*
+    Clocks start at 0 on every lifeline
+*/
+fn reset_clocks(gen_ctx : &GeneralContext, exe_ctx : &mut ExecutionContext) {
+    for lf_id in 0..gen_ctx.get_lf_num() {
+        let mut lf_interpretation : BTreeMap<usize,TD_Generic>;
+        match exe_ctx.get_lf_interpretation(lf_id) {
+            None => {
+                lf_interpretation = BTreeMap::new();
+            },
+            Some( got_lfint ) => {
+                lf_interpretation = got_lfint.clone();
+            }
+        }
+        for clock_id in gen_ctx.get_clocks() {
+            lf_interpretation.insert(*clock_id,TD_Generic::Float(TD_Float::Value(0.0)));
+        }
+        exe_ctx.set_lf_interpretation(lf_id,lf_interpretation);
+    }
+}
+
+fn advance_clocks(gen_ctx : &GeneralContext, exe_ctx : &mut ExecutionContext, elapsed : f64) {
+    for lf_id in 0..gen_ctx.get_lf_num() {
+        match exe_ctx.get_lf_interpretation(lf_id) {
+            None => {},
+            Some( got_lfint ) => {
+                let mut lf_interpretation = got_lfint.clone();
+                for (vr_id,td_gen) in lf_interpretation.iter_mut() {
+                    if exe_ctx.is_clock(gen_ctx,*vr_id).unwrap_or(false) {
+                        match td_gen {
+                            TD_Generic::Float( TD_Float::Value(clock_value) ) => {
+                                *clock_value = *clock_value + elapsed;
+                            },
+                            _ => {}
+                        }
+                    }
+                }
+                exe_ctx.set_lf_interpretation(lf_id,lf_interpretation);
+            }
+        }
+    }
+}
+
+fn sample_value(td_type : &TD_DataType, rng : &mut Pcg32) -> TD_Generic {
+    match td_type {
+        TD_DataType::Bool => {
+            if rng.gen::<bool>() {
+                return TD_Generic::Bool(TD_Bool::TRUE);
+            } else {
+                return TD_Generic::Bool(TD_Bool::FALSE);
+            }
+        },
+        TD_DataType::Integer => {
+            return TD_Generic::Integer( TD_Integer::Value( rng.gen_range(0, MAX_SAMPLED_INTEGER + 1) ) );
+        },
+        TD_DataType::Float => {
+            return TD_Generic::Float( TD_Float::Value( round_hundredths(rng.gen_range(0.0, MAX_SAMPLED_FLOAT)) ) );
+        },
+        TD_DataType::String => {
+            let drawn = SAMPLED_STRINGS[ rng.gen_range(0, SAMPLED_STRINGS.len()) ];
+            return TD_Generic::String( TD_String::Value(drawn.to_string()) );
+        }
+    }
+}
+
+fn shuffle_frontier(frontier : &mut Vec<Position>, rng : &mut Pcg32) {
+    let mut remaining = frontier.len();
+    while remaining > 1 {
+        let drawn = rng.gen_range(0, remaining);
+        remaining = remaining - 1;
+        frontier.swap(drawn,remaining);
+    }
+}
+
+fn round_hundredths(value : f64) -> f64 {
+    return (value * 100.0).round() / 100.0;
+}
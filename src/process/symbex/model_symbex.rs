@@ -80,7 +80,7 @@ pub async fn model_symbolic_execution(  client : &mut SymbexClient<tonic::transp
             for param in &model_action.params {
                 match param {
                     ValueOrNewFresh::Value(td_gen) => {
-                        let param_diversity_fqn = message_parameter_diversity_fqn(gen_ctx,exe_ctx,model_action.lf_act.lf_id,model_action.ms_id,pr_id);
+                        let param_diversity_fqn = action_parameter_diversity_fqn(gen_ctx,exe_ctx,model_action.lf_act.lf_id,model_action.ms_id,&model_action.com_kind,pr_id);
                         variable_diversity_values.push( VariableValuePair{variable_id:param_diversity_fqn,
                             value : Some(td_generic_to_grpc(gen_ctx,exe_ctx,model_action.lf_act.lf_id,&td_gen))} );
                     },
@@ -160,8 +160,8 @@ pub async fn model_symbolic_execution(  client : &mut SymbexClient<tonic::transp
             let mut effective_parameters : Vec<TD_Generic> = Vec::new();
             let mut pr_id : usize = 0;
             for param in &model_action.params {
-                let prm_type = gen_ctx.get_pr_type(model_action.ms_id, pr_id).unwrap();
-                let prm_fqn = message_parameter_diversity_fqn(gen_ctx,exe_ctx,model_action.lf_act.lf_id,model_action.ms_id,pr_id);
+                let prm_type = gen_ctx.get_action_pr_type(model_action.ms_id, &model_action.com_kind, pr_id).unwrap();
+                let prm_fqn = action_parameter_diversity_fqn(gen_ctx,exe_ctx,model_action.lf_act.lf_id,model_action.ms_id,&model_action.com_kind,pr_id);
                 let td_gen = symbex_request_variable(gen_ctx,exe_ctx,symbex_result.new_diversity_ec_id,client,prm_fqn,&prm_type).await;
                 effective_parameters.push( td_gen );
                 pr_id = pr_id +1;
@@ -55,6 +55,7 @@ pub async fn trace_symbolic_execution(  client : &mut SymbexClient<tonic::transp
                                         exe_ctx : &mut ExecutionContext,
                                         lf_id : usize,
                                         ms_id : usize,
+                                        com_kind : &CommunicationKind,
                                         trace_params : &Vec<TD_Generic>,
                                         trace_delay_opt : &Option<TD_Float>,
                                         temporality : &HibouProcessTemporality,
@@ -64,7 +65,7 @@ pub async fn trace_symbolic_execution(  client : &mut SymbexClient<tonic::transp
     let mut param_counter : usize = 0;
     let mut variable_diversity_values : Vec<VariableValuePair> = Vec::new();
     for trace_param_td_gen in trace_params {
-        let trace_param_diversity_fqn = trace_message_parameter_diversity_fqn(gen_ctx,exe_ctx,lf_id,ms_id,param_counter);
+        let trace_param_diversity_fqn = trace_action_parameter_diversity_fqn(gen_ctx,exe_ctx,lf_id,ms_id,com_kind,param_counter);
         variable_diversity_values.push( VariableValuePair{variable_id:trace_param_diversity_fqn,
             value : Some(td_generic_to_grpc(gen_ctx,exe_ctx,lf_id,trace_param_td_gen))} );
         param_counter = param_counter +1;
@@ -88,7 +89,7 @@ pub async fn trace_symbolic_execution(  client : &mut SymbexClient<tonic::transp
         }
     }
     // ***
-    let target_action_fqn = trace_action_compare_diversity_fqn(gen_ctx,exe_ctx,lf_id,ms_id);
+    let target_action_fqn = trace_action_compare_diversity_fqn(gen_ctx,exe_ctx,lf_id,ms_id,com_kind);
     match symbex_fire_action(gen_ctx,
                              exe_ctx,
                              parent_diversity_ec_id,
@@ -14,7 +14,11 @@ pub enum CoverageVerdict{
     Cov,
     TooShort,
     LackObs,
-    Out
+    Out,
+    // deviation from an assert fragment which has been entered
+    AssertOut,
+    // behaviour forbidden by a neg fragment
+    Neg
 }
 
 impl std::string::ToString for CoverageVerdict {
@@ -32,14 +36,22 @@ impl std::string::ToString for CoverageVerdict {
             },
             CoverageVerdict::Out => {
                 return "Out".to_string();
+            },
+            CoverageVerdict::AssertOut => {
+                return "AssertOut".to_string();
+            },
+            CoverageVerdict::Neg => {
+                return "Neg".to_string();
             }
         }
     }
 
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
 pub enum GlobalVerdict {
+    NegFail,
+    AssertFail,
     Fail,
     Inconc,
     WeakPass,
@@ -60,12 +72,69 @@ impl std::string::ToString for GlobalVerdict {
             },
             GlobalVerdict::Fail => {
                 return "Fail".to_string();
+            },
+            GlobalVerdict::AssertFail => {
+                return "AssertFail".to_string();
+            },
+            GlobalVerdict::NegFail => {
+                return "NegFail".to_string();
+            }
+        }
+    }
+}
+
+impl GlobalVerdict {
+
+    /*
+        A behaviour forbidden by a neg fragment takes precedence over any other verdict,
+        hence it is the only one which cannot be overturned by the exploration of other paths
+    */
+    pub fn is_definitive(&self) -> bool {
+        match self {
+            GlobalVerdict::NegFail => {
+                return true;
+            },
+            _ => {
+                return false;
+            }
+        }
+    }
+
+    pub fn get_explanation(&self) -> Option<String> {
+        match self {
+            GlobalVerdict::NegFail => {
+                return Some( "the trace exhibits a behaviour forbidden by a '@neg' fragment".to_string() );
+            },
+            GlobalVerdict::AssertFail => {
+                return Some( "the trace deviates from an '@assert' fragment it has entered".to_string() );
+            },
+            _ => {
+                return None;
             }
         }
     }
 }
 
+/*
+    NegFail takes precedence over AssertFail, which takes precedence over all the other verdicts,
+    so that the global verdict does not depend on the order in which paths are explored
+*/
 pub fn update_global_verdict_from_new_coverage_verdict(glo:GlobalVerdict,cov:CoverageVerdict) -> GlobalVerdict {
+    match (&glo,&cov) {
+        (GlobalVerdict::NegFail,_) => {
+            return GlobalVerdict::NegFail;
+        },
+        (_,CoverageVerdict::Neg) => {
+            return GlobalVerdict::NegFail;
+        },
+        (GlobalVerdict::AssertFail,_) => {
+            return GlobalVerdict::AssertFail;
+        },
+        (_,CoverageVerdict::AssertOut) => {
+            return GlobalVerdict::AssertFail;
+        },
+        _ => {}
+    }
     match glo {
         GlobalVerdict::Pass => {
             return GlobalVerdict::Pass;
@@ -93,7 +162,8 @@ pub fn update_global_verdict_from_new_coverage_verdict(glo:GlobalVerdict,cov:Cov
                 }
             }
         },
-        GlobalVerdict::Fail => {
+        GlobalVerdict::Fail | GlobalVerdict::NegFail | GlobalVerdict::AssertFail => {
+            // NegFail and AssertFail have been handled above
             match cov {
                 CoverageVerdict::Cov => {
                     return GlobalVerdict::Pass;
@@ -21,8 +21,9 @@ use crate::core::syntax::action::*;
 use crate::core::syntax::position::{Position,SYNTAX_POSITION_EPSILON,SYNTAX_POSITION_LEFT,SYNTAX_POSITION_RIGHT};
 use crate::rendering::textual::colored::colored_text::TextToPrint;
 use crate::rendering::textual::colored::amble::diagram_revr_amble;
-use crate::rendering::textual::colored::message::diagram_repr_message;
+use crate::rendering::textual::colored::message::{diagram_repr_message,diagram_repr_tags};
 use crate::rendering::hibou_color_palette::*;
+use crate::rendering::textual::convention::SYNTAX_REF;
 
 pub fn extract_texts_on_interaction(interaction : &Interaction,
                      gen_ctx : &GeneralContext,
@@ -36,7 +37,10 @@ pub fn extract_texts_on_interaction(interaction : &Interaction,
             let mut lf_char_spaces : HashMap<usize,usize> = HashMap::new();
             let mut texts : Vec<Vec<TextToPrint>> = Vec::new();
             // ***
-            let msg_to_print = diagram_repr_message(act.ms_id,&act.params,gen_ctx,exe_ctx);
+            let mut msg_to_print = diagram_repr_message(act.ms_id,&act.params,gen_ctx,exe_ctx);
+            if act.tags.len() > 0 {
+                msg_to_print.append( &mut diagram_repr_tags(&act.tags,gen_ctx) );
+            }
             match lf_char_spaces.get(&act.lf_act.lf_id) {
                 None => {
                     lf_char_spaces.insert(*(&act.lf_act.lf_id),TextToPrint::char_count(&msg_to_print));
@@ -93,9 +97,22 @@ pub fn extract_texts_on_interaction(interaction : &Interaction,
         Interaction::Par(i1,i2) => {
             return extract_texts_in_binary_operator(i1,i2,gen_ctx,exe_ctx);
         },
-        Interaction::Loop(_,i1) => {
+        Interaction::Loop(_,_,i1) | Interaction::Opt(i1) | Interaction::Call(_,_,i1)
+                | Interaction::Neg(_,i1) | Interaction::Assert(_,i1) | Interaction::Break(_,i1) | Interaction::Create(_,i1) => {
             return extract_texts_on_interaction(i1,gen_ctx,exe_ctx);
         },
+        Interaction::Ref(ref_id,i1) => {
+            // the content of a reference is not drawn but its lifelines are, wide enough for the label of its frame
+            let (mut lf_char_spaces,_) = extract_texts_on_interaction(i1,gen_ctx,exe_ctx);
+            if lf_char_spaces.len() > 0 {
+                let label_chars = SYNTAX_REF.chars().count() + 1 + gen_ctx.get_ref_label(*ref_id).unwrap().chars().count();
+                let chars_per_lf = (label_chars + lf_char_spaces.len() - 1) / lf_char_spaces.len();
+                for lf_chars in lf_char_spaces.values_mut() {
+                    *lf_chars = cmp::max(*lf_chars, chars_per_lf);
+                }
+            }
+            return (lf_char_spaces,Vec::new());
+        },
         Interaction::Scope(vr_ids_vec,i1) => {
             let mut scope_text : Vec<TextToPrint> = Vec::new();
             let mut counter : usize = 0;
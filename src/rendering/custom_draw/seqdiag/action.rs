@@ -134,7 +134,7 @@ pub fn draw_action( image : &mut RgbImage,
                     position_to_draw_message_text = main_lf_coords.x_middle + (main_lf_coords.x_span_inner/4.0);
                 }
                 arr_kinds.push( ActionArrowKind::Emission );
-                draw_action_content(image,exe_ctx,max_preamble_len,lf_actions,arr_kinds,lf_x_widths,yshift,None);
+                draw_action_content(image,exe_ctx,&action.com_kind,max_preamble_len,lf_actions,arr_kinds,lf_x_widths,yshift,None);
             } else if lf_actions.len() == 2 {
                 {
                     let origin_lf_id = *(&action.lf_act.lf_id);
@@ -162,7 +162,7 @@ pub fn draw_action( image : &mut RgbImage,
                 arr_kinds.push( ActionArrowKind::None );
                 arr_kinds.push( ActionArrowKind::None );
                 let tar_lf_id = lf_actions.get(1).unwrap().lf_id;
-                draw_action_content(image,exe_ctx,max_preamble_len,lf_actions,arr_kinds,lf_x_widths,yshift,Some((action.lf_act.lf_id,tar_lf_id)));
+                draw_action_content(image,exe_ctx,&action.com_kind,max_preamble_len,lf_actions,arr_kinds,lf_x_widths,yshift,Some((action.lf_act.lf_id,tar_lf_id)));
             } else {
                 {
                     let main_lf_coords = lf_x_widths.get(&action.lf_act.lf_id).unwrap();
@@ -172,7 +172,7 @@ pub fn draw_action( image : &mut RgbImage,
                 for idx in 1..lf_actions.len() {
                     arr_kinds.push( ActionArrowKind::BroadcastReception );
                 }
-                draw_action_content(image,exe_ctx,max_preamble_len,lf_actions,arr_kinds,lf_x_widths,yshift,None);
+                draw_action_content(image,exe_ctx,&action.com_kind,max_preamble_len,lf_actions,arr_kinds,lf_x_widths,yshift,None);
             }
         },
         ObservableActionKind::Reception => {
@@ -181,7 +181,7 @@ pub fn draw_action( image : &mut RgbImage,
                 position_to_draw_message_text = main_lf_coords.x_middle - (main_lf_coords.x_span_inner/4.0);
             }
             arr_kinds.push( ActionArrowKind::Reception );
-            draw_action_content(image,exe_ctx,max_preamble_len,lf_actions,arr_kinds,lf_x_widths,yshift,None);
+            draw_action_content(image,exe_ctx,&action.com_kind,max_preamble_len,lf_actions,arr_kinds,lf_x_widths,yshift,None);
         }
     }
     // ***
@@ -228,8 +228,51 @@ fn draw_centered_colored_text(image: &mut RgbImage,
     draw_colored_text(image,to_print,xpos-(text_width/2.0),ypos);
 }
 
+/*
+    Calls are drawn with a filled arrowhead and replies with a dashed line
+*/
+fn draw_message_line(image : &mut RgbImage,
+                     com_kind : &CommunicationKind,
+                     x_start : f32,
+                     x_end : f32,
+                     y_pos : f32) {
+    match com_kind {
+        CommunicationKind::Reply => {
+            draw_dashed_line_segment(image,x_start,x_end,y_pos,Rgb(HCP_Black));
+        },
+        _ => {
+            draw_line_segment_mut(image,
+                                  (x_start, y_pos),
+                                  (x_end, y_pos),
+                                  Rgb(HCP_Black));
+        }
+    }
+}
+
+fn draw_message_arrowhead(image : &mut RgbImage,
+                          com_kind : &CommunicationKind,
+                          rightward : bool,
+                          x_pos : f32,
+                          y_pos : f32) {
+    match (com_kind,rightward) {
+        (CommunicationKind::Call,true) => {
+            draw_filled_arrowhead_rightward(image,x_pos,y_pos,Rgb(HCP_Black));
+        },
+        (CommunicationKind::Call,false) => {
+            draw_filled_arrowhead_leftward(image,x_pos,y_pos,Rgb(HCP_Black));
+        },
+        (_,true) => {
+            draw_arrowhead_rightward(image,x_pos,y_pos,Rgb(HCP_Black));
+        },
+        (_,false) => {
+            draw_arrowhead_leftward(image,x_pos,y_pos,Rgb(HCP_Black));
+        }
+    }
+}
+
 fn draw_message_arrow(image : &mut RgbImage,
                       arr_kind : &ActionArrowKind,
+                      com_kind : &CommunicationKind,
                      lf_x_coords : &DrawingLifelineCoords,
                      arrow_y_shift : u32) {
     let event_y_pos : f32 = get_y_pos_from_yshift(arrow_y_shift);
@@ -237,21 +280,15 @@ fn draw_message_arrow(image : &mut RgbImage,
         &ActionArrowKind::Emission => {
             let msg_x_left = lf_x_coords.x_middle;
             let msg_x_right= msg_x_left + lf_x_coords.x_span_inner/2.0;
-            draw_arrowhead_rightward(image,msg_x_right,event_y_pos,Rgb(HCP_Black));
-            draw_line_segment_mut(image,
-                                  (msg_x_left, event_y_pos),
-                                  (msg_x_right, event_y_pos),
-                                  Rgb(HCP_Black));
+            draw_message_arrowhead(image,com_kind,true,msg_x_right,event_y_pos);
+            draw_message_line(image,com_kind,msg_x_left,msg_x_right,event_y_pos);
         },
         &ActionArrowKind::Reception => {
             let msg_x_right = lf_x_coords.x_middle;
             let msg_x_left= msg_x_right - lf_x_coords.x_span_inner/2.0;
             draw_filled_circle_mut(image, (msg_x_left as i32, event_y_pos as i32), 3, Rgb(HCP_Black));
-            draw_arrowhead_rightward(image,msg_x_right,event_y_pos,Rgb(HCP_Black));
-            draw_line_segment_mut(image,
-                                  (msg_x_left, event_y_pos),
-                                  (msg_x_right, event_y_pos),
-                                  Rgb(HCP_Black));
+            draw_message_arrowhead(image,com_kind,true,msg_x_right,event_y_pos);
+            draw_message_line(image,com_kind,msg_x_left,msg_x_right,event_y_pos);
         },
         ActionArrowKind::BroadcastEmission => {
             let msg_x_left = lf_x_coords.x_middle;
@@ -278,6 +315,7 @@ fn draw_message_arrow(image : &mut RgbImage,
 fn draw_lf_act(image : &mut RgbImage,
                atd : &LfActToDraw,
                arr_kind : &ActionArrowKind,
+               com_kind : &CommunicationKind,
                lf_x_widths : &HashMap<usize,DrawingLifelineCoords>,
                yshift: u32,
                max_preamble_len : usize) {
@@ -319,12 +357,13 @@ fn draw_lf_act(image : &mut RgbImage,
     }
     // ***
     // we do it afterwards so that the gray squares are not painted over the arrows but stay under
-    draw_message_arrow(image,arr_kind,lf_x_coords,arrow_y_shift);
+    draw_message_arrow(image,arr_kind,com_kind,lf_x_coords,arrow_y_shift);
 }
 
 
 fn draw_action_content( image : &mut RgbImage,
                         exe_ctx : &ExecutionContext,
+                        com_kind : &CommunicationKind,
                         max_preamble_len : usize,
                         lf_actions : Vec<LfActToDraw>,
                         arr_kinds : Vec<ActionArrowKind>,
@@ -335,7 +374,7 @@ fn draw_action_content( image : &mut RgbImage,
     for idx in 0..lf_actions.len() {
         let atd : &LfActToDraw = lf_actions.get(idx).unwrap();
         let arr_kind : &ActionArrowKind = arr_kinds.get(idx).unwrap();
-        draw_lf_act(image,atd,arr_kind,lf_x_widths,
+        draw_lf_act(image,atd,arr_kind,com_kind,lf_x_widths,
                     yshift,max_preamble_len);
     }
     // ***
@@ -345,15 +384,8 @@ fn draw_action_content( image : &mut RgbImage,
             let arrow_y_pos = get_y_pos_from_yshift(yshift + 2 + 2*(max_preamble_len as u32));
             let msg_x_orig : f32 = lf_x_widths.get(&origin_lf_id).unwrap().x_middle;
             let msg_x_targ : f32 = lf_x_widths.get(&target_lf_id).unwrap().x_middle;
-            if origin_lf_id < target_lf_id {
-                draw_arrowhead_rightward(image,msg_x_targ,arrow_y_pos,Rgb(HCP_Black));
-            } else {
-                draw_arrowhead_leftward(image,msg_x_targ,arrow_y_pos,Rgb(HCP_Black));
-            }
-            draw_line_segment_mut(image,
-                                  (msg_x_targ, arrow_y_pos),
-                                  (msg_x_orig, arrow_y_pos),
-                                  Rgb(HCP_Black));
+            draw_message_arrowhead(image,com_kind,origin_lf_id < target_lf_id,msg_x_targ,arrow_y_pos);
+            draw_message_line(image,com_kind,msg_x_targ,msg_x_orig,arrow_y_pos);
         }
     }
 }
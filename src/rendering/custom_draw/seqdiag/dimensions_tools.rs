@@ -80,9 +80,26 @@ fn get_interaction_depth(interaction : &Interaction, exe_ctx : &ExecutionContext
         &Interaction::Scope(_, ref i1) => {
             return get_interaction_depth(i1,exe_ctx) + 4;
         }
-        &Interaction::Loop(_, ref i1) => {
+        &Interaction::Loop(_, _, ref i1) => {
             return get_interaction_depth(i1,exe_ctx) + 4;
         }
+        &Interaction::Opt(ref i1) => {
+            return get_interaction_depth(i1,exe_ctx) + 4;
+        }
+        &Interaction::Ref(_, _) => {
+            return 4;
+        }
+        &Interaction::Neg(_, ref i1) | &Interaction::Assert(_, ref i1) | &Interaction::Break(_, ref i1)
+                | &Interaction::Create(_, ref i1) => {
+            return get_interaction_depth(i1,exe_ctx) + 4;
+        }
+        &Interaction::Call(_, _, ref i1) => {
+            let mut sum : usize = 4;
+            for frag in get_recursive_strict_frags(i1) {
+                sum = sum + get_interaction_depth(frag,exe_ctx) + 1;
+            }
+            return sum;
+        }
     }
 }
 
@@ -44,7 +44,9 @@ use crate::rendering::custom_draw::seqdiag::recursive_frag::*;
 use crate::rendering::custom_draw::seqdiag::dimensions_tools::*;
 use crate::rendering::custom_draw::seqdiag::action::draw_action;
 use crate::rendering::custom_draw::seqdiag::lf_coords::DrawingLifelineCoords;
+use crate::rendering::custom_draw::extraction::ext_interaction::extract_texts_on_interaction;
 use crate::rendering::hibou_color_palette::*;
+use crate::process::lint::collect_actions;
 use crate::rendering::custom_draw::utils::colored_text::draw_colored_text;
 // **********
 
@@ -58,6 +60,7 @@ pub fn draw_interaction_rec(    image : &mut RgbImage,
                                 interaction : &Interaction,
                                 lf_x_widths : &HashMap<usize,DrawingLifelineCoords>,
                                 texts_to_print : &mut Vec<Vec<TextToPrint>>,
+                                covered : &Option<&HashSet<Vec<u32>>>,
                                 nest_shift : &mut u32,
                                 yshift : &mut u32)
                         -> [usize;2] { // returns left and right borders of the interaction
@@ -66,34 +69,117 @@ pub fn draw_interaction_rec(    image : &mut RgbImage,
             return [gen_ctx.get_lf_num(),0]; // because when going up we keep the minimum on the left and maximum on the right
         },
         &Interaction::Action(ref act) => {
+            if is_left_unexercised(interaction, covered) {
+                highlight_action_texts(act, texts_to_print);
+            }
             *yshift = *yshift +1;
             let (new_yshift,lr_bounds) = draw_action(image,exe_ctx,act,lf_x_widths,texts_to_print,*yshift);
             *yshift = new_yshift + 1;
             return lr_bounds;
         },
         &Interaction::Seq(ref i1,ref i2) => {
-            let wr1 : [usize;2] = draw_interaction_rec(image, gen_ctx, exe_ctx,i1, lf_x_widths, texts_to_print, nest_shift, yshift);
+            let wr1 : [usize;2] = draw_interaction_rec(image, gen_ctx, exe_ctx,i1, lf_x_widths, texts_to_print, covered, nest_shift, yshift);
             *yshift = *yshift +1;
-            let wr2 : [usize;2] = draw_interaction_rec(image,  gen_ctx, exe_ctx,i2, lf_x_widths, texts_to_print, nest_shift, yshift);
+            let wr2 : [usize;2] = draw_interaction_rec(image,  gen_ctx, exe_ctx,i2, lf_x_widths, texts_to_print, covered, nest_shift, yshift);
             return [ std::cmp::min(wr1[0],wr2[0]) , std::cmp::max(wr1[1],wr2[1]) ];
         },
         &Interaction::Strict(ref i1,ref i2) => {
             let mut frags = get_recursive_strict_frags(i1);
             frags.extend( get_recursive_strict_frags(i2) );
             let label = vec![TextToPrint{text:SYNTAX_STRICT.to_string(),color:Rgb(HCP_Black)}];
-            return draw_n_ary_combined_fragment(image, gen_ctx, exe_ctx,frags,lf_x_widths,texts_to_print, label, nest_shift, yshift);
+            return draw_n_ary_combined_fragment(image, gen_ctx, exe_ctx,frags,lf_x_widths,texts_to_print, covered, label, nest_shift, yshift);
         },
         &Interaction::Alt(ref i1,ref i2) => {
             let mut frags = get_recursive_alt_frags(i1);
             frags.extend( get_recursive_alt_frags(i2) );
-            let label = vec![TextToPrint{text:SYNTAX_ALT.to_string(),color:Rgb(HCP_Black)}];
-            return draw_n_ary_combined_fragment(image, gen_ctx, exe_ctx,frags,lf_x_widths,texts_to_print, label, nest_shift, yshift);
+            let mut label_color = HCP_Black;
+            if frags.iter().any(|frag| is_left_unexercised(frag, covered)) {
+                label_color = HCP_LightRed;
+            }
+            let label = vec![TextToPrint{text:SYNTAX_ALT.to_string(),color:Rgb(label_color)}];
+            return draw_n_ary_combined_fragment(image, gen_ctx, exe_ctx,frags,lf_x_widths,texts_to_print, covered, label, nest_shift, yshift);
         },
         &Interaction::Par(ref i1,ref i2) => {
             let mut frags = get_recursive_par_frags(i1);
             frags.extend( get_recursive_par_frags(i2) );
             let label = vec![TextToPrint{text:SYNTAX_PAR.to_string(),color:Rgb(HCP_Black)}];
-            return draw_n_ary_combined_fragment(image, gen_ctx, exe_ctx,frags,lf_x_widths,texts_to_print, label, nest_shift, yshift);
+            return draw_n_ary_combined_fragment(image, gen_ctx, exe_ctx,frags,lf_x_widths,texts_to_print, covered, label, nest_shift, yshift);
+        },
+        &Interaction::Opt(ref i1) => {
+            let mut label_color = HCP_Black;
+            if is_left_unexercised(i1, covered) {
+                label_color = HCP_LightRed;
+            }
+            let label = vec![TextToPrint{text:SYNTAX_OPT.to_string(),color:Rgb(label_color)}];
+            return draw_unary_combined_fragment(image,  gen_ctx, exe_ctx,i1,lf_x_widths,texts_to_print, covered, label, nest_shift, yshift);
+        },
+        &Interaction::Break(_, ref i1) => {
+            let mut label_color = HCP_Black;
+            if is_left_unexercised(i1, covered) {
+                label_color = HCP_LightRed;
+            }
+            let label = vec![TextToPrint{text:SYNTAX_BREAK.to_string(),color:Rgb(label_color)}];
+            return draw_unary_combined_fragment(image,  gen_ctx, exe_ctx,i1,lf_x_widths,texts_to_print, covered, label, nest_shift, yshift);
+        },
+        &Interaction::Neg(_, ref i1) => {
+            if **i1 == Interaction::Empty {
+                // an exhausted neg fragment covers no lifeline
+                return [gen_ctx.get_lf_num(),0];
+            }
+            let label = vec![TextToPrint{text:SYNTAX_NEG.to_string(),color:Rgb(HCP_Black)}];
+            return draw_unary_combined_fragment(image,  gen_ctx, exe_ctx,i1,lf_x_widths,texts_to_print, covered, label, nest_shift, yshift);
+        },
+        &Interaction::Assert(_, ref i1) => {
+            let label = vec![TextToPrint{text:SYNTAX_ASSERT.to_string(),color:Rgb(HCP_Black)}];
+            return draw_unary_combined_fragment(image,  gen_ctx, exe_ctx,i1,lf_x_widths,texts_to_print, covered, label, nest_shift, yshift);
+        },
+        &Interaction::Ref(ref_id, ref i1) => {
+            // only a frame spanning the lifelines of the referenced interaction is drawn
+            let (involved_lfs,_) = extract_texts_on_interaction(i1,gen_ctx,exe_ctx);
+            match (involved_lfs.keys().min(),involved_lfs.keys().max()) {
+                (Some(left_bound),Some(right_bound)) => {
+                    let mut label_color = HCP_Black;
+                    if is_left_unexercised(i1, covered) {
+                        label_color = HCP_LightRed;
+                    }
+                    let label = vec![TextToPrint{text:format!("{} {}",SYNTAX_REF,gen_ctx.get_ref_label(ref_id).unwrap()),color:Rgb(label_color)}];
+                    let start_y : u32 = *yshift;
+                    *yshift += 3;
+                    let end_y : u32 = *yshift;
+                    draw_combined_fragment_frame(image, label, *nest_shift,lf_x_widths,*left_bound,*right_bound,vec![start_y,end_y]);
+                    return [*left_bound,*right_bound];
+                },
+                _ => {
+                    return [gen_ctx.get_lf_num(),0];
+                }
+            }
+        },
+        &Interaction::Create(ref lf_ids, ref i1) => {
+            // the created lifelines are given with their group if they have one
+            let mut label : Vec<TextToPrint> = Vec::new();
+            label.push( TextToPrint{text:format!("{}{}",SYNTAX_CREATE,SYNTAX_SCOPE_START),color:Rgb(HCP_Black)});
+            let mut counter : usize = 0;
+            for lf_id in lf_ids {
+                label.push( TextToPrint{text:gen_ctx.get_lf_name(*lf_id).unwrap(),color:Rgb(HC_Lifeline)});
+                match gen_ctx.get_lf_group(*lf_id) {
+                    None => {},
+                    Some( lgr_id ) => {
+                        label.push( TextToPrint{text:":".to_string(),color:Rgb(HCP_Black)});
+                        label.push( TextToPrint{text:gen_ctx.get_lgr_name(lgr_id).unwrap(),color:Rgb(HC_LifelineGroup)});
+                    }
+                }
+                counter = counter + 1;
+                if counter < lf_ids.len() {
+                    label.push( TextToPrint{text:SYNTAX_SCOPE_SEPARATOR.to_string(),color:Rgb(HCP_Black)});
+                }
+            }
+            label.push( TextToPrint{text:SYNTAX_SCOPE_END.to_string(),color:Rgb(HCP_Black)});
+            return draw_unary_combined_fragment(image,  gen_ctx, exe_ctx,i1,lf_x_widths,texts_to_print, covered, label, nest_shift, yshift);
+        },
+        &Interaction::Call(_, _, ref i1) => {
+            let label = vec![TextToPrint{text:SYNTAX_CALL.to_string(),color:Rgb(HCP_Black)}];
+            let frags = get_recursive_strict_frags(i1);
+            return draw_sequential_combined_fragment(image, gen_ctx, exe_ctx,frags,lf_x_widths,texts_to_print, covered, label, nest_shift, yshift);
         },
         &Interaction::Scope(_, ref i1) => {
             let mut label : Vec<TextToPrint> = Vec::new();
@@ -101,23 +187,101 @@ pub fn draw_interaction_rec(    image : &mut RgbImage,
             let mut scope_content = texts_to_print.remove(0);
             label.append( &mut scope_content);
             label.push( TextToPrint{text:"}".to_string(),color:Rgb(HCP_Black)});
-            return draw_unary_combined_fragment(image,  gen_ctx, exe_ctx,i1,lf_x_widths,texts_to_print, label, nest_shift, yshift);
+            return draw_unary_combined_fragment(image,  gen_ctx, exe_ctx,i1,lf_x_widths,texts_to_print, covered, label, nest_shift, yshift);
         },
-        &Interaction::Loop(ref lkind, ref i1) => {
-            match lkind {
+        &Interaction::Loop(ref lkind, ref bounds, ref i1) => {
+            let mut label_color = HCP_Black;
+            if is_left_unexercised(i1, covered) {
+                label_color = HCP_LightRed;
+            }
+            let mut loop_text = match lkind {
                 ScheduleOperatorKind::Strict => {
-                    let label = vec![TextToPrint{text:SYNTAX_LOOPX.to_string(),color:Rgb(HCP_Black)}];
-                    return draw_unary_combined_fragment(image,  gen_ctx, exe_ctx,i1,lf_x_widths,texts_to_print, label, nest_shift, yshift);
+                    SYNTAX_LOOPX.to_string()
                 },
                 ScheduleOperatorKind::Seq => {
-                    let label = vec![TextToPrint{text:SYNTAX_LOOPH.to_string(),color:Rgb(HCP_Black)}];
-                    return draw_unary_combined_fragment(image,  gen_ctx, exe_ctx,i1,lf_x_widths,texts_to_print, label, nest_shift, yshift);
+                    SYNTAX_LOOPH.to_string()
                 },
                 ScheduleOperatorKind::Par => {
-                    let label = vec![TextToPrint{text:SYNTAX_LOOPP.to_string(),color:Rgb(HCP_Black)}];
-                    return draw_unary_combined_fragment(image,  gen_ctx, exe_ctx,i1,lf_x_widths,texts_to_print, label, nest_shift, yshift);
+                    SYNTAX_LOOPP.to_string()
+                }
+            };
+            if !bounds.is_unbounded() {
+                match bounds.max {
+                    None => {
+                        loop_text.push_str( &format!("{{{},*}}", bounds.min) );
+                    },
+                    Some( max ) => {
+                        loop_text.push_str( &format!("{{{},{}}}", bounds.min, max) );
+                    }
                 }
             }
+            let label = vec![TextToPrint{text:loop_text,color:Rgb(label_color)}];
+            return draw_unary_combined_fragment(image,  gen_ctx, exe_ctx,i1,lf_x_widths,texts_to_print, covered, label, nest_shift, yshift);
+        }
+    }
+}
+
+/*
+    When drawing the coverage of a model, i.e. when the original positions of the exercised actions are given,
+    a fragment is not exercised if none of its actions is
+*/
+fn is_exercised(interaction : &Interaction, covered : &Option<&HashSet<Vec<u32>>>) -> bool {
+    match covered {
+        None => {
+            return true;
+        },
+        Some( covered_positions ) => {
+            match interaction {
+                Interaction::Empty => {
+                    return false;
+                },
+                Interaction::Action( act ) => {
+                    match &act.original_position {
+                        None => {
+                            return true;
+                        },
+                        Some( orig_pos ) => {
+                            return covered_positions.contains(orig_pos);
+                        }
+                    }
+                },
+                Interaction::Strict(i1,i2) | Interaction::Seq(i1,i2) | Interaction::Alt(i1,i2) | Interaction::Par(i1,i2) => {
+                    return is_exercised(i1,covered) || is_exercised(i2,covered);
+                },
+                Interaction::Loop(_,_,i1) | Interaction::Opt(i1) | Interaction::Scope(_,i1) | Interaction::Call(_,_,i1)
+                        | Interaction::Neg(_,i1) | Interaction::Assert(_,i1) | Interaction::Break(_,i1) | Interaction::Create(_,i1)
+                        | Interaction::Ref(_,i1) => {
+                    return is_exercised(i1,covered);
+                }
+            }
+        }
+    }
+}
+
+/*
+    A fragment without any action, such as an empty '@alt' branch, cannot be exercised
+    and is thus not highlighted
+*/
+fn is_left_unexercised(interaction : &Interaction, covered : &Option<&HashSet<Vec<u32>>>) -> bool {
+    let mut actions : Vec<&ObservableAction> = Vec::new();
+    collect_actions(interaction, &mut actions);
+    return !actions.is_empty() && !is_exercised(interaction, covered);
+}
+
+// the texts of an action are its message followed by the items of the pre and post ambles of the lifelines it involves
+fn highlight_action_texts(act : &ObservableAction, texts_to_print : &mut Vec<Vec<TextToPrint>>) {
+    let mut texts_num = 1 + act.lf_act.preamble.len() + act.lf_act.postamble.len();
+    match &act.act_kind {
+        ObservableActionKind::Reception => {},
+        ObservableActionKind::Emission( targets ) => {
+            for target_lf_act in targets {
+                texts_num = texts_num + target_lf_act.preamble.len() + target_lf_act.postamble.len();
+            }
+        }
+    }
+    for text in texts_to_print.iter_mut().take(texts_num) {
+        for text_part in text.iter_mut() {
+            text_part.color = Rgb(HCP_LightRed);
         }
     }
 }
@@ -128,6 +292,7 @@ fn draw_unary_combined_fragment(    image : &mut RgbImage,
                                     i1 : &Interaction,
                                     lf_x_widths : &HashMap<usize,DrawingLifelineCoords>,
                                     texts_to_print : &mut Vec<Vec<TextToPrint>>,
+                                    covered : &Option<&HashSet<Vec<u32>>>,
                                     label : Vec<TextToPrint>,
                                     nest_shift : &mut u32,
                                     yshift : &mut u32) -> [usize;2] {
@@ -135,7 +300,7 @@ fn draw_unary_combined_fragment(    image : &mut RgbImage,
     *nest_shift += 1;
     let start_y : u32 = *yshift;
     *yshift += 3;
-    let lr_bounds : [usize;2] = draw_interaction_rec(image,  gen_ctx, exe_ctx,i1, lf_x_widths, texts_to_print, nest_shift, yshift);
+    let lr_bounds : [usize;2] = draw_interaction_rec(image,  gen_ctx, exe_ctx,i1, lf_x_widths, texts_to_print, covered, nest_shift, yshift);
     *yshift += 1;
     let end_y : u32 = *yshift;
     *nest_shift -= 1;
@@ -145,12 +310,47 @@ fn draw_unary_combined_fragment(    image : &mut RgbImage,
     return lr_bounds;
 }
 
+/*
+    The call, the body and the reply of a call are drawn one after the other in a single frame, without separators
+*/
+fn draw_sequential_combined_fragment(   image : &mut RgbImage,
+                                        gen_ctx : &GeneralContext,
+                                        exe_ctx : &ExecutionContext,
+                                        sub_ints : Vec<&Interaction>,
+                                        lf_x_widths : &HashMap<usize,DrawingLifelineCoords>,
+                                        texts_to_print : &mut Vec<Vec<TextToPrint>>,
+                                        covered : &Option<&HashSet<Vec<u32>>>,
+                                        label : Vec<TextToPrint>,
+                                        nest_shift : &mut u32,
+                                        yshift : &mut u32) -> [usize;2] {
+    // draw content and gather data
+    *nest_shift += 1;
+    let start_y : u32 = *yshift;
+    *yshift += 3;
+    let mut min_lf_id : usize = gen_ctx.get_lf_num();
+    let mut max_lf_id : usize = 0;
+    for my_int in sub_ints {
+        let lr_bounds = draw_interaction_rec(image,  gen_ctx, exe_ctx,my_int, lf_x_widths, texts_to_print, covered, nest_shift, yshift);
+        min_lf_id = cmp::min( min_lf_id, lr_bounds[0]);
+        max_lf_id = cmp::max( max_lf_id, lr_bounds[1]);
+        *yshift += 1;
+    }
+    let end_y : u32 = *yshift;
+    *nest_shift -= 1;
+    // draw frame
+    let lr_bounds: [usize;2] = [ min_lf_id, max_lf_id ];
+    let y_drafts : Vec<u32> = [start_y,end_y].to_vec();
+    draw_combined_fragment_frame(image, label, *nest_shift,lf_x_widths,lr_bounds[0],lr_bounds[1],y_drafts);
+    return lr_bounds;
+}
+
 fn draw_n_ary_combined_fragment(  image : &mut RgbImage,
                                   gen_ctx : &GeneralContext,
                                   exe_ctx : &ExecutionContext,
                                   sub_ints : Vec<&Interaction>,
                                   lf_x_widths : &HashMap<usize,DrawingLifelineCoords>,
                                   texts_to_print : &mut Vec<Vec<TextToPrint>>,
+                                  covered : &Option<&HashSet<Vec<u32>>>,
                                   label : Vec<TextToPrint>,
                                   nest_shift : &mut u32,
                                   yshift : &mut u32) -> [usize;2] {
@@ -164,7 +364,7 @@ fn draw_n_ary_combined_fragment(  image : &mut RgbImage,
     let mut max_lf_id : usize = 0;
     for my_int in sub_ints {
         *yshift += 1;
-        let lr_bounds = draw_interaction_rec(image,  gen_ctx, exe_ctx,my_int, lf_x_widths, texts_to_print, nest_shift, yshift);
+        let lr_bounds = draw_interaction_rec(image,  gen_ctx, exe_ctx,my_int, lf_x_widths, texts_to_print, covered, nest_shift, yshift);
         min_lf_id = cmp::min( min_lf_id, lr_bounds[0]);
         max_lf_id = cmp::max( max_lf_id, lr_bounds[1]);
         *yshift += 1;
@@ -46,6 +46,28 @@ pub fn draw_interaction(path_str : &String,
                         gen_ctx : &GeneralContext,
                         exe_ctx : &ExecutionContext,
                         remaining_multi_trace : &Option<AnalysableMultiTrace>) {
+    draw_interaction_highlighted(path_str, interaction, gen_ctx, exe_ctx, remaining_multi_trace, &None);
+}
+
+/*
+    Draws an interaction in which the actions whose original position is not among the covered ones are highlighted
+    as well as the '@alt', '@opt' and loops having a branch or a body in which no action is covered
+    References are expanded so that the coverage of their content is shown
+*/
+pub fn draw_interaction_coverage(path_str : &String,
+                                 interaction : &Interaction,
+                                 gen_ctx : &GeneralContext,
+                                 exe_ctx : &ExecutionContext,
+                                 covered : &HashSet<Vec<u32>>) {
+    draw_interaction_highlighted(path_str, &interaction.expand_references(), gen_ctx, exe_ctx, &None, &Some(covered));
+}
+
+fn draw_interaction_highlighted(path_str : &String,
+                                interaction : &Interaction,
+                                gen_ctx : &GeneralContext,
+                                exe_ctx : &ExecutionContext,
+                                remaining_multi_trace : &Option<AnalysableMultiTrace>,
+                                covered : &Option<&HashSet<Vec<u32>>>) {
     let path = Path::new( path_str );
     // ***
     let (mut lf_char_widths,mut texts_to_print) = extract_texts_on_interaction(interaction,gen_ctx,exe_ctx);
@@ -139,7 +161,7 @@ pub fn draw_interaction(path_str : &String,
     let mut nest_shift : u32 = 1; // shift to display nested fragments
     let mut yshift : u32 = 5 + ((additional_y_space_for_context*2) as u32);
 
-    draw_interaction_rec(&mut image,  gen_ctx, exe_ctx, interaction, &lf_x_widths, &mut texts_to_print,&mut nest_shift, &mut yshift);
+    draw_interaction_rec(&mut image,  gen_ctx, exe_ctx, interaction, &lf_x_widths, &mut texts_to_print, covered, &mut nest_shift, &mut yshift);
 
     match multi_trace_txttoprint {
         None => {},
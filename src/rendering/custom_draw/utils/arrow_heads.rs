@@ -14,7 +14,7 @@
 // **********
 
 use image::{Rgb,RgbImage};
-use imageproc::drawing::{draw_line_segment_mut};
+use imageproc::drawing::{Point,draw_line_segment_mut,draw_convex_polygon_mut};
 use imageproc::drawing::draw_cubic_bezier_curve_mut;
 
 // **********
@@ -77,3 +77,30 @@ pub fn draw_arrowhead_leftward(image : &mut RgbImage, x_pos : f32, y_pos : f32,
 
 // **********
 
+pub fn draw_filled_arrowhead_rightward(image : &mut RgbImage, x_pos : f32, y_pos : f32, my_color : Rgb<u8>) {
+    let points = [Point::new(x_pos as i32, y_pos as i32),
+                  Point::new((x_pos - (ARROW_HEAD_LENGTH as f32)) as i32, (y_pos - (ARROW_HEAD_LENGTH as f32)) as i32),
+                  Point::new((x_pos - (ARROW_HEAD_LENGTH as f32)) as i32, (y_pos + (ARROW_HEAD_LENGTH as f32)) as i32)];
+    draw_convex_polygon_mut(image, &points, my_color);
+}
+
+pub fn draw_filled_arrowhead_leftward(image : &mut RgbImage, x_pos : f32, y_pos : f32, my_color : Rgb<u8>) {
+    let points = [Point::new(x_pos as i32, y_pos as i32),
+                  Point::new((x_pos + (ARROW_HEAD_LENGTH as f32)) as i32, (y_pos - (ARROW_HEAD_LENGTH as f32)) as i32),
+                  Point::new((x_pos + (ARROW_HEAD_LENGTH as f32)) as i32, (y_pos + (ARROW_HEAD_LENGTH as f32)) as i32)];
+    draw_convex_polygon_mut(image, &points, my_color);
+}
+
+/*
+    Horizontal dashed line, as used for replies
+*/
+pub fn draw_dashed_line_segment(image : &mut RgbImage, x_start : f32, x_end : f32, y_pos : f32, my_color : Rgb<u8>) {
+    let x_min = x_start.min(x_end);
+    let x_max = x_start.max(x_end);
+    let mut x_pos = x_min;
+    while x_pos < x_max {
+        let dash_end = (x_pos + (ARROW_HEAD_LENGTH as f32)).min(x_max);
+        draw_line_segment_mut(image, (x_pos, y_pos), (dash_end, y_pos), my_color);
+        x_pos = x_pos + 2.0*(ARROW_HEAD_LENGTH as f32);
+    }
+}
@@ -94,5 +94,6 @@ pub const HC_Concrete_Value : [u8;3] = HCP_StandardGray;
 pub const HC_Variable : [u8;3] = HCP_StandardRed;
 pub const HC_Symbol : [u8;3] = HCP_StandardOrange;
 pub const HC_NewFresh : [u8;3] = HCP_StandardPurple;
+pub const HC_Tag : [u8;3] = HCP_StandardPink;
 
 //pub const HC_Concrete_Value_Secondary : [u8;3] = HCP_StandardGray;
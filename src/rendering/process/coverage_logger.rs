@@ -0,0 +1,295 @@
+/********************************************************************************
+ * Copyright (c) 2020 Erwan Mahe (github.com/erwanM974)
+ *
+ * This program and the accompanying materials are made available under the
+ * terms of the Eclipse Public License 2.0 which is available at
+ * http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+ * which is available at https://www.apache.org/licenses/LICENSE-2.0.
+ *
+ * SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+ ********************************************************************************/
+
+use std::collections::btree_map::BTreeMap;
+use std::fs::File;
+use std::io::Write;
+
+// ***
+use crate::core::context::general::GeneralContext;
+use crate::core::context::execution::ExecutionContext;
+use crate::core::syntax::position::*;
+use crate::core::syntax::interaction::Interaction;
+use crate::core::trace::{AnalysableMultiTrace,TraceAction};
+use crate::core::syntax::action::*;
+
+use crate::process::log::ProcessLogger;
+use crate::process::verdicts::CoverageVerdict;
+use crate::process::hibou_process::FilterEliminationKind;
+use crate::process::lint::{collect_actions,action_to_short_text};
+use crate::process::checkpoint::{original_position_to_checkpoint_item,parse_original_position};
+
+use crate::rendering::textual::monochrome::position::position_to_text;
+// ***
+
+struct ActionCoverage {
+    text : String,
+    guarded : bool,
+    fired : u32,
+    unsat : u32
+}
+
+/*
+    Counts, for each action of the initial interaction (identified by its 'original_position'),
+    how many times it has been fired and how many times its firing was not satisfiable.
+    At the end of the process, a report lists :
+        - the actions that were never fired i.e. dead actions within the bounds of the exploration
+        - the guarded actions whose guards were always satisfied or never satisfied wherever they were encountered
+          (when analysing, the unsatisfiable firings of actions matched against the trace are not counted)
+*/
+pub struct CoverageProcessLogger {
+    log_name : String,
+    actions : BTreeMap<Vec<u32>,ActionCoverage>
+}
+
+impl CoverageProcessLogger {
+    pub fn new(log_name : String) -> CoverageProcessLogger {
+        return CoverageProcessLogger{log_name,actions:BTreeMap::new()};
+    }
+
+    fn collect_model_actions(&mut self, interaction : &Interaction, gen_ctx : &GeneralContext) {
+        self.actions = BTreeMap::new();
+        let mut actions : Vec<&ObservableAction> = Vec::new();
+        collect_actions(interaction, &mut actions);
+        for action in actions {
+            match &action.original_position {
+                None => {},
+                Some( orig_pos ) => {
+                    self.actions.insert( orig_pos.clone(),
+                                         ActionCoverage{text:action_to_short_text(action,gen_ctx),
+                                             guarded:has_guard(action),
+                                             fired:0,
+                                             unsat:0} );
+                }
+            }
+        }
+    }
+
+    /*
+        Receptions deployed from an emission have the original position of the emission extended by one index
+        and do not correspond to any action of the initial interaction so they are not counted
+    */
+    fn get_coverage_mut(&mut self, model_action : &ObservableAction) -> Option<&mut ActionCoverage> {
+        match &model_action.original_position {
+            None => {
+                return None;
+            },
+            Some( orig_pos ) => {
+                return self.actions.get_mut(orig_pos);
+            }
+        }
+    }
+
+    fn report_as_string(&self, options_as_str : &Vec<String>) -> String {
+        let mut report = format!("model coverage for '{}'\n", self.log_name);
+        for opt_str in options_as_str {
+            report.push_str( &format!("    {}\n", opt_str) );
+        }
+        // ***
+        let fired_num = self.actions.values().filter(|cov| cov.fired > 0).count();
+        report.push_str( &format!("\nfired actions : {}/{}\n", fired_num, self.actions.len()) );
+        for (orig_pos,cov) in &self.actions {
+            report.push_str( &format!("    {} '{}' : fired {} time(s)", original_position_to_text(orig_pos), cov.text, cov.fired) );
+            if cov.guarded {
+                report.push_str( &format!(", unsatisfiable {} time(s)", cov.unsat) );
+            }
+            report.push_str( "\n" );
+        }
+        // ***
+        report.push_str( "\nnever fired actions :\n" );
+        push_report_section(&mut report,
+                            self.actions.iter().filter(|(_,cov)| cov.fired == 0).collect());
+        report.push_str( "\nguards always satisfied :\n" );
+        push_report_section(&mut report,
+                            self.actions.iter().filter(|(_,cov)| cov.guarded && cov.fired > 0 && cov.unsat == 0).collect());
+        report.push_str( "\nguards never satisfied :\n" );
+        push_report_section(&mut report,
+                            self.actions.iter().filter(|(_,cov)| cov.guarded && cov.fired == 0 && cov.unsat > 0).collect());
+        return report;
+    }
+}
+
+fn push_report_section(report : &mut String, selected : Vec<(&Vec<u32>,&ActionCoverage)>) {
+    if selected.is_empty() {
+        report.push_str( "    none\n" );
+    } else {
+        for (orig_pos,cov) in selected {
+            report.push_str( &format!("    {} '{}'\n", original_position_to_text(orig_pos), cov.text) );
+        }
+    }
+}
+
+// the original position of an action at the root of the interaction is empty hence the prefix
+fn original_position_to_text(orig_pos : &Vec<u32>) -> String {
+    return format!("position {}", position_to_text( &Position::from_vec(&mut orig_pos.clone()) ));
+}
+
+fn has_guard(action : &ObservableAction) -> bool {
+    let mut lf_acts : Vec<&LifelineAction> = vec![&action.lf_act];
+    match &action.act_kind {
+        ObservableActionKind::Reception => {},
+        ObservableActionKind::Emission( targets ) => {
+            lf_acts.extend( targets.iter() );
+        }
+    }
+    for lf_act in lf_acts {
+        for amble_item in lf_act.preamble.iter().chain(lf_act.postamble.iter()) {
+            match amble_item {
+                ActionAmbleItem::Guard(_) => {
+                    return true;
+                },
+                _ => {}
+            }
+        }
+    }
+    return false;
+}
+
+impl ProcessLogger for CoverageProcessLogger {
+
+    fn log_init(&mut self,
+                interaction : &Interaction,
+                gen_ctx : &GeneralContext,
+                _exe_ctx : &ExecutionContext,
+                _remaining_multi_trace : &Option<AnalysableMultiTrace>) {
+        self.collect_model_actions(interaction, gen_ctx);
+    }
+
+    /*
+        The counts prior to the interruption are saved in the checkpoint, one line per action :
+        "<original_position> <fired> <unsat>"
+    */
+    fn log_resume(&mut self,
+                  interaction : &Interaction,
+                  gen_ctx : &GeneralContext,
+                  _exe_ctx : &ExecutionContext,
+                  _remaining_multi_trace : &Option<AnalysableMultiTrace>,
+                  saved_state : &Vec<String>) -> Result<(),String> {
+        self.collect_model_actions(interaction, gen_ctx);
+        for line in saved_state {
+            let items : Vec<&str> = line.split_whitespace().collect();
+            if items.len() != 3 {
+                return Err( format!("unexpected coverage count '{}'", line) );
+            }
+            let orig_pos = parse_original_position(items[0])?;
+            let (fired,unsat) : (u32,u32);
+            match (items[1].parse::<u32>(),items[2].parse::<u32>()) {
+                (Ok(got_fired),Ok(got_unsat)) => {
+                    fired = got_fired;
+                    unsat = got_unsat;
+                },
+                _ => {
+                    return Err( format!("unexpected coverage count '{}'", line) );
+                }
+            }
+            match self.actions.get_mut(&orig_pos) {
+                None => {
+                    return Err( format!("no action at {} in the model", original_position_to_text(&orig_pos)) );
+                },
+                Some( cov ) => {
+                    cov.fired = fired;
+                    cov.unsat = unsat;
+                }
+            }
+        }
+        return Ok(());
+    }
+
+    fn log_checkpoint(&mut self) -> Vec<String> {
+        let mut lines : Vec<String> = Vec::new();
+        for (orig_pos,cov) in &self.actions {
+            lines.push( format!("{} {} {}", original_position_to_checkpoint_item(orig_pos), cov.fired, cov.unsat) );
+        }
+        return lines;
+    }
+
+    fn log_term(&mut self,
+                options_as_str : &Vec<String>) {
+        let report = self.report_as_string(options_as_str);
+        match File::create(&format!("{:}_coverage.txt",self.log_name)) {
+            Err(e) => {
+                println!("could not create coverage report : {:?}", e);
+            },
+            Ok( mut file ) => {
+                match file.write_all( report.as_bytes() ) {
+                    Err(e) => {
+                        println!("could not write coverage report : {:?}", e);
+                    },
+                    Ok(_) => {}
+                }
+            }
+        }
+    }
+
+    fn log_execution(&mut self,
+                     _gen_ctx : &GeneralContext,
+                     _parent_state_id : u32,
+                     _new_state_id : u32,
+                     _action_position : &Position,
+                     _trace_action : Option<&TraceAction>,
+                     model_action : &ObservableAction,
+                     _new_interaction : &Interaction,
+                     _new_exe_ctx : &ExecutionContext,
+                     _remaining_multi_trace : &Option<AnalysableMultiTrace>) {
+        match self.get_coverage_mut(model_action) {
+            None => {},
+            Some( cov ) => {
+                cov.fired = cov.fired + 1;
+            }
+        }
+    }
+
+    fn log_verdict(&mut self,
+                   _parent_state_id : u32,
+                   _verdict : &CoverageVerdict) {
+        // nothing to count
+    }
+
+    fn log_forget(&mut self,
+                  _state_id : u32) {
+        // nothing to count
+    }
+
+    fn log_filtered(&mut self,
+                    _gen_ctx : &GeneralContext,
+                    _exe_ctx:&ExecutionContext,
+                    _parent_state_id : u32,
+                    _new_state_id : u32,
+                    _action_position : &Position,
+                    _action : &ObservableAction,
+                    _elim_kind : &FilterEliminationKind) {
+        // actions eliminated by filters are neither fired nor evaluated
+    }
+
+    fn log_unsat(&mut self,
+                 _gen_ctx : &GeneralContext,
+                 _exe_ctx:&ExecutionContext,
+                 _parent_state_id : u32,
+                 _new_state_id : u32,
+                 _action_position : &Position,
+                 trace_action : Option<&TraceAction>,
+                 model_action : &ObservableAction) {
+        /*
+            When analysing, the firing may be unsatisfiable because the arguments or the delay
+            of the trace action do not match and not because of the guard
+            so only the unsatisfiable firings made without a trace action are counted
+        */
+        if trace_action.is_some() {
+            return;
+        }
+        match self.get_coverage_mut(model_action) {
+            None => {},
+            Some( cov ) => {
+                cov.unsat = cov.unsat + 1;
+            }
+        }
+    }
+}
@@ -12,8 +12,8 @@
 
 use std::collections::HashMap;
 use std::fs;
-use std::fs::File;
-use std::io::{Read,BufReader,BufRead,BufWriter,Write};
+use std::fs::{File,OpenOptions};
+use std::io::{Read,BufReader,BufRead,BufWriter,Write,Seek,SeekFrom};
 
 // ***
 use std::process::{Command,Output};
@@ -52,19 +52,28 @@ pub enum GraphicProcessLoggerKind {
 
 pub struct GraphicProcessLogger {
     log_name : String,
-    file : File,
+    file : Option<File>, // opened in 'log_init' or 'log_resume' depending on whether or not the process is resumed
     kind:GraphicProcessLoggerKind
 }
 
 impl GraphicProcessLogger {
     pub fn new(log_name : String,kind:GraphicProcessLoggerKind) -> GraphicProcessLogger {
-        let file = File::create(&format!("{:}.dot",log_name)).unwrap();
-        // ***
         return GraphicProcessLogger{
             log_name,
-            file,
+            file:None,
             kind}
     }
+
+    fn write_dot(&mut self, bytes : &[u8]) {
+        match self.file.as_mut() {
+            None => {
+                panic!("graphic logger used before being initialized");
+            },
+            Some( file ) => {
+                file.write( bytes );
+            }
+        }
+    }
 }
 
 impl ProcessLogger for GraphicProcessLogger {
@@ -100,7 +109,7 @@ impl ProcessLogger for GraphicProcessLogger {
         string_to_write.push_str(&gv_edge.to_dot_string());
         string_to_write.push_str("\n");
         // *****
-        self.file.write( string_to_write.as_bytes() );
+        self.write_dot( string_to_write.as_bytes() );
     }
 
     fn log_init(&mut self,
@@ -121,7 +130,9 @@ impl ProcessLogger for GraphicProcessLogger {
         // creates temp directory
         fs::create_dir_all("./temp").unwrap();
         // ***
-        self.file.write("digraph G {\n".as_bytes() );
+        // a new process starts a new graph
+        self.file = Some( OpenOptions::new().create(true).write(true).truncate(true).open(&format!("{:}.dot",self.log_name)).unwrap() );
+        self.write_dot("digraph G {\n".as_bytes() );
         // ***
         // ***
         let gv_node_path : String = format!("./temp/{:}_i1.png", self.log_name);
@@ -135,7 +146,62 @@ impl ProcessLogger for GraphicProcessLogger {
         let gv_node = GraphVizNode{id : "i1".to_owned(), style : node_gv_options};
         let mut string_to_write = gv_node.to_dot_string();
         string_to_write.push_str("\n");
-        self.file.write( string_to_write.as_bytes() );
+        self.write_dot( string_to_write.as_bytes() );
+    }
+
+    fn log_resume(&mut self,
+                  _interaction : &Interaction,
+                  _gen_ctx : &GeneralContext,
+                  _exe_ctx : &ExecutionContext,
+                  _remaining_multi_trace : &Option<AnalysableMultiTrace>,
+                  saved_state : &Vec<String>) -> Result<(),String> {
+        // continues the graph as it was written when the checkpoint was made
+        // what was written afterwards is removed given that it will be written again
+        // the temp directory containing the already drawn nodes is kept
+        let offset : u64;
+        match saved_state.get(0).map(|line| line.parse::<u64>()) {
+            Some( Ok(got) ) => {
+                offset = got;
+            },
+            _ => {
+                return Err( "no offset in graph file".to_string() );
+            }
+        }
+        let mut file : File;
+        match OpenOptions::new().write(true).open(&format!("{:}.dot",self.log_name)) {
+            Err(e) => {
+                return Err( format!("could not open graph file : {:}", e) );
+            },
+            Ok( got ) => {
+                file = got;
+            }
+        }
+        match file.metadata() {
+            Err(e) => {
+                return Err( format!("could not read graph file : {:}", e) );
+            },
+            Ok( metadata ) => {
+                if metadata.len() < offset {
+                    return Err( format!("graph file is shorter than when the checkpoint was made") );
+                }
+            }
+        }
+        file.set_len(offset).unwrap();
+        file.seek(SeekFrom::Start(offset)).unwrap();
+        self.file = Some(file);
+        fs::create_dir_all("./temp").unwrap();
+        return Ok(());
+    }
+
+    fn log_checkpoint(&mut self) -> Vec<String> {
+        match self.file.as_mut() {
+            None => {
+                return Vec::new();
+            },
+            Some( file ) => {
+                return vec![ file.seek(SeekFrom::Current(0)).unwrap().to_string() ];
+            }
+        }
     }
 
     fn log_term(&mut self,
@@ -157,10 +223,10 @@ impl ProcessLogger for GraphicProcessLogger {
             // ***
             let legend_node = GraphVizNode{id : "legend".to_owned(), style : legend_node_gv_options};
             let legend_as_dot_str = format!("{}\n", legend_node.to_dot_string());
-            self.file.write( legend_as_dot_str.as_bytes() );
+            self.write_dot( legend_as_dot_str.as_bytes() );
         }
         // ***
-        self.file.write( "}".as_bytes() );
+        self.write_dot( "}".as_bytes() );
         // ***
         match self.kind {
             GraphicProcessLoggerKind::png => {
@@ -254,16 +320,16 @@ impl ProcessLogger for GraphicProcessLogger {
             firing_gv_node_options.push(GraphvizNodeStyleItem::Label( "".to_string() ));
             firing_gv_node_options.push( GraphvizNodeStyleItem::Shape(GvNodeShape::Rectangle) );
             let firing_gv_node = GraphVizNode{id : firing_node_name.clone(), style : firing_gv_node_options};
-            self.file.write( firing_gv_node.to_dot_string().as_bytes() );
-            self.file.write("\n".as_bytes() );
+            self.write_dot( firing_gv_node.to_dot_string().as_bytes() );
+            self.write_dot("\n".as_bytes() );
         }
         // *** Transition To Firing
         {
             let mut tran_gv_options : GraphvizEdgeStyle = Vec::new();
             tran_gv_options.push( GraphvizEdgeStyleItem::Head( GvArrowHeadStyle::Vee(GvArrowHeadSide::Both) ) );
             let gv_edge = GraphVizEdge{origin_id : parent_interaction_node_name, target_id : firing_node_name.clone(), style : tran_gv_options};
-            self.file.write( gv_edge.to_dot_string().as_bytes() );
-            self.file.write("\n".as_bytes() );
+            self.write_dot( gv_edge.to_dot_string().as_bytes() );
+            self.write_dot("\n".as_bytes() );
         }
         // *** Resulting Interaction Node
         {
@@ -275,19 +341,24 @@ impl ProcessLogger for GraphicProcessLogger {
             node_gv_options.push(GraphvizNodeStyleItem::Label( "".to_string() ));
             node_gv_options.push( GraphvizNodeStyleItem::Shape(GvNodeShape::Rectangle) );
             let gv_node = GraphVizNode{id : current_node_name.clone(), style : node_gv_options};
-            self.file.write( gv_node.to_dot_string().as_bytes() );
-            self.file.write("\n".as_bytes() );
+            self.write_dot( gv_node.to_dot_string().as_bytes() );
+            self.write_dot("\n".as_bytes() );
         }
         // *** Transition To Interaction Node
         {
             let mut tran_gv_options : GraphvizEdgeStyle = Vec::new();
             tran_gv_options.push( GraphvizEdgeStyleItem::Head( GvArrowHeadStyle::Vee(GvArrowHeadSide::Both) ) );
             let gv_edge = GraphVizEdge{origin_id : firing_node_name, target_id : current_node_name, style : tran_gv_options};
-            self.file.write( gv_edge.to_dot_string().as_bytes() );
-            self.file.write("\n".as_bytes() );
+            self.write_dot( gv_edge.to_dot_string().as_bytes() );
+            self.write_dot("\n".as_bytes() );
         }
     }
 
+    fn log_forget(&mut self,
+                  _state_id : u32) {
+        // the process graph keeps every state
+    }
+
     fn log_filtered(&mut self,
                     gen_ctx : &GeneralContext,
                     exe_ctx:&ExecutionContext,
@@ -310,16 +381,16 @@ impl ProcessLogger for GraphicProcessLogger {
             firing_gv_node_options.push(GraphvizNodeStyleItem::Label( "".to_string() ));
             firing_gv_node_options.push( GraphvizNodeStyleItem::Shape(GvNodeShape::Rectangle) );
             let firing_gv_node = GraphVizNode{id : firing_node_name.clone(), style : firing_gv_node_options};
-            self.file.write( firing_gv_node.to_dot_string().as_bytes() );
-            self.file.write("\n".as_bytes() );
+            self.write_dot( firing_gv_node.to_dot_string().as_bytes() );
+            self.write_dot("\n".as_bytes() );
         }
         // *** Transition To Firing
         {
             let mut tran_gv_options : GraphvizEdgeStyle = Vec::new();
             tran_gv_options.push( GraphvizEdgeStyleItem::Head( GvArrowHeadStyle::Vee(GvArrowHeadSide::Both) ) );
             let gv_edge = GraphVizEdge{origin_id : parent_interaction_node_name, target_id : firing_node_name.clone(), style : tran_gv_options};
-            self.file.write( gv_edge.to_dot_string().as_bytes() );
-            self.file.write("\n".as_bytes() );
+            self.write_dot( gv_edge.to_dot_string().as_bytes() );
+            self.write_dot("\n".as_bytes() );
         }
         // *** Filtered Node
         {
@@ -335,8 +406,8 @@ impl ProcessLogger for GraphicProcessLogger {
             node_gv_options.push( GraphvizNodeStyleItem::Style(vec![GvNodeStyleKind::Filled]) );
 
             let gv_node = GraphVizNode{id : elim_node_name.clone(), style : node_gv_options};
-            self.file.write( gv_node.to_dot_string().as_bytes() );
-            self.file.write("\n".as_bytes() );
+            self.write_dot( gv_node.to_dot_string().as_bytes() );
+            self.write_dot("\n".as_bytes() );
         }
         // *** Transition To Filtered Node
         {
@@ -344,8 +415,8 @@ impl ProcessLogger for GraphicProcessLogger {
             tran_gv_options.push( GraphvizEdgeStyleItem::Head( GvArrowHeadStyle::Vee(GvArrowHeadSide::Both) ) );
             tran_gv_options.push( GraphvizEdgeStyleItem::Color( GraphvizColor::burlywood4 ) );
             let gv_edge = GraphVizEdge{origin_id : firing_node_name, target_id : elim_node_name, style : tran_gv_options};
-            self.file.write( gv_edge.to_dot_string().as_bytes() );
-            self.file.write("\n".as_bytes() );
+            self.write_dot( gv_edge.to_dot_string().as_bytes() );
+            self.write_dot("\n".as_bytes() );
         }
     }
 
@@ -378,16 +449,16 @@ impl ProcessLogger for GraphicProcessLogger {
             firing_gv_node_options.push(GraphvizNodeStyleItem::Label( "".to_string() ));
             firing_gv_node_options.push( GraphvizNodeStyleItem::Shape(GvNodeShape::Rectangle) );
             let firing_gv_node = GraphVizNode{id : firing_node_name.clone(), style : firing_gv_node_options};
-            self.file.write( firing_gv_node.to_dot_string().as_bytes() );
-            self.file.write("\n".as_bytes() );
+            self.write_dot( firing_gv_node.to_dot_string().as_bytes() );
+            self.write_dot("\n".as_bytes() );
         }
         // *** Transition To Firing
         {
             let mut tran_gv_options : GraphvizEdgeStyle = Vec::new();
             tran_gv_options.push( GraphvizEdgeStyleItem::Head( GvArrowHeadStyle::Vee(GvArrowHeadSide::Both) ) );
             let gv_edge = GraphVizEdge{origin_id : parent_interaction_node_name, target_id : firing_node_name.clone(), style : tran_gv_options};
-            self.file.write( gv_edge.to_dot_string().as_bytes() );
-            self.file.write("\n".as_bytes() );
+            self.write_dot( gv_edge.to_dot_string().as_bytes() );
+            self.write_dot("\n".as_bytes() );
         }
         // *** UNSAT Node
         {
@@ -403,8 +474,8 @@ impl ProcessLogger for GraphicProcessLogger {
             node_gv_options.push( GraphvizNodeStyleItem::Style(vec![GvNodeStyleKind::Filled]) );
 
             let gv_node = GraphVizNode{id : unsat_node_name.clone(), style : node_gv_options};
-            self.file.write( gv_node.to_dot_string().as_bytes() );
-            self.file.write("\n".as_bytes() );
+            self.write_dot( gv_node.to_dot_string().as_bytes() );
+            self.write_dot("\n".as_bytes() );
         }
         // *** Transition To UNSAT Node
         {
@@ -412,8 +483,8 @@ impl ProcessLogger for GraphicProcessLogger {
             tran_gv_options.push( GraphvizEdgeStyleItem::Head( GvArrowHeadStyle::Vee(GvArrowHeadSide::Both) ) );
             tran_gv_options.push( GraphvizEdgeStyleItem::Color( GraphvizColor::red4 ) );
             let gv_edge = GraphVizEdge{origin_id : firing_node_name, target_id : unsat_node_name, style : tran_gv_options};
-            self.file.write( gv_edge.to_dot_string().as_bytes() );
-            self.file.write("\n".as_bytes() );
+            self.write_dot( gv_edge.to_dot_string().as_bytes() );
+            self.write_dot("\n".as_bytes() );
         }
     }
 }
\ No newline at end of file
@@ -11,4 +11,6 @@
 
 
 pub mod graphic_logger;
+pub mod coverage_logger;
+pub mod traceability_logger;
 pub mod verdict;
\ No newline at end of file
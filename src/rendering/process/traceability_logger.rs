@@ -0,0 +1,486 @@
+/********************************************************************************
+ * Copyright (c) 2020 Erwan Mahe (github.com/erwanM974)
+ *
+ * This program and the accompanying materials are made available under the
+ * terms of the Eclipse Public License 2.0 which is available at
+ * http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+ * which is available at https://www.apache.org/licenses/LICENSE-2.0.
+ *
+ * SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+ ********************************************************************************/
+
+use std::collections::BTreeSet;
+use std::collections::btree_map::BTreeMap;
+use std::fs::File;
+use std::io::Write;
+
+// ***
+use crate::core::context::general::GeneralContext;
+use crate::core::context::execution::ExecutionContext;
+use crate::core::syntax::position::*;
+use crate::core::syntax::interaction::Interaction;
+use crate::core::trace::{AnalysableMultiTrace,TraceAction};
+use crate::core::syntax::action::*;
+
+use crate::process::log::ProcessLogger;
+use crate::process::verdicts::CoverageVerdict;
+use crate::process::hibou_process::FilterEliminationKind;
+use crate::process::lint::{collect_actions,action_to_short_text};
+use crate::process::fired_paths::FiredPaths;
+use crate::process::checkpoint::{original_position_to_checkpoint_item,parse_original_position};
+
+use crate::rendering::textual::monochrome::position::position_to_text;
+use crate::rendering::textual::monochrome::htf_trace::trace_action_to_htf_text;
+// ***
+
+struct TracedAction {
+    text : String,
+    tags : Vec<String>,
+    exercised : bool,
+    // indexes of the failing traces which stopped at this action
+    failing : Vec<usize>
+}
+
+/*
+    The action fired to reach a state
+*/
+struct FiredStep {
+    original_position : Option<Vec<u32>>,
+    text : String
+}
+
+/*
+    Relates the requirement tags of the initial interaction to the outcome of the process.
+    For each tag and each action carrying it (identified by its 'original_position'), the report tells :
+        - whether the action was exercised by an accepted trace
+          i.e. fired on a path ending in a 'Cov' or 'TooShort' verdict when analysing,
+          or fired outside of a forbidden behaviour when exploring
+        - which failing traces stopped at the action
+          i.e. the paths ending in an 'Out', 'AssertOut' or 'Neg' verdict whose last fired action is this one
+    The report is written both as a CSV file and as an HTML file.
+    The actions fired so far and what has been reported on them are saved in checkpoints, one item per line :
+        - "step <state_id> <parent_state_id> <forgotten> <original_position> <text>"
+        - "exercised <original_position>"
+        - "failing <text>", in the order of the indexes of the failing traces
+        - "blamed <original_position> <failing_trace_index>"
+*/
+pub struct TraceabilityProcessLogger {
+    log_name : String,
+    actions : BTreeMap<Vec<u32>,TracedAction>,
+    fired_from : FiredPaths<FiredStep>,
+    failing_traces : Vec<String>
+}
+
+impl TraceabilityProcessLogger {
+    pub fn new(log_name : String) -> TraceabilityProcessLogger {
+        return TraceabilityProcessLogger{log_name,
+            actions:BTreeMap::new(),
+            fired_from:FiredPaths::new(),
+            failing_traces:Vec::new()};
+    }
+
+    /*
+        Receptions deployed from an emission have the original position of the emission extended by one index
+        so that they are accounted for as the emission they originate from
+    */
+    fn get_traced_action_mut(&mut self, fired_position : &Vec<u32>) -> Option<&mut TracedAction> {
+        for (orig_pos,traced) in self.actions.iter_mut() {
+            if fired_position.starts_with(orig_pos) {
+                return Some( traced );
+            }
+        }
+        return None;
+    }
+
+    fn collect_traced_actions(&mut self, interaction : &Interaction, gen_ctx : &GeneralContext) {
+        self.actions = BTreeMap::new();
+        let mut actions : Vec<&ObservableAction> = Vec::new();
+        collect_actions(interaction, &mut actions);
+        for action in actions {
+            match &action.original_position {
+                None => {},
+                Some( orig_pos ) => {
+                    if action.tags.len() > 0 {
+                        let tags : Vec<String> = action.tags.iter().map(|tag_id| gen_ctx.get_tag(*tag_id).unwrap()).collect();
+                        self.actions.insert( orig_pos.clone(),
+                                             TracedAction{text:action_to_short_text(action,gen_ctx),
+                                                 tags,
+                                                 exercised:false,
+                                                 failing:Vec::new()} );
+                    }
+                }
+            }
+        }
+    }
+
+    fn resume_from_line(&mut self, line : &str, saved_steps : &mut Vec<(u32,u32,bool,FiredStep)>) -> Result<(),String> {
+        let items : Vec<&str> = line.splitn(2,' ').collect();
+        if items.len() != 2 {
+            return Err( format!("unexpected traceability item '{}'", line) );
+        }
+        match items[0] {
+            "step" => {
+                let step_items : Vec<&str> = items[1].splitn(5,' ').collect();
+                if step_items.len() != 5 {
+                    return Err( format!("unexpected traceability item '{}'", line) );
+                }
+                let (state_id,parent_state_id,forgotten) : (u32,u32,bool);
+                match (step_items[0].parse::<u32>(),step_items[1].parse::<u32>(),step_items[2]) {
+                    (Ok(got_state_id),Ok(got_parent_state_id),"0") => {
+                        state_id = got_state_id;
+                        parent_state_id = got_parent_state_id;
+                        forgotten = false;
+                    },
+                    (Ok(got_state_id),Ok(got_parent_state_id),"1") => {
+                        state_id = got_state_id;
+                        parent_state_id = got_parent_state_id;
+                        forgotten = true;
+                    },
+                    _ => {
+                        return Err( format!("unexpected traceability item '{}'", line) );
+                    }
+                }
+                let original_position : Option<Vec<u32>>;
+                if step_items[3] == NO_ORIGINAL_POSITION_ITEM {
+                    original_position = None;
+                } else {
+                    original_position = Some( parse_original_position(step_items[3])? );
+                }
+                saved_steps.push( (state_id,parent_state_id,forgotten,FiredStep{original_position,text:step_items[4].to_string()}) );
+            },
+            "exercised" => {
+                let orig_pos = parse_original_position(items[1])?;
+                match self.actions.get_mut(&orig_pos) {
+                    None => {
+                        return Err( format!("no traced action at {} in the model", original_position_to_text(&orig_pos)) );
+                    },
+                    Some( traced ) => {
+                        traced.exercised = true;
+                    }
+                }
+            },
+            "failing" => {
+                self.failing_traces.push( items[1].to_string() );
+            },
+            "blamed" => {
+                let blamed_items : Vec<&str> = items[1].split(' ').collect();
+                if blamed_items.len() != 2 {
+                    return Err( format!("unexpected traceability item '{}'", line) );
+                }
+                let orig_pos = parse_original_position(blamed_items[0])?;
+                let trace_id : usize;
+                match blamed_items[1].parse::<usize>() {
+                    Ok( got_trace_id ) if got_trace_id < self.failing_traces.len() => {
+                        trace_id = got_trace_id;
+                    },
+                    _ => {
+                        return Err( format!("unexpected traceability item '{}'", line) );
+                    }
+                }
+                match self.actions.get_mut(&orig_pos) {
+                    None => {
+                        return Err( format!("no traced action at {} in the model", original_position_to_text(&orig_pos)) );
+                    },
+                    Some( traced ) => {
+                        traced.failing.push( trace_id );
+                    }
+                }
+            },
+            _ => {
+                return Err( format!("unexpected traceability item '{}'", line) );
+            }
+        }
+        return Ok(());
+    }
+
+    fn get_rows(&self) -> Vec<(&String,&Vec<u32>,&TracedAction)> {
+        let mut all_tags : BTreeSet<&String> = BTreeSet::new();
+        for traced in self.actions.values() {
+            all_tags.extend( traced.tags.iter() );
+        }
+        let mut rows : Vec<(&String,&Vec<u32>,&TracedAction)> = Vec::new();
+        for tag in all_tags {
+            for (orig_pos,traced) in &self.actions {
+                if traced.tags.contains(tag) {
+                    rows.push( (tag,orig_pos,traced) );
+                }
+            }
+        }
+        return rows;
+    }
+
+    fn get_failing_texts(&self, traced : &TracedAction) -> Vec<&String> {
+        return traced.failing.iter().map(|trace_id| self.failing_traces.get(*trace_id).unwrap()).collect();
+    }
+
+    fn report_as_csv(&self) -> String {
+        let mut report = "requirement,position,action,exercised,failing traces\n".to_string();
+        for (tag,orig_pos,traced) in self.get_rows() {
+            let failing : Vec<String> = self.get_failing_texts(traced).iter().map(|s| s.to_string()).collect();
+            let fields = vec![tag.clone(),
+                              original_position_to_text(orig_pos),
+                              traced.text.clone(),
+                              traced.exercised.to_string(),
+                              failing.join(" ; ")];
+            let escaped : Vec<String> = fields.iter().map(|f| escape_csv_field(f)).collect();
+            report.push_str( &format!("{}\n", escaped.join(",")) );
+        }
+        return report;
+    }
+
+    fn report_as_html(&self, options_as_str : &Vec<String>) -> String {
+        let mut report = format!("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>traceability for '{}'</title>\n", escape_html(&self.log_name));
+        report.push_str( "<style>table{border-collapse:collapse;} th,td{border:1px solid black;padding:4px;} .ko{color:#d82626;} .ok{color:#165616;}</style>\n</head>\n<body>\n" );
+        report.push_str( &format!("<h1>traceability for '{}'</h1>\n<ul>\n", escape_html(&self.log_name)) );
+        for opt_str in options_as_str {
+            report.push_str( &format!("<li>{}</li>\n", escape_html(opt_str)) );
+        }
+        report.push_str( "</ul>\n<table>\n<tr><th>requirement</th><th>position</th><th>action</th><th>exercised</th><th>failing traces</th></tr>\n" );
+        for (tag,orig_pos,traced) in self.get_rows() {
+            let exercised_cell : String;
+            if traced.exercised {
+                exercised_cell = "<td class=\"ok\">yes</td>".to_string();
+            } else {
+                exercised_cell = "<td class=\"ko\">no</td>".to_string();
+            }
+            let failing : Vec<String> = self.get_failing_texts(traced).iter().map(|s| escape_html(s)).collect();
+            report.push_str( &format!("<tr><td>{}</td><td>{}</td><td>{}</td>{}<td class=\"ko\">{}</td></tr>\n",
+                                      escape_html(tag),
+                                      escape_html(&original_position_to_text(orig_pos)),
+                                      escape_html(&traced.text),
+                                      exercised_cell,
+                                      failing.join("<br>")) );
+        }
+        report.push_str( "</table>\n</body>\n</html>\n" );
+        return report;
+    }
+}
+
+static NO_ORIGINAL_POSITION_ITEM : &'static str = "-";
+
+fn original_position_to_text(orig_pos : &Vec<u32>) -> String {
+    return position_to_text( &Position::from_vec(&mut orig_pos.clone()) );
+}
+
+fn escape_csv_field(field : &str) -> String {
+    if field.contains(',') || field.contains('"') || field.contains('\n') {
+        return format!("\"{}\"", field.replace("\"", "\"\""));
+    } else {
+        return field.to_string();
+    }
+}
+
+fn escape_html(text : &str) -> String {
+    return text.replace("&", "&amp;").replace("<", "&lt;").replace(">", "&gt;").replace("\"", "&quot;");
+}
+
+fn write_report(file_name : &str, report : &str) {
+    match File::create(file_name) {
+        Err(e) => {
+            println!("could not create traceability report : {:?}", e);
+        },
+        Ok( mut file ) => {
+            match file.write_all( report.as_bytes() ) {
+                Err(e) => {
+                    println!("could not write traceability report : {:?}", e);
+                },
+                Ok(_) => {}
+            }
+        }
+    }
+}
+
+impl ProcessLogger for TraceabilityProcessLogger {
+
+    fn log_init(&mut self,
+                interaction : &Interaction,
+                gen_ctx : &GeneralContext,
+                _exe_ctx : &ExecutionContext,
+                _remaining_multi_trace : &Option<AnalysableMultiTrace>) {
+        self.collect_traced_actions(interaction, gen_ctx);
+        self.fired_from = FiredPaths::new();
+        self.failing_traces = Vec::new();
+    }
+
+    fn log_resume(&mut self,
+                  interaction : &Interaction,
+                  gen_ctx : &GeneralContext,
+                  _exe_ctx : &ExecutionContext,
+                  _remaining_multi_trace : &Option<AnalysableMultiTrace>,
+                  saved_state : &Vec<String>) -> Result<(),String> {
+        self.collect_traced_actions(interaction, gen_ctx);
+        self.failing_traces = Vec::new();
+        let mut saved_steps : Vec<(u32,u32,bool,FiredStep)> = Vec::new();
+        for line in saved_state {
+            self.resume_from_line(line, &mut saved_steps)?;
+        }
+        self.fired_from = FiredPaths::from_saved(saved_steps);
+        return Ok(());
+    }
+
+    fn log_checkpoint(&mut self) -> Vec<String> {
+        let mut lines : Vec<String> = Vec::new();
+        for (state_id,parent_state_id,forgotten,step) in self.fired_from.get_steps() {
+            let orig_pos_item : String;
+            match &step.original_position {
+                None => {
+                    orig_pos_item = NO_ORIGINAL_POSITION_ITEM.to_string();
+                },
+                Some( orig_pos ) => {
+                    orig_pos_item = original_position_to_checkpoint_item(orig_pos);
+                }
+            }
+            lines.push( format!("step {} {} {} {} {}", state_id, parent_state_id, forgotten as u32, orig_pos_item, step.text) );
+        }
+        for failing_text in &self.failing_traces {
+            lines.push( format!("failing {}", failing_text) );
+        }
+        for (orig_pos,traced) in &self.actions {
+            if traced.exercised {
+                lines.push( format!("exercised {}", original_position_to_checkpoint_item(orig_pos)) );
+            }
+            for trace_id in &traced.failing {
+                lines.push( format!("blamed {} {}", original_position_to_checkpoint_item(orig_pos), trace_id) );
+            }
+        }
+        return lines;
+    }
+
+    fn log_term(&mut self,
+                options_as_str : &Vec<String>) {
+        write_report( &format!("{:}_traceability.csv",self.log_name), &self.report_as_csv() );
+        write_report( &format!("{:}_traceability.html",self.log_name), &self.report_as_html(options_as_str) );
+    }
+
+    fn log_execution(&mut self,
+                     gen_ctx : &GeneralContext,
+                     parent_state_id : u32,
+                     new_state_id : u32,
+                     _action_position : &Position,
+                     trace_action : Option<&TraceAction>,
+                     model_action : &ObservableAction,
+                     new_interaction : &Interaction,
+                     _new_exe_ctx : &ExecutionContext,
+                     remaining_multi_trace : &Option<AnalysableMultiTrace>) {
+        // the traces are given by the consumed trace actions when analysing and by the fired actions when exploring
+        let step_text : String;
+        match trace_action {
+            None => {
+                step_text = action_to_short_text(model_action,gen_ctx);
+            },
+            Some( got_trace_action ) => {
+                match trace_action_to_htf_text(got_trace_action,gen_ctx) {
+                    Err(_) => {
+                        step_text = action_to_short_text(model_action,gen_ctx);
+                    },
+                    Ok( got_text ) => {
+                        step_text = got_text;
+                    }
+                }
+            }
+        }
+        self.fired_from.add_step( parent_state_id, new_state_id, FiredStep{original_position:model_action.original_position.clone(),
+            text:step_text} );
+        // when exploring, every path which does not exhibit a forbidden behaviour is the prefix of an accepted trace
+        if remaining_multi_trace.is_none() && !new_interaction.is_within_neg() {
+            match &model_action.original_position {
+                None => {},
+                Some( orig_pos ) => {
+                    match self.get_traced_action_mut(orig_pos) {
+                        None => {},
+                        Some( traced ) => {
+                            traced.exercised = true;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn log_verdict(&mut self,
+                   parent_state_id : u32,
+                   verdict : &CoverageVerdict) {
+        match verdict {
+            CoverageVerdict::Cov | CoverageVerdict::TooShort => {
+                let fired_positions : Vec<Vec<u32>> = self.fired_from.get_path_to(parent_state_id).iter()
+                    .filter_map(|step| step.original_position.clone()).collect();
+                for fired_pos in fired_positions {
+                    match self.get_traced_action_mut(&fired_pos) {
+                        None => {},
+                        Some( traced ) => {
+                            traced.exercised = true;
+                        }
+                    }
+                }
+            },
+            CoverageVerdict::Out | CoverageVerdict::AssertOut | CoverageVerdict::Neg => {
+                let last_position : Option<Vec<u32>>;
+                let failing_text : String;
+                {
+                    let path = self.fired_from.get_path_to(parent_state_id);
+                    match path.last() {
+                        None => {
+                            // no action was fired so no action is to blame
+                            return;
+                        },
+                        Some( last_step ) => {
+                            last_position = last_step.original_position.clone();
+                        }
+                    }
+                    let steps : Vec<String> = path.iter().map(|step| step.text.clone()).collect();
+                    failing_text = format!("{} after {}", verdict.to_string(), steps.join("."));
+                }
+                match last_position {
+                    None => {},
+                    Some( last_pos ) => {
+                        if self.get_traced_action_mut(&last_pos).is_none() {
+                            return;
+                        }
+                        let trace_id : usize;
+                        match self.failing_traces.iter().position(|t| *t == failing_text) {
+                            None => {
+                                self.failing_traces.push( failing_text );
+                                trace_id = self.failing_traces.len() - 1;
+                            },
+                            Some( got_trace_id ) => {
+                                trace_id = got_trace_id;
+                            }
+                        }
+                        let traced = self.get_traced_action_mut(&last_pos).unwrap();
+                        if !traced.failing.contains(&trace_id) {
+                            traced.failing.push( trace_id );
+                        }
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+
+    fn log_forget(&mut self,
+                  state_id : u32) {
+        self.fired_from.forget_state(state_id);
+    }
+
+    fn log_filtered(&mut self,
+                    _gen_ctx : &GeneralContext,
+                    _exe_ctx:&ExecutionContext,
+                    _parent_state_id : u32,
+                    _new_state_id : u32,
+                    _action_position : &Position,
+                    _action : &ObservableAction,
+                    _elim_kind : &FilterEliminationKind) {
+        // actions eliminated by filters are neither exercised nor failing
+    }
+
+    fn log_unsat(&mut self,
+                 _gen_ctx : &GeneralContext,
+                 _exe_ctx:&ExecutionContext,
+                 _parent_state_id : u32,
+                 _new_state_id : u32,
+                 _action_position : &Position,
+                 _trace_action : Option<&TraceAction>,
+                 _model_action : &ObservableAction) {
+        // unsatisfiable firings do not extend the traces
+    }
+}
@@ -29,6 +29,12 @@ impl CoverageVerdict {
             },
             CoverageVerdict::Out => {
                 return GraphvizColor::red3;
+            },
+            CoverageVerdict::AssertOut => {
+                return GraphvizColor::magenta3;
+            },
+            CoverageVerdict::Neg => {
+                return GraphvizColor::darkorchid;
             }
         }
     }
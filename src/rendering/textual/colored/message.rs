@@ -61,6 +61,25 @@ pub fn diagram_repr_message(ms_id : usize,
 }
 
 
+/*
+    Tags annotating an action, given after its message
+*/
+pub fn diagram_repr_tags(tags : &Vec<usize>,
+                         gen_ctx : &GeneralContext) -> Vec<TextToPrint> {
+    let mut to_print: Vec<TextToPrint> = Vec::new();
+    to_print.push( TextToPrint{text:SYNTAX_TAGS_START.to_string(),color:Rgb(HC_Grammar_Symbol)} );
+    let mut current_tag : usize = 0;
+    for tag_id in tags {
+        to_print.push( TextToPrint{text:gen_ctx.get_tag(*tag_id).unwrap(),color:Rgb(HC_Tag)} );
+        current_tag = current_tag +1;
+        if current_tag < tags.len() {
+            to_print.push( TextToPrint{text:",".to_string(),color:Rgb(HC_Grammar_Symbol)} );
+        }
+    }
+    to_print.push( TextToPrint{text:SYNTAX_TAGS_END.to_string(),color:Rgb(HC_Grammar_Symbol)} );
+    return to_print;
+}
+
 pub fn diagram_repr_raw_message(ms_id : usize,
                             arguments : &Vec<TD_Generic>,
                             gen_ctx : &GeneralContext,
@@ -24,6 +24,20 @@ use crate::rendering::textual::colored::message::{diagram_repr_message,diagram_r
 use crate::core::trace::{TraceAction,TraceActionKind};
 
 
+fn diagram_repr_communication_kind(com_kind : &CommunicationKind) -> Vec<TextToPrint> {
+    match com_kind {
+        CommunicationKind::Asynchronous => {
+            return Vec::new();
+        },
+        CommunicationKind::Call => {
+            return vec![ TextToPrint{text:SYNTAX_CALL_MARK.to_string(),color:Rgb(HC_Grammar_Symbol)} ];
+        },
+        CommunicationKind::Reply => {
+            return vec![ TextToPrint{text:SYNTAX_REPLY_MARK.to_string(),color:Rgb(HC_Grammar_Symbol)} ];
+        }
+    }
+}
+
 pub fn diagram_repr_atomic_trace_action(action : &TraceAction,
                                         gen_ctx : &GeneralContext,
                                         exe_ctx : &ExecutionContext) -> Vec<TextToPrint> {
@@ -53,6 +67,7 @@ pub fn diagram_repr_atomic_trace_action(action : &TraceAction,
             to_print.push( TextToPrint{text:SYNTAX_EMISSION.to_string(),color:Rgb(HC_Grammar_Symbol)} );
         }
     }
+    to_print.append( &mut diagram_repr_communication_kind(&action.com_kind) );
     // ***
     to_print.append( &mut diagram_repr_raw_message(action.ms_id, &action.arguments,gen_ctx,exe_ctx));
     // ***
@@ -79,6 +94,7 @@ pub fn diagram_repr_atomic_model_action(action : &ObservableAction,
             to_print.push( TextToPrint{text:SYNTAX_EMISSION.to_string(),color:Rgb(HC_Grammar_Symbol)} );
         }
     }
+    to_print.append( &mut diagram_repr_communication_kind(&action.com_kind) );
     // ***
     to_print.append( &mut diagram_repr_message(action.ms_id, &action.params,gen_ctx,exe_ctx));
     // ***
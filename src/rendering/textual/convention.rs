@@ -16,7 +16,13 @@ pub static SYNTAX_STRICT: &'static str = "strict";
 pub static SYNTAX_SEQ: &'static str = "seq";
 pub static SYNTAX_PAR: &'static str = "par";
 pub static SYNTAX_ALT: &'static str = "alt";
+pub static SYNTAX_OPT: &'static str = "opt";
+pub static SYNTAX_NEG: &'static str = "neg";
+pub static SYNTAX_ASSERT: &'static str = "assert";
 pub static SYNTAX_SCOPE: &'static str = "scope";
+pub static SYNTAX_CALL: &'static str = "call";
+pub static SYNTAX_CREATE: &'static str = "create";
+pub static SYNTAX_REF: &'static str = "ref";
 pub static SYNTAX_LOOP: &'static str = "loop_";
 pub static SYNTAX_LOOPX: &'static str = "loopX";
 pub static SYNTAX_LOOPH: &'static str = "loopH";
@@ -27,6 +33,8 @@ pub static SYNTAX_CONTINUE: &'static str = "continue";
 
 pub static SYNTAX_EMISSION: &'static str = "!";
 pub static SYNTAX_RECEPTION: &'static str = "?";
+pub static SYNTAX_CALL_MARK: &'static str = ">";
+pub static SYNTAX_REPLY_MARK: &'static str = "<";
 pub static SYNTAX_LOGGING: &'static str = ":";
 
 
@@ -36,6 +44,9 @@ pub static SYNTAX_SCOPE_START: &'static str = "{";
 pub static SYNTAX_SCOPE_SEPARATOR: &'static str = ",";
 pub static SYNTAX_SCOPE_END: &'static str = "}";
 
+pub static SYNTAX_TAGS_START: &'static str = " [";
+pub static SYNTAX_TAGS_END: &'static str = "]";
+
 
 
 
@@ -0,0 +1,115 @@
+/********************************************************************************
+ * Copyright (c) 2020 Erwan Mahe (github.com/erwanM974)
+ *
+ * This program and the accompanying materials are made available under the
+ * terms of the Eclipse Public License 2.0 which is available at
+ * http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+ * which is available at https://www.apache.org/licenses/LICENSE-2.0.
+ *
+ * SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+ ********************************************************************************/
+
+use crate::core::context::general::GeneralContext;
+use crate::core::error::HibouCoreError;
+use crate::core::trace::{TraceAction,TraceActionKind};
+use crate::core::syntax::action::CommunicationKind;
+use crate::core::syntax::data::generic::TD_Generic;
+use crate::core::syntax::data::builtin::bool::TD_Bool;
+use crate::core::syntax::data::builtin::integer::TD_Integer;
+use crate::core::syntax::data::builtin::float::TD_Float;
+use crate::core::syntax::data::builtin::string::TD_String;
+
+use crate::rendering::textual::convention::*;
+
+/*
+    Writes a trace over all lifelines in the syntax of hibou trace files (.hxtf)
+    so that it can be analyzed afterwards
+*/
+pub fn trace_to_htf_text(trace : &Vec<TraceAction>, gen_ctx : &GeneralContext) -> Result<String,HibouCoreError> {
+    let mut my_string = "[#all]".to_string();
+    let mut rem_actions = trace.len();
+    for trace_action in trace {
+        my_string.push_str("\n\t");
+        my_string.push_str( &trace_action_to_htf_text(trace_action,gen_ctx)? );
+        rem_actions = rem_actions - 1;
+        if rem_actions > 0 {
+            my_string.push_str(".");
+        }
+    }
+    my_string.push_str("\n");
+    return Ok(my_string);
+}
+
+pub fn trace_action_to_htf_text(trace_action : &TraceAction, gen_ctx : &GeneralContext) -> Result<String,HibouCoreError> {
+    let mut my_string = String::new();
+    match &trace_action.delay {
+        None => {},
+        Some( TD_Float::Value(delay) ) => {
+            my_string.push_str( &format!("[{}]", float_to_htf_text(*delay)) );
+        },
+        Some( _ ) => {
+            return Err( HibouCoreError::UnresolvedFreshValue("in the delay of a trace action".to_string()) );
+        }
+    }
+    my_string.push_str( &gen_ctx.get_lf_name(trace_action.lf_id)? );
+    match &trace_action.act_kind {
+        TraceActionKind::Emission => {
+            my_string.push_str(SYNTAX_EMISSION);
+        },
+        TraceActionKind::Reception => {
+            my_string.push_str(SYNTAX_RECEPTION);
+        }
+    }
+    match &trace_action.com_kind {
+        CommunicationKind::Asynchronous => {},
+        CommunicationKind::Call => {
+            my_string.push_str(SYNTAX_CALL_MARK);
+        },
+        CommunicationKind::Reply => {
+            my_string.push_str(SYNTAX_REPLY_MARK);
+        }
+    }
+    my_string.push_str( &gen_ctx.get_ms_name(trace_action.ms_id)? );
+    if trace_action.arguments.len() > 0 {
+        let mut args_strs : Vec<String> = Vec::new();
+        for argument in &trace_action.arguments {
+            args_strs.push( literal_to_htf_text(argument)? );
+        }
+        my_string.push_str( &format!("({})", args_strs.join(",")) );
+    }
+    return Ok(my_string);
+}
+
+fn literal_to_htf_text(td_gen : &TD_Generic) -> Result<String,HibouCoreError> {
+    match td_gen {
+        TD_Generic::Bool( TD_Bool::TRUE ) => {
+            return Ok( SYNTAX_LOGIC_TRUE.to_string() );
+        },
+        TD_Generic::Bool( TD_Bool::FALSE ) => {
+            return Ok( SYNTAX_LOGIC_FALSE.to_string() );
+        },
+        TD_Generic::Integer( TD_Integer::Value(value) ) => {
+            return Ok( value.to_string() );
+        },
+        TD_Generic::Float( TD_Float::Value(value) ) => {
+            return Ok( float_to_htf_text(*value) );
+        },
+        TD_Generic::String( TD_String::Value(value) ) => {
+            return Ok( format!("\"{}\"", value) );
+        },
+        _ => {
+            return Err( HibouCoreError::UnresolvedFreshValue("in the arguments of a trace action".to_string()) );
+        }
+    }
+}
+
+// floats in traces always have a decimal part
+// and are printed in full so that reading the trace back gives the same values
+fn float_to_htf_text(value : f64) -> String {
+    let text = format!("{}", value);
+    if text.contains('.') || !value.is_finite() {
+        return text;
+    } else {
+        return format!("{}.0", text);
+    }
+}
@@ -10,5 +10,6 @@
  ********************************************************************************/
 
 pub mod position;
+pub mod htf_trace;
 //pub mod short_action;
 //pub mod trace;
\ No newline at end of file
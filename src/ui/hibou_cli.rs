@@ -9,6 +9,8 @@
  * SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
  ********************************************************************************/
 
+use std::rc::Rc;
+use std::cell::RefCell;
 use std::collections::HashSet;
 use std::fs::write;
 use std::path::Path;
@@ -17,13 +19,15 @@ use clap::ArgMatches;
 
 
 use crate::core::context::general::GeneralContext;
+use crate::core::context::execution::ExecutionContext;
+use crate::core::syntax::interaction::Interaction;
 
 use crate::core::trace::*;
 
 
 
 
-use crate::rendering::custom_draw::seqdiag::interaction::draw_interaction;
+use crate::rendering::custom_draw::seqdiag::interaction::{draw_interaction,draw_interaction_coverage};
 
 
 use crate::rendering::process::graphic_logger::GraphicProcessLogger;
@@ -32,6 +36,12 @@ use crate::process::log::*;
 
 use crate::process::analysis::analyze;
 use crate::process::exploration::explore;
+use crate::process::checkpoint::{CheckpointOptions,ProcessCheckpoint};
+use crate::process::simulation::{SimulationOptions,simulate};
+use crate::process::lint::lint_model;
+use crate::process::model_coverage::{ModelCoverage,AcceptedPathsLogger};
+use crate::process::verdicts::GlobalVerdict;
+use crate::rendering::textual::monochrome::htf_trace::trace_to_htf_text;
 use crate::from_text::hsf_file::{ProcessKind,parse_hsf_file};
 use crate::from_text::htf_file::parse_htf_file;
 
@@ -51,6 +61,74 @@ fn get_ascii_left() -> Vec<&'static str> {
     return my_vec;
 }
 
+fn get_checkpoint_args(matches : &ArgMatches,
+                       checkpoint_file_path : String) -> Result<(Option<CheckpointOptions>,Option<ProcessCheckpoint>),String> {
+    let checkpoint_options : Option<CheckpointOptions>;
+    if matches.is_present("checkpoint") {
+        let period_str = matches.value_of("checkpoint").unwrap();
+        match period_str.parse::<u32>() {
+            Ok( period ) => {
+                if period == 0 {
+                    return Err( format!("checkpoint period must be strictly positive, got '{}'", period_str) );
+                }
+                checkpoint_options = Some( CheckpointOptions::new(checkpoint_file_path.clone(), period) );
+            },
+            Err(_) => {
+                return Err( format!("could not parse checkpoint period '{}'", period_str) );
+            }
+        }
+    } else {
+        checkpoint_options = None;
+    }
+    // ***
+    let resume_from : Option<ProcessCheckpoint>;
+    if matches.is_present("resume") {
+        match ProcessCheckpoint::read_from_file(&checkpoint_file_path) {
+            Ok( checkpoint ) => {
+                resume_from = Some(checkpoint);
+            },
+            Err(e) => {
+                return Err( e.to_string() );
+            }
+        }
+    } else {
+        resume_from = None;
+    }
+    // ***
+    return Ok( (checkpoint_options,resume_from) );
+}
+
+fn get_simulation_args(matches : &ArgMatches) -> Result<SimulationOptions,String> {
+    let runs : u32;
+    match matches.value_of("runs").unwrap_or("1").parse::<u32>() {
+        Ok( got ) => {
+            runs = got;
+        },
+        Err(_) => {
+            return Err( format!("could not parse number of runs '{}'", matches.value_of("runs").unwrap()) );
+        }
+    }
+    let seed : u64;
+    match matches.value_of("seed").unwrap_or("0").parse::<u64>() {
+        Ok( got ) => {
+            seed = got;
+        },
+        Err(_) => {
+            return Err( format!("could not parse seed '{}'", matches.value_of("seed").unwrap()) );
+        }
+    }
+    let max_length : u32;
+    match matches.value_of("length").unwrap_or("100").parse::<u32>() {
+        Ok( got ) => {
+            max_length = got;
+        },
+        Err(_) => {
+            return Err( format!("could not parse maximum trace length '{}'", matches.value_of("length").unwrap()) );
+        }
+    }
+    return Ok( SimulationOptions::new(runs,seed,max_length) );
+}
+
 fn print_retval(ret_print : Vec<String>) {
     let ascii_left = get_ascii_left();
     // ***
@@ -104,7 +182,11 @@ pub async fn hibou_cli() -> i32 {
                 ret_print.push( format!("from file '{}'",hsf_file_path) );
                 ret_print.push( format!("on file : {}",spec_output_file) );
                 ret_print.push( "".to_string());
-                draw_interaction(&spec_output_file, &my_int,&gen_ctx,&exe_ctx, &None);
+                if matches.is_present("expand_refs") {
+                    draw_interaction(&spec_output_file, &my_int.expand_references(),&gen_ctx,&exe_ctx, &None);
+                } else {
+                    draw_interaction(&spec_output_file, &my_int,&gen_ctx,&exe_ctx, &None);
+                }
             }
         }
     } else if let Some(matches) = matches.subcommand_matches("explore") {
@@ -116,19 +198,42 @@ pub async fn hibou_cli() -> i32 {
                 return -1;
             },
             Ok( (gen_ctx,exe_ctx,my_int,hoptions) ) => {
+                let file_name = Path::new(hsf_file_path).file_stem().unwrap().to_str().unwrap();
+                let (checkpoint_options,resume_from) : (Option<CheckpointOptions>,Option<ProcessCheckpoint>);
+                match get_checkpoint_args(matches, format!("{}.hcp", file_name)) {
+                    Err(e) => {
+                        ret_print.push( e );
+                        print_retval(ret_print);
+                        return -1;
+                    },
+                    Ok( got ) => {
+                        checkpoint_options = got.0;
+                        resume_from = got.1;
+                    }
+                }
                 // ***
                 ret_print.push( "".to_string());
                 ret_print.push( "EXPLORING SEMANTICS".to_string());
                 ret_print.push( format!("of interaction from file '{}'",hsf_file_path) );
                 ret_print.push( "".to_string());
                 // ***
-                explore(my_int,
+                match explore(my_int,
                         gen_ctx,exe_ctx,
                         hoptions.temporality,
+                        hoptions.engine,
                         hoptions.pre_filters,
                         hoptions.strategy,
                         hoptions.frontier_priorities,
-                        hoptions.loggers).await;
+                        hoptions.loggers,
+                        checkpoint_options,
+                        resume_from).await {
+                    Err(e) => {
+                        ret_print.push( e.to_string() );
+                        print_retval(ret_print);
+                        return -1;
+                    },
+                    Ok(_) => {}
+                }
             }
         }
     } else if let Some(matches) = matches.subcommand_matches("analyze") {
@@ -148,24 +253,214 @@ pub async fn hibou_cli() -> i32 {
                         return -1;
                     },
                     Ok( multi_trace ) => {
+                        let hsf_name = Path::new(hsf_file_path).file_stem().unwrap().to_str().unwrap();
+                        let htf_name = Path::new(htf_file_path).file_stem().unwrap().to_str().unwrap();
+                        let (checkpoint_options,resume_from) : (Option<CheckpointOptions>,Option<ProcessCheckpoint>);
+                        match get_checkpoint_args(matches, format!("{}_{}.hcp", hsf_name, htf_name)) {
+                            Err(e) => {
+                                ret_print.push( e );
+                                print_retval(ret_print);
+                                return -1;
+                            },
+                            Ok( got ) => {
+                                checkpoint_options = got.0;
+                                resume_from = got.1;
+                            }
+                        }
+                        // ***
                         ret_print.push( "ANALYZING TRACE".to_string());
                         ret_print.push( format!("from file '{}'",htf_file_path) );
                         ret_print.push( "W.R.T. INTERACTION".to_string());
                         ret_print.push( format!("from file '{}'",hsf_file_path) );
                         ret_print.push( "".to_string());
 
-                        let verdict = analyze(my_int,
+                        match analyze(my_int,
                                               multi_trace,
                                               gen_ctx,
                                               exe_ctx,
                                               hoptions.temporality,
+                                              hoptions.engine,
                                               hoptions.pre_filters,
                                               hoptions.strategy,
                                               hoptions.frontier_priorities,
                                               hoptions.loggers,
-                                              hoptions.goal.unwrap()).await;
-
-                        ret_print.push( format!("verdict: '{}'", verdict.to_string() ) );
+                                              hoptions.goal.unwrap(),
+                                              checkpoint_options,
+                                              resume_from).await {
+                            Err(e) => {
+                                ret_print.push( e.to_string() );
+                                print_retval(ret_print);
+                                return -1;
+                            },
+                            Ok( verdict ) => {
+                                ret_print.push( format!("verdict: '{}'", verdict.to_string() ) );
+                                match verdict.get_explanation() {
+                                    None => {},
+                                    Some( explanation ) => {
+                                        ret_print.push( format!("because {}", explanation ) );
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    } else if let Some(matches) = matches.subcommand_matches("coverage") {
+        let hsf_file_path = matches.value_of("hsf").unwrap();
+        let mut model_coverage : Option<(ModelCoverage,GeneralContext,ExecutionContext,Interaction)> = None;
+        ret_print.push( "".to_string());
+        ret_print.push( "MEASURING COVERAGE".to_string());
+        ret_print.push( format!("of interaction from file '{}'",hsf_file_path) );
+        ret_print.push( "".to_string());
+        for htf_file_path in matches.values_of("htf").unwrap() {
+            // the options, and the loggers they declare, are consumed by each analysis
+            match parse_hsf_file(hsf_file_path,&ProcessKind::Analyze) {
+                Err(e) => {
+                    ret_print.push( e.to_string() );
+                    print_retval(ret_print);
+                    return -1;
+                },
+                Ok( (gen_ctx,exe_ctx,my_int,mut hoptions) ) => {
+                    if model_coverage.is_none() {
+                        model_coverage = Some( (ModelCoverage::new(&my_int,&gen_ctx),gen_ctx.clone(),exe_ctx.clone(),my_int.clone()) );
+                    }
+                    let multi_trace : AnalysableMultiTrace;
+                    match parse_htf_file(htf_file_path,&gen_ctx,&hoptions.temporality) {
+                        Err(e) => {
+                            ret_print.push( e.to_string() );
+                            print_retval(ret_print);
+                            return -1;
+                        },
+                        Ok( got_multi_trace ) => {
+                            multi_trace = got_multi_trace;
+                        }
+                    }
+                    let fired_positions : Rc<RefCell<HashSet<Vec<u32>>>> = Rc::new(RefCell::new(HashSet::new()));
+                    hoptions.loggers.push( Box::new(AcceptedPathsLogger::new(fired_positions.clone())) );
+                    match analyze(my_int,
+                                  multi_trace,
+                                  gen_ctx,
+                                  exe_ctx,
+                                  hoptions.temporality,
+                                  hoptions.engine,
+                                  hoptions.pre_filters,
+                                  hoptions.strategy,
+                                  hoptions.frontier_priorities,
+                                  hoptions.loggers,
+                                  hoptions.goal.unwrap(),
+                                  None,
+                                  None).await {
+                        Err(e) => {
+                            ret_print.push( e.to_string() );
+                            print_retval(ret_print);
+                            return -1;
+                        },
+                        Ok( verdict ) => {
+                            ret_print.push( format!("trace from file '{}' : verdict '{}'", htf_file_path, verdict.to_string()) );
+                            match verdict {
+                                GlobalVerdict::Pass | GlobalVerdict::WeakPass => {
+                                    model_coverage.as_mut().unwrap().0.add_accepted_trace( &fired_positions.borrow() );
+                                },
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        // ***
+        let (model_coverage,gen_ctx,exe_ctx,my_int) = model_coverage.unwrap();
+        let file_name = Path::new(hsf_file_path).file_stem().unwrap().to_str().unwrap();
+        let coverage_output_file = format!("{}_coverage.png", file_name);
+        draw_interaction_coverage(&coverage_output_file, &my_int, &gen_ctx, &exe_ctx, &model_coverage.get_covered_action_positions());
+        ret_print.push( "".to_string());
+        ret_print.append( &mut model_coverage.report_lines() );
+        ret_print.push( "".to_string());
+        ret_print.push( format!("uncovered parts highlighted on file : {}",coverage_output_file) );
+    } else if let Some(matches) = matches.subcommand_matches("lint") {
+        let hsf_file_path = matches.value_of("hsf").unwrap();
+        match parse_hsf_file(hsf_file_path,&ProcessKind::None) {
+            Err(e) => {
+                ret_print.push( e.to_string() );
+                print_retval(ret_print);
+                return -1;
+            },
+            Ok( (gen_ctx,exe_ctx,my_int,_) ) => {
+                ret_print.push( "".to_string());
+                ret_print.push( "LINTING".to_string());
+                ret_print.push( format!("interaction from file '{}'",hsf_file_path) );
+                ret_print.push( "".to_string());
+                let warnings = lint_model(&my_int,&gen_ctx,&exe_ctx);
+                if warnings.len() == 0 {
+                    ret_print.push( "no warning".to_string() );
+                } else {
+                    ret_print.push( format!("{} warning(s) :", warnings.len()) );
+                    for warning in warnings {
+                        ret_print.push( warning.to_string() );
+                    }
+                }
+            }
+        }
+    } else if let Some(matches) = matches.subcommand_matches("simulate") {
+        let hsf_file_path = matches.value_of("hsf").unwrap();
+        match parse_hsf_file(hsf_file_path,&ProcessKind::None) {
+            Err(e) => {
+                ret_print.push( e.to_string() );
+                print_retval(ret_print);
+                return -1;
+            },
+            Ok( (gen_ctx,exe_ctx,my_int,_) ) => {
+                let sim_options : SimulationOptions;
+                match get_simulation_args(matches) {
+                    Err(e) => {
+                        ret_print.push( e );
+                        print_retval(ret_print);
+                        return -1;
+                    },
+                    Ok( got ) => {
+                        sim_options = got;
+                    }
+                }
+                // ***
+                ret_print.push( "".to_string());
+                ret_print.push( "SIMULATING".to_string());
+                ret_print.push( format!("interaction from file '{}'",hsf_file_path) );
+                ret_print.push( format!("with seed {}",sim_options.seed) );
+                ret_print.push( "".to_string());
+                // ***
+                match simulate(&my_int,&gen_ctx,&exe_ctx,&sim_options) {
+                    Err(e) => {
+                        ret_print.push( e.to_string() );
+                        print_retval(ret_print);
+                        return -1;
+                    },
+                    Ok( runs ) => {
+                        let file_name = Path::new(hsf_file_path).file_stem().unwrap().to_str().unwrap();
+                        let mut run_id : u32 = 1;
+                        for run in runs {
+                            let trace_file = format!("{}_sim{}.hxtf", file_name, run_id);
+                            match trace_to_htf_text(&run,&gen_ctx) {
+                                Err(e) => {
+                                    ret_print.push( e.to_string() );
+                                    print_retval(ret_print);
+                                    return -1;
+                                },
+                                Ok( trace_text ) => {
+                                    match write(&trace_file,trace_text) {
+                                        Err(e) => {
+                                            ret_print.push( format!("could not write run {} on file {} : {}",run_id,trace_file,e) );
+                                            print_retval(ret_print);
+                                            return -1;
+                                        },
+                                        Ok(_) => {
+                                            ret_print.push( format!("run {} of length {} on file : {}",run_id,run.len(),trace_file) );
+                                        }
+                                    }
+                                }
+                            }
+                            run_id = run_id + 1;
+                        }
                     }
                 }
             }
@@ -59,59 +59,35 @@ pub fn generate_xlia_model(gen_ctx : &GeneralContext,
     let mut trace_compare_actions : Vec<String> = Vec::new();
     let mut ms_id : usize = 0;
     for (ms_name,ms_args) in gen_ctx.get_ms_specs() {
-        //let mut signal_string : String = format!("\tsignal {}",ms_name);
-        let mut compare_action_string = format!("\tmachine action_compare_ms_{} {{\n", ms_name);
-        compare_action_string.push_str("\t@moe:\n");
-        compare_action_string.push_str("\t\t@run{\n");
-        match temporality {
-            HibouProcessTemporality::Timed => {
-                compare_action_string.push_str("\t\t\t// time does not flow in this action\n");
-                compare_action_string.push_str( "\t\t\tguard($delay == 0.0);\n" );
-                compare_action_string.push_str("\t\t\t// to compare timed trace delay\n");
-                compare_action_string.push_str( "\t\t\tguard( last_lf_compare_clock == trace_delay );\n" );
-            },
-            HibouProcessTemporality::UnTimed => {
-                // nothing
-            }
-        }
-        compare_action_string.push_str("\t\t\t// values of ms_M_pr_P kept from last symbolic step\n");
-        compare_action_string.push_str("\t\t\t// values of trace_ms_M_pr_P provided by HIBOU\n");
+        let mut compared_parameters : Vec<(String,String)> = Vec::new();
         let mut arg_count = 0;
-        if ms_args.len() > 0 {
-            for ( arg_type, _) in ms_args {
-                // ***
-                let xlia_arg_type = arg_type.as_xlia_str();
-                let parameter_diversity_name = message_parameter_diversity_name(gen_ctx,ms_id,arg_count);
-                variable_declaration.push_str(&format!("\tvar {} {};\n",xlia_arg_type,parameter_diversity_name));
-                let trace_parameter_diversity_name = trace_message_parameter_diversity_name(gen_ctx,ms_id,arg_count);
-                variable_declaration.push_str(&format!("\tvar {} {};\n",xlia_arg_type,trace_parameter_diversity_name));
-                // ***
-                compare_action_string.push_str( &format!("\t\t\tguard({} == {});\n", parameter_diversity_name, trace_parameter_diversity_name)  );
-                // ***
-                /*signal_string.push_str(&arg_type.as_xlia_str());
-                if arg_count < ms_args.len() {
-                    signal_string.push_str(",");
-                }*/
-                arg_count = arg_count + 1;
-            }
-            //signal_string.push_str(")");
+        for ( arg_type, _) in ms_args {
+            // ***
+            let xlia_arg_type = arg_type.as_xlia_str();
+            let parameter_diversity_name = message_parameter_diversity_name(gen_ctx,ms_id,arg_count);
+            variable_declaration.push_str(&format!("\tvar {} {};\n",xlia_arg_type,parameter_diversity_name));
+            let trace_parameter_diversity_name = trace_message_parameter_diversity_name(gen_ctx,ms_id,arg_count);
+            variable_declaration.push_str(&format!("\tvar {} {};\n",xlia_arg_type,trace_parameter_diversity_name));
+            // ***
+            compared_parameters.push( (parameter_diversity_name,trace_parameter_diversity_name) );
+            arg_count = arg_count + 1;
         }
+        trace_compare_actions.push( make_trace_compare_action(&format!("action_compare_ms_{}", ms_name), &compared_parameters, temporality) );
         // ***
-        match temporality {
-            HibouProcessTemporality::Timed => {
-                compare_action_string.push_str("\t\t\t// we now reset the last_lf_compare_clock because we are in the moment of the latest visible action on that lifeline \n");
-                compare_action_string.push_str("\t\t\tlast_lf_compare_clock := 0.0;\n");
-            },
-            HibouProcessTemporality::UnTimed => {
-                // nothing
+        match gen_ctx.get_ms_return_type(ms_id) {
+            None => {},
+            Some( return_type ) => {
+                // the reply of a synchronous call carries the returned value in variables of its own
+                let xlia_return_type = return_type.as_xlia_str();
+                let reply_diversity_name = reply_value_diversity_name(gen_ctx,ms_id);
+                variable_declaration.push_str(&format!("\tvar {} {};\n",xlia_return_type,reply_diversity_name));
+                let trace_reply_diversity_name = trace_reply_value_diversity_name(gen_ctx,ms_id);
+                variable_declaration.push_str(&format!("\tvar {} {};\n",xlia_return_type,trace_reply_diversity_name));
+                // ***
+                let compared_value = vec![(reply_diversity_name,trace_reply_diversity_name)];
+                trace_compare_actions.push( make_trace_compare_action(&format!("action_compare_rp_{}", ms_name), &compared_value, temporality) );
             }
         }
-        // ***
-        compare_action_string.push_str("\t\t}\n");
-        compare_action_string.push_str("\t}\n");
-        trace_compare_actions.push(compare_action_string);
-        //signal_string.push_str(";\n");
-        //xlia_model.push_str(&signal_string);
         ms_id = ms_id +1;
     }
 
@@ -161,6 +137,17 @@ pub fn generate_xlia_model(gen_ctx : &GeneralContext,
         let lf_name = gen_ctx.get_lf_name(lf_id).unwrap();
         let mut lifeline_string : String = format!("\tlifeline machine <or> {} {{\n",lf_name);
         // ***
+        if gen_ctx.is_created_lf(lf_id) {
+            match gen_ctx.get_lf_group(lf_id) {
+                None => {
+                    lifeline_string.push_str("\t\t// created by a '@create' fragment\n");
+                },
+                Some( lgr_id ) => {
+                    lifeline_string.push_str(&format!("\t\t// created by a '@create' fragment in lifeline group '{}'\n", gen_ctx.get_lgr_name(lgr_id).unwrap()) );
+                }
+            }
+        }
+        // ***
         lifeline_string.push_str("\t@public:\n");
         lifeline_string.push_str("\t\tport output hevent(string);\n");
         // ***
@@ -201,12 +188,55 @@ pub fn generate_xlia_model(gen_ctx : &GeneralContext,
 }
 
 
+/*
+    Action fired by HIBOU to compare the values of a trace action, provided in the trace_X variables,
+    with those computed by the last symbolic step
+*/
+fn make_trace_compare_action(machine_name : &str,
+                             compared_values : &Vec<(String,String)>,
+                             temporality : &HibouProcessTemporality) -> String {
+    let mut compare_action_string = format!("\tmachine {} {{\n", machine_name);
+    compare_action_string.push_str("\t@moe:\n");
+    compare_action_string.push_str("\t\t@run{\n");
+    match temporality {
+        HibouProcessTemporality::Timed => {
+            compare_action_string.push_str("\t\t\t// time does not flow in this action\n");
+            compare_action_string.push_str( "\t\t\tguard($delay == 0.0);\n" );
+            compare_action_string.push_str("\t\t\t// to compare timed trace delay\n");
+            compare_action_string.push_str( "\t\t\tguard( last_lf_compare_clock == trace_delay );\n" );
+        },
+        HibouProcessTemporality::UnTimed => {
+            // nothing
+        }
+    }
+    compare_action_string.push_str("\t\t\t// compared values kept from last symbolic step\n");
+    compare_action_string.push_str("\t\t\t// their trace counterparts are provided by HIBOU\n");
+    for (model_value_name,trace_value_name) in compared_values {
+        compare_action_string.push_str( &format!("\t\t\tguard({} == {});\n", model_value_name, trace_value_name)  );
+    }
+    // ***
+    match temporality {
+        HibouProcessTemporality::Timed => {
+            compare_action_string.push_str("\t\t\t// we now reset the last_lf_compare_clock because we are in the moment of the latest visible action on that lifeline \n");
+            compare_action_string.push_str("\t\t\tlast_lf_compare_clock := 0.0;\n");
+        },
+        HibouProcessTemporality::UnTimed => {
+            // nothing
+        }
+    }
+    // ***
+    compare_action_string.push_str("\t\t}\n");
+    compare_action_string.push_str("\t}\n");
+    return compare_action_string;
+}
+
 fn update_xlia_lifelines_from_lf_act(gen_ctx : &GeneralContext,
                                          exe_ctx : &ExecutionContext,
                                          lf_id : usize,
                                          preamble : &Vec<ActionAmbleItem>,
                                          postamble : &Vec<ActionAmbleItem>,
                                          ms_id : usize,
+                                         com_kind : &CommunicationKind,
                                          params : &Vec<ValueOrNewFresh>,
                                          is_emission : bool,
                                          is_target : bool,
@@ -256,7 +286,7 @@ fn update_xlia_lifelines_from_lf_act(gen_ctx : &GeneralContext,
         xlia_action_str.push_str("\t\t\t// Emission - values of ms_M_pr_P computed by DIVERSITY - later queried by HIBOU\n");
         //let mut params_diversity_names : Vec<String> =
         for idx in 0..params.len() {
-            let parameter_diversity_name = message_parameter_diversity_name(gen_ctx,ms_id,idx);
+            let parameter_diversity_name = action_parameter_diversity_name(gen_ctx,ms_id,com_kind,idx);
             let param_val = params.get(idx).unwrap();
             match param_val {
                 ValueOrNewFresh::NewFresh => {
@@ -272,7 +302,7 @@ fn update_xlia_lifelines_from_lf_act(gen_ctx : &GeneralContext,
     } else {
         xlia_action_str.push_str("\t\t\t// Reception - values of ms_M_pr_P provided by HIBOU -\n");
         for idx in 0..params.len() {
-            let parameter_diversity_name = message_parameter_diversity_name(gen_ctx,ms_id,idx);
+            let parameter_diversity_name = action_parameter_diversity_name(gen_ctx,ms_id,com_kind,idx);
             let param_val = params.get(idx).unwrap();
             match param_val {
                 ValueOrNewFresh::NewFresh => {
@@ -303,7 +333,17 @@ fn update_xlia_lifelines_from_lf_act(gen_ctx : &GeneralContext,
                         xlia_action_str.push_str( &format!("\t\t\t{} = newfresh({});\n", vr_complete_name_within_array, vr_base_for_newfresh_name) );
                     },
                     ValueOrNewFresh::Value( td_gen ) => {
-                        xlia_action_str.push_str( &format!("\t\t\t{} = {};\n",variable_diversity_name(gen_ctx,*vr_id),td_generic_to_xlia(gen_ctx,td_gen))   );
+                        let value_xlia_str : String;
+                        match com_kind {
+                            CommunicationKind::Reply => {
+                                // the post-amble of a reply only assigns the returned value on the caller
+                                value_xlia_str = reply_value_diversity_name(gen_ctx,ms_id);
+                            },
+                            _ => {
+                                value_xlia_str = td_generic_to_xlia(gen_ctx,td_gen);
+                            }
+                        }
+                        xlia_action_str.push_str( &format!("\t\t\t{} = {};\n",variable_diversity_name(gen_ctx,*vr_id),value_xlia_str)   );
                     }
                 }
             },
@@ -346,7 +386,7 @@ fn generate_xlia_lifelines(gen_ctx : &GeneralContext,
             match &obs_act.act_kind {
                 ObservableActionKind::Emission( targets ) => {
                     update_xlia_lifelines_from_lf_act(gen_ctx,exe_ctx,obs_act.lf_act.lf_id,
-                                                      &obs_act.lf_act.preamble,&obs_act.lf_act.postamble,obs_act.ms_id,&obs_act.params,true,false,
+                                                      &obs_act.lf_act.preamble,&obs_act.lf_act.postamble,obs_act.ms_id,&obs_act.com_kind,&obs_act.params,true,false,
                                                       lifelines_actions,relative_position.clone());
                     // ***
                     let mut target_counter : u32 = 1;
@@ -355,23 +395,25 @@ fn generate_xlia_lifelines(gen_ctx : &GeneralContext,
                         rel_pos.push(target_counter );
                         target_counter = target_counter +1;
                         update_xlia_lifelines_from_lf_act(gen_ctx,exe_ctx,target.lf_id,
-                                                          &target.preamble,&target.postamble,obs_act.ms_id,&obs_act.params,false,true,
+                                                          &target.preamble,&target.postamble,obs_act.ms_id,&obs_act.com_kind,&obs_act.params,false,true,
                                                           lifelines_actions,rel_pos);
                     }
                 },
                 ObservableActionKind::Reception => {
                     update_xlia_lifelines_from_lf_act(gen_ctx,exe_ctx,obs_act.lf_act.lf_id,
-                                                      &obs_act.lf_act.preamble,&obs_act.lf_act.postamble,obs_act.ms_id,&obs_act.params,false,false,
+                                                      &obs_act.lf_act.preamble,&obs_act.lf_act.postamble,obs_act.ms_id,&obs_act.com_kind,&obs_act.params,false,false,
                                                       lifelines_actions,relative_position.clone());
                 }
             }
         },
-        Interaction::Scope(_,sub_interaction) => {
+        // the actions of forbidden behaviours are generated as well since HIBOU fires them to detect these behaviours
+        Interaction::Scope(_,sub_interaction) | Interaction::Call(_,_,sub_interaction) | Interaction::Assert(_,sub_interaction)
+                | Interaction::Neg(_,sub_interaction) | Interaction::Create(_,sub_interaction) | Interaction::Ref(_,sub_interaction) => {
             let mut rel_pos = relative_position.clone();
             rel_pos.push(1 );
             generate_xlia_lifelines(gen_ctx,exe_ctx,sub_interaction,lifelines_actions,rel_pos);
         },
-        Interaction::Loop(_,sub_interaction) => {
+        Interaction::Loop(_,_,sub_interaction) | Interaction::Opt(sub_interaction) | Interaction::Break(_,sub_interaction) => {
             let mut rel_pos = relative_position.clone();
             rel_pos.push(1 );
             generate_xlia_lifelines(gen_ctx,exe_ctx,sub_interaction,lifelines_actions,rel_pos);
@@ -33,6 +33,31 @@ pub fn trace_message_parameter_diversity_name(gen_ctx : &GeneralContext, ms_id :
     return format!("trace_ms_{}_pr_{}",ms_name,pr_id);
 }
 
+/*
+    The value returned by a synchronous call, carried by its reply,
+    is of the return type of the message and thus has variables of its own
+*/
+pub fn reply_value_diversity_name(gen_ctx : &GeneralContext, ms_id : usize) -> String {
+    let ms_name= gen_ctx.get_ms_name(ms_id).unwrap();
+    return format!("rp_{}_val",ms_name);
+}
+
+pub fn trace_reply_value_diversity_name(gen_ctx : &GeneralContext, ms_id : usize) -> String {
+    let ms_name= gen_ctx.get_ms_name(ms_id).unwrap();
+    return format!("trace_rp_{}_val",ms_name);
+}
+
+pub fn action_parameter_diversity_name(gen_ctx : &GeneralContext, ms_id : usize, com_kind : &CommunicationKind, pr_id : usize) -> String {
+    match com_kind {
+        CommunicationKind::Reply => {
+            return reply_value_diversity_name(gen_ctx,ms_id);
+        },
+        _ => {
+            return message_parameter_diversity_name(gen_ctx,ms_id,pr_id);
+        }
+    }
+}
+
 pub fn variable_array_index_diversity_name(gen_ctx : &GeneralContext, vr_id : usize) -> String {
     let vr_name : String = gen_ctx.get_vr_name(vr_id).unwrap();
     return format!("index_{}",vr_name);